@@ -1,4 +1,5 @@
 #include "Graphics/Rect.hpp"
+#include "Graphics/View.hpp"
 #include "Graphics/Color.hpp"
 #include "SFML/Window/ContextSettings.hpp"
 #include "System/Vector2.hpp"
@@ -43,20 +44,20 @@ extern "C" void sfRenderTexture_clear(sf::RenderTexture *renderTexture, sfColor
     renderTexture->clear(sf::Color(color.r, color.g, color.b, color.a));
 }
 
-extern "C" void sfRenderTexture_setView(sf::RenderTexture *renderTexture, const sf::View *view) {
-    renderTexture->setView(*view);
+extern "C" void sfRenderTexture_setView(sf::RenderTexture *renderTexture, const sfView *view) {
+    renderTexture->setView(convertView(view));
 }
 
-extern "C" const sf::View *sfRenderTexture_getView(const sf::RenderTexture *renderTexture) {
-    return &renderTexture->getView();
+extern "C" sfView sfRenderTexture_getView(const sf::RenderTexture *renderTexture) {
+    return convertView(renderTexture->getView());
 }
 
-extern "C" const sf::View *sfRenderTexture_getDefaultView(const sf::RenderTexture *renderTexture) {
-    return &renderTexture->getDefaultView();
+extern "C" sfView sfRenderTexture_getDefaultView(const sf::RenderTexture *renderTexture) {
+    return convertView(renderTexture->getDefaultView());
 }
 
-extern "C" sfIntRect sfRenderTexture_getViewport(const sf::RenderTexture *renderTexture, const sf::View *view) {
-    sf::IntRect rect = renderTexture->getViewport(*view);
+extern "C" sfIntRect sfRenderTexture_getViewport(const sf::RenderTexture *renderTexture, const sfView *view) {
+    sf::IntRect rect = renderTexture->getViewport(convertView(view));
     return {rect.left, rect.top, rect.width, rect.height};
 }
 
@@ -65,8 +66,8 @@ extern "C" sfVector2f sfRenderTexture_mapPixelToCoords(const sf::RenderTexture *
     return {result.x, result.y};
 }
 
-extern "C" sfVector2f sfRenderTexture_mapPixelToCoords_View(const sf::RenderTexture *renderTexture, sfVector2i point, const sf::View *targetView) {
-    sf::Vector2f result = renderTexture->mapPixelToCoords(sf::Vector2i(point.x, point.y), *targetView);
+extern "C" sfVector2f sfRenderTexture_mapPixelToCoords_View(const sf::RenderTexture *renderTexture, sfVector2i point, const sfView *targetView) {
+    sf::Vector2f result = renderTexture->mapPixelToCoords(sf::Vector2i(point.x, point.y), convertView(targetView));
     return {result.x, result.y};
 }
 
@@ -75,8 +76,8 @@ extern "C" sfVector2i sfRenderTexture_mapCoordsToPixel(const sf::RenderTexture *
     return {result.x, result.y};
 }
 
-extern "C" sfVector2i sfRenderTexture_mapCoordsToPixel_View(const sf::RenderTexture *renderTexture, sfVector2f point, const sf::View *targetView) {
-    sf::Vector2i result = renderTexture->mapCoordsToPixel(sf::Vector2f(point.x, point.y), *targetView);
+extern "C" sfVector2i sfRenderTexture_mapCoordsToPixel_View(const sf::RenderTexture *renderTexture, sfVector2f point, const sfView *targetView) {
+    sf::Vector2i result = renderTexture->mapCoordsToPixel(sf::Vector2f(point.x, point.y), convertView(targetView));
     return {result.x, result.y};
 }
 
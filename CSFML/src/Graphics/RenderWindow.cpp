@@ -1,4 +1,5 @@
 #include "Graphics/Rect.hpp"
+#include "Graphics/View.hpp"
 #include "Graphics/Color.hpp"
 #include "System/Vector2.hpp"
 #include "Window/VideoMode.hpp"
@@ -139,20 +140,20 @@ extern "C" void sfRenderWindow_clear(sf::RenderWindow *renderWindow, sfColor col
     renderWindow->clear(sf::Color(color.r, color.g, color.b, color.a));
 }
 
-extern "C" void sfRenderWindow_setView(sf::RenderWindow *renderWindow, const sf::View *view) {
-    renderWindow->setView(*view);
+extern "C" void sfRenderWindow_setView(sf::RenderWindow *renderWindow, const sfView *view) {
+    renderWindow->setView(convertView(view));
 }
 
-extern "C" const sf::View *sfRenderWindow_getView(const sf::RenderWindow *renderWindow) {
-    return &renderWindow->getView();
+extern "C" sfView sfRenderWindow_getView(const sf::RenderWindow *renderWindow) {
+    return convertView(renderWindow->getView());
 }
 
-extern "C" const sf::View *sfRenderWindow_getDefaultView(const sf::RenderWindow *renderWindow) {
-    return &renderWindow->getDefaultView();
+extern "C" sfView sfRenderWindow_getDefaultView(const sf::RenderWindow *renderWindow) {
+    return convertView(renderWindow->getDefaultView());
 }
 
-extern "C" sfIntRect sfRenderWindow_getViewport(const sf::RenderWindow *renderWindow, const sf::View *view) {
-    sf::IntRect rect = renderWindow->getViewport(*view);
+extern "C" sfIntRect sfRenderWindow_getViewport(const sf::RenderWindow *renderWindow, const sfView *view) {
+    sf::IntRect rect = renderWindow->getViewport(convertView(view));
     return {rect.left, rect.top, rect.width, rect.height};
 }
 
@@ -161,8 +162,8 @@ extern "C" sfVector2f sfRenderWindow_mapPixelToCoords(const sf::RenderWindow *re
     return {vec2.x, vec2.y};
 }
 
-extern "C" sfVector2f sfRenderWindow_mapPixelToCoords_View(const sf::RenderWindow *renderWindow, sfVector2i point, const sf::View *targetView) {
-    sf::Vector2f vec2 = renderWindow->mapPixelToCoords(sf::Vector2i(point.x, point.y), *targetView);
+extern "C" sfVector2f sfRenderWindow_mapPixelToCoords_View(const sf::RenderWindow *renderWindow, sfVector2i point, const sfView *targetView) {
+    sf::Vector2f vec2 = renderWindow->mapPixelToCoords(sf::Vector2i(point.x, point.y), convertView(targetView));
     return {vec2.x, vec2.y};
 }
 
@@ -171,8 +172,8 @@ extern "C" sfVector2i sfRenderWindow_mapCoordsToPixel(const sf::RenderWindow *re
     return {vec2.x, vec2.y};
 }
 
-extern "C" sfVector2i sfRenderWindow_mapCoordsToPixel_View(const sf::RenderWindow *renderWindow, sfVector2f point, const sf::View *targetView) {
-    sf::Vector2i vec2 = renderWindow->mapCoordsToPixel(sf::Vector2f(point.x, point.y), *targetView);
+extern "C" sfVector2i sfRenderWindow_mapCoordsToPixel_View(const sf::RenderWindow *renderWindow, sfVector2f point, const sfView *targetView) {
+    sf::Vector2i vec2 = renderWindow->mapCoordsToPixel(sf::Vector2f(point.x, point.y), convertView(targetView));
     return {vec2.x, vec2.y};
 }
 
@@ -1,8 +1,15 @@
 #include "System/Vector2.hpp"
 #include "System/Vector3.hpp"
 #include <SFML/Graphics/Shader.hpp>
+#include <SFML/OpenGL.hpp>
+#include <SFML/Window/Context.hpp>
+#include <SFML/Window/GlResource.hpp>
 #include <cstddef>
 
+#ifndef APIENTRY
+#define APIENTRY
+#endif
+
 // 2D vectors
 typedef sfVector2f sfGlslVec2;
 typedef sfVector2i sfGlslIvec2;
@@ -182,6 +189,25 @@ extern "C" unsigned int sfShader_getNativeHandle(const sf::Shader *shader) {
     return shader->getNativeHandle();
 }
 
+namespace {
+// TransientContextLock is only accessible to GlResource subclasses. It activates
+// SFML's shared context if no context is active on the thread, and does nothing otherwise.
+struct ContextLock : sf::GlResource {
+    TransientContextLock lock;
+};
+} // namespace
+
+// Returns -1 if the uniform doesn't exist, or -2 if there is no way to find out
+extern "C" int sfShader_getUniformLocation(const sf::Shader *shader, const char *name) {
+    typedef GLint(APIENTRY * GetUniformLocation)(GLuint, const char *);
+    ContextLock lock;
+    GetUniformLocation getUniformLocation = reinterpret_cast<GetUniformLocation>(sf::Context::getFunction("glGetUniformLocation"));
+    if (!getUniformLocation) {
+        return -2;
+    }
+    return getUniformLocation(shader->getNativeHandle(), name);
+}
+
 extern "C" void sfShader_bind(const sf::Shader *shader) {
     sf::Shader::bind(shader);
 }
@@ -0,0 +1,30 @@
+#ifndef SFML_VIEW_H
+#define SFML_VIEW_H
+
+#include "Graphics/Rect.hpp"
+#include "System/Vector2.hpp"
+#include <SFML/Graphics/View.hpp>
+
+// Mirrors the layout of the Rust `View` value type
+struct sfView {
+    sfVector2f center;
+    sfVector2f size;
+    float rotation;
+    sfFloatRect viewport;
+};
+
+inline sf::View convertView(const sfView *view) {
+    sf::View sfmlView(sf::Vector2f(view->center.x, view->center.y), sf::Vector2f(view->size.x, view->size.y));
+    sfmlView.setRotation(view->rotation);
+    sfmlView.setViewport(sf::FloatRect(view->viewport.left, view->viewport.top, view->viewport.width, view->viewport.height));
+    return sfmlView;
+}
+
+inline sfView convertView(const sf::View &view) {
+    sf::Vector2f center = view.getCenter();
+    sf::Vector2f size = view.getSize();
+    sf::FloatRect viewport = view.getViewport();
+    return {{center.x, center.y}, {size.x, size.y}, view.getRotation(), {viewport.left, viewport.top, viewport.width, viewport.height}};
+}
+
+#endif // SFML_VIEW_H
@@ -0,0 +1,110 @@
+#include <SFML/System/Err.hpp>
+#include <atomic>
+#include <cstddef>
+#include <cstdint>
+#include <cstdio>
+#include <streambuf>
+#include <string>
+#include <utility>
+
+typedef void (*sfErrCallback)(const char *message, size_t len);
+
+namespace {
+std::atomic<sfErrCallback> callback{nullptr};
+// SFML may write to sf::err() from any thread, so keep per-thread state
+thread_local std::string pending;
+thread_local bool pendingHeld = false;
+thread_local std::string lastMessage;
+thread_local bool hasLastMessage = false;
+thread_local uint64_t messageCount = 0;
+
+// Hand the pending message to Rust, or to stderr if no callback is set.
+void emitPending() {
+    size_t end = pending.find_last_not_of("\r\n ");
+    pendingHeld = false;
+    if (end == std::string::npos) {
+        pending.clear();
+        return;
+    }
+    lastMessage.assign(pending, 0, end + 1);
+    hasLastMessage = true;
+    ++messageCount;
+    pending.clear();
+    sfErrCallback cb = callback.load();
+    if (cb) {
+        cb(lastMessage.data(), lastMessage.size());
+    } else {
+        fwrite(lastMessage.data(), 1, lastMessage.size(), stderr);
+        fputc('\n', stderr);
+    }
+}
+
+// Emit a message held back by RustErrStreamBuf::sync, if nothing completed it.
+void flushHeld() {
+    if (pendingHeld) {
+        emitPending();
+    }
+}
+
+// Collects everything written to sf::err() until it's flushed (usually by std::endl),
+// then emits it as a single message.
+class RustErrStreamBuf : public std::streambuf {
+  protected:
+    int overflow(int character) override {
+        if (character == traits_type::eof()) {
+            return sync();
+        }
+        pending.push_back(static_cast<char>(character));
+        return character;
+    }
+
+    std::streamsize xsputn(const char *s, std::streamsize n) override {
+        pending.append(s, static_cast<size_t>(n));
+        return n;
+    }
+
+    int sync() override {
+        size_t end = pending.find_last_not_of("\r\n ");
+        // Headers like "Failed to compile fragment shader:" are flushed before
+        // the actual log, so hold them until the next flush, which emits them
+        // along with whatever was written in between.
+        if (end != std::string::npos && pending[end] == ':' && !pendingHeld) {
+            pendingHeld = true;
+            return 0;
+        }
+        emitPending();
+        return 0;
+    }
+};
+
+struct ErrRedirect {
+    RustErrStreamBuf buf;
+    std::streambuf *original;
+    ErrRedirect() {
+        original = sf::err().rdbuf(&buf);
+    }
+    ~ErrRedirect() {
+        sf::err().rdbuf(original);
+    }
+};
+
+ErrRedirect redirect;
+} // namespace
+
+extern "C" void sfErr_setCallback(sfErrCallback cb) {
+    callback.store(cb);
+}
+
+extern "C" std::string *sfErr_takeLastMessage() {
+    flushHeld();
+    if (!hasLastMessage) {
+        return nullptr;
+    }
+    hasLastMessage = false;
+    return new std::string(std::move(lastMessage));
+}
+
+extern "C" uint64_t sfErr_messageCount() {
+    flushHeld();
+    return messageCount;
+}
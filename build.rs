@@ -61,6 +61,7 @@ fn main() {
     build.files(
         [
             "CSFML/src/System/Clock.cpp",
+            "CSFML/src/System/Err.cpp",
             "CSFML/src/System/Sleep.cpp",
             "CSFML/src/System/SfString.cpp",
             "CSFML/src/System/SfStdString.cpp",
@@ -82,9 +83,7 @@ fn main() {
             "CSFML/src/Graphics/Shader.cpp",
             "CSFML/src/Graphics/Sprite.cpp",
             "CSFML/src/Graphics/Texture.cpp",
-            "CSFML/src/Graphics/Transform.cpp",
             "CSFML/src/Graphics/VertexBuffer.cpp",
-            "CSFML/src/Graphics/View.cpp",
         ]
         .iter(),
     );
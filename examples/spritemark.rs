@@ -85,7 +85,7 @@ fn main() -> SfResult<()> {
     let mut sec_clock = Clock::start()?;
     let mut fps = 0;
     let mut lmb_down = false;
-    let mut view = View::new()?;
+    let mut view = View::default();
 
     while window.is_open() {
         while let Some(event) = window.poll_event() {
@@ -0,0 +1,121 @@
+//! Derive macros for [sf2g](https://docs.rs/sf2g).
+//!
+//! Use them through the `derive` feature of sf2g, rather than depending on this crate directly.
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::quote,
+    syn::{
+        Data, DeriveInput, Error, GenericParam, Lifetime, LifetimeParam, LitStr, Member,
+        parse_macro_input, parse_quote,
+    },
+};
+
+/// Derive `sf2g::graphics::Uniforms` for a struct.
+///
+/// See the documentation of the `Uniforms` trait for the supported field types and attributes.
+#[proc_macro_derive(Uniforms, attributes(uniforms, uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_uniforms(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_uniforms(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Uniforms can only be derived for structs",
+        ));
+    };
+    let mut prefix = String::new();
+    for attr in &input.attrs {
+        if attr.path().is_ident("uniforms") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("prefix") {
+                    prefix = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `uniforms` attribute"))
+                }
+            })?;
+        }
+    }
+    let tex = Lifetime::new("'__sf2g_texture", Span::call_site());
+    let mut names = Vec::new();
+    let mut setters = Vec::new();
+    let mut bounds = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let mut skip = false;
+        let mut rename = None;
+        for attr in &field.attrs {
+            if attr.path().is_ident("uniform") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else if meta.path.is_ident("rename") {
+                        rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown `uniform` attribute"))
+                    }
+                })?;
+            }
+        }
+        if skip {
+            continue;
+        }
+        let (member, name) = match (&field.ident, rename) {
+            (Some(ident), rename) => {
+                let name = rename.unwrap_or_else(|| {
+                    let name = ident.to_string();
+                    name.strip_prefix("r#").map_or(name.clone(), str::to_owned)
+                });
+                (Member::Named(ident.clone()), name)
+            }
+            (None, Some(rename)) => (Member::Unnamed(i.into()), rename),
+            (None, None) => {
+                return Err(Error::new_spanned(
+                    field,
+                    "fields of tuple structs need a `#[uniform(rename = \"...\")]` attribute",
+                ));
+            }
+        };
+        let name = format!("{prefix}{name}");
+        let ty = &field.ty;
+        setters.push(quote! {
+            ::sf2g::graphics::UniformField::set_field(&self.#member, shader, #name)?;
+        });
+        bounds.push(quote! { #ty: ::sf2g::graphics::UniformField<#tex> });
+        names.push(name);
+    }
+    let ident = &input.ident;
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeParam::new(tex.clone())));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    for bound in bounds {
+        where_clause.predicates.push(parse_quote!(#bound));
+    }
+    Ok(quote! {
+        impl #impl_generics ::sf2g::graphics::Uniforms<#tex> for #ident #ty_generics
+        #where_clause
+        {
+            const NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn apply_uniforms(
+                &self,
+                shader: &mut ::sf2g::graphics::Shader<#tex>,
+            ) -> ::sf2g::SfResult<()> {
+                #(#setters)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
@@ -4,6 +4,6 @@ mod fbox;
 mod string;
 mod vector;
 
+pub(crate) use vector::CppVectorItem;
 pub use {fbox::FBox, string::CppString, vector::CppVector};
-pub(crate) use {fbox::RawDefault, vector::CppVectorItem};
 pub(crate) type CppStringVector = CppVector<CppString>;
@@ -27,6 +27,7 @@ type sfVertexBuffer = crate::graphics::VertexBuffer;
 /// `glBlendFunc()` or `glBlendFuncSeparate()`.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendFactor {
     /// (0, 0, 0, 0)
     Zero,
@@ -56,6 +57,7 @@ pub enum BlendFactor {
 /// `glBlendEquation()` or `glBlendEquationSeparate()`.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendEquation {
     /// `pixel = src * src_factor + dst * dst_factor`
     Add,
@@ -113,6 +115,7 @@ pub enum BlendEquation {
 /// [`RenderTarget::draw`]: crate::graphics::RenderTarget::draw
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlendMode {
     /// Source blending factor for the color channels
     pub color_src_factor: BlendFactor,
@@ -248,6 +251,7 @@ pub struct sfGlslMat4 {
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum sfPrimitiveType {
     /// List of individual points
     Points,
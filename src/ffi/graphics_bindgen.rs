@@ -144,8 +144,8 @@ pub fn sfRenderTexture_setActive(renderTexture: *mut sfRenderTexture, active: bo
 pub fn sfRenderTexture_display(renderTexture: *mut sfRenderTexture);
 pub fn sfRenderTexture_clear(renderTexture: *mut sfRenderTexture, color: sfColor);
 pub fn sfRenderTexture_setView(renderTexture: *mut sfRenderTexture, view: *const sfView);
-pub fn sfRenderTexture_getView(renderTexture: *const sfRenderTexture) -> *const sfView;
-pub fn sfRenderTexture_getDefaultView(renderTexture: *const sfRenderTexture) -> *const sfView;
+pub fn sfRenderTexture_getView(renderTexture: *const sfRenderTexture) -> sfView;
+pub fn sfRenderTexture_getDefaultView(renderTexture: *const sfRenderTexture) -> sfView;
 pub fn sfRenderTexture_getViewport(renderTexture: *const sfRenderTexture, view: *const sfView) -> sfIntRect;
 pub fn sfRenderTexture_mapPixelToCoords(renderTexture: *const sfRenderTexture, point: sfVector2i) -> sfVector2f;
 pub fn sfRenderTexture_mapPixelToCoords_View(renderTexture: *const sfRenderTexture, point: sfVector2i, targetView: *const sfView) -> sfVector2f;
@@ -199,8 +199,8 @@ pub fn sfRenderWindow_setJoystickThreshold(renderWindow: *mut sfRenderWindow, th
 pub fn sfRenderWindow_getSystemHandle(renderWindow: *const sfRenderWindow) -> sfWindowHandle;
 pub fn sfRenderWindow_clear(renderWindow: *mut sfRenderWindow, color: sfColor);
 pub fn sfRenderWindow_setView(renderWindow: *mut sfRenderWindow, view: *const sfView);
-pub fn sfRenderWindow_getView(renderWindow: *const sfRenderWindow) -> *const sfView;
-pub fn sfRenderWindow_getDefaultView(renderWindow: *const sfRenderWindow) -> *const sfView;
+pub fn sfRenderWindow_getView(renderWindow: *const sfRenderWindow) -> sfView;
+pub fn sfRenderWindow_getDefaultView(renderWindow: *const sfRenderWindow) -> sfView;
 pub fn sfRenderWindow_getViewport(renderWindow: *const sfRenderWindow, view: *const sfView) -> sfIntRect;
 pub fn sfRenderWindow_mapPixelToCoords(renderWindow: *const sfRenderWindow, point: sfVector2i) -> sfVector2f;
 pub fn sfRenderWindow_mapPixelToCoords_View(renderWindow: *const sfRenderWindow, point: sfVector2i, targetView: *const sfView) -> sfVector2f;
@@ -250,6 +250,7 @@ pub fn sfShader_setVec4UniformArray(shader: *mut sfShader, name: *const c_char,
 pub fn sfShader_setMat3UniformArray(shader: *mut sfShader, name: *const c_char, matrixArray: *const sfGlslMat3, length: usize);
 pub fn sfShader_setMat4UniformArray(shader: *mut sfShader, name: *const c_char, matrixArray: *const sfGlslMat4, length: usize);
 pub fn sfShader_getNativeHandle(shader: *const sfShader) -> c_uint;
+pub fn sfShader_getUniformLocation(shader: *const sfShader, name: *const c_char) -> c_int;
 pub fn sfShader_bind(shader: *const sfShader);
 pub fn sfShader_isAvailable() -> bool;
 pub fn sfShader_isGeometryAvailable() -> bool;
@@ -299,15 +300,6 @@ pub fn sfTexture_swap(left: *mut sfTexture, right: *mut sfTexture);
 pub fn sfTexture_getNativeHandle(texture: *const sfTexture) -> c_uint;
 pub fn sfTexture_bind(texture: *const sfTexture);
 pub fn sfTexture_getMaximumSize() -> c_uint;
-// Transform.cpp
-pub fn sfTransform_transformPoint(transform: *const sfTransform, point: sfVector2f) -> sfVector2f;
-pub fn sfTransform_transformRect(transform: *const sfTransform, rectangle: sfFloatRect) -> sfFloatRect;
-pub fn sfTransform_combine(transform: *mut sfTransform, other: *const sfTransform);
-pub fn sfTransform_translate(transform: *mut sfTransform, x: f32, y: f32);
-pub fn sfTransform_rotate(transform: *mut sfTransform, angle: f32);
-pub fn sfTransform_rotateWithCenter(transform: *mut sfTransform, angle: f32, centerX: f32, centerY: f32);
-pub fn sfTransform_scale(transform: *mut sfTransform, scaleX: f32, scaleY: f32);
-pub fn sfTransform_scaleWithCenter(transform: *mut sfTransform, scaleX: f32, scaleY: f32, centerX: f32, centerY: f32);
 // VertexBuffer.cpp
 pub fn sfVertexBuffer_new() -> *mut sfVertexBuffer;
 pub fn sfVertexBuffer_cpy(vertexBuffer: *const sfVertexBuffer) -> *mut sfVertexBuffer;
@@ -324,21 +316,5 @@ pub fn sfVertexBuffer_setUsage(vertexBuffer: *mut sfVertexBuffer, usage: sfVerte
 pub fn sfVertexBuffer_getUsage(vertexBuffer: *const sfVertexBuffer) -> sfVertexBufferUsage;
 pub fn sfVertexBuffer_bind(vertexBuffer: *const sfVertexBuffer);
 pub fn sfVertexBuffer_isAvailable() -> bool;
-// View.cpp
-pub fn sfView_new() -> *mut sfView;
-pub fn sfView_del(view: *mut sfView);
-pub fn sfView_cpy(view: *const sfView) -> *mut sfView;
-pub fn sfView_setCenter(view: *mut sfView, center: sfVector2f);
-pub fn sfView_setSize(view: *mut sfView, size: sfVector2f);
-pub fn sfView_setRotation(view: *mut sfView, angle: f32);
-pub fn sfView_setViewport(view: *mut sfView, viewport: sfFloatRect);
-pub fn sfView_reset(view: *mut sfView, rectangle: sfFloatRect);
-pub fn sfView_getCenter(view: *const sfView) -> sfVector2f;
-pub fn sfView_getSize(view: *const sfView) -> sfVector2f;
-pub fn sfView_getRotation(view: *const sfView) -> f32;
-pub fn sfView_getViewport(view: *const sfView) -> sfFloatRect;
-pub fn sfView_move(view: *mut sfView, offset: sfVector2f);
-pub fn sfView_rotate(view: *mut sfView, angle: f32);
-pub fn sfView_zoom(view: *mut sfView, factor: f32);
 
 }
\ No newline at end of file
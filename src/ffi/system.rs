@@ -5,5 +5,6 @@ use crate::{
 };
 
 pub type sfTime = i64;
+pub type sfErrCallback = unsafe extern "C" fn(message: *const c_char, len: usize);
 
 include!("system_bindgen.rs");
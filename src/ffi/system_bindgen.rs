@@ -8,6 +8,10 @@ pub fn sfClock_new() -> *mut sfClock;
 pub fn sfClock_delete(clock: *mut sfClock);
 pub fn sfClock_getElapsedTime(clock: *const sfClock) -> i64;
 pub fn sfClock_restart(clock: *mut sfClock) -> i64;
+// Err.cpp
+pub fn sfErr_setCallback(cb: Option<sfErrCallback>);
+pub fn sfErr_takeLastMessage() -> *mut sfStdString;
+pub fn sfErr_messageCount() -> u64;
 // SfStdString.cpp
 pub fn sfStdString_del(s: *mut sfStdString);
 pub fn sfStdString_getLength(s: *const sfStdString) -> usize;
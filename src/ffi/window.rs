@@ -110,6 +110,7 @@ pub enum sfCursorType {
 /// [`Window::settings`]: crate::window::Window::settings
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct sfContextSettings {
     /// Bits of the depth buffer.
     pub depth_bits: c_uint,
@@ -278,6 +279,7 @@ pub(crate) union EventUnion {
 
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Axes supported by SFML joysticks
 pub enum JoystickAxis {
     /// The X axis.
@@ -302,6 +304,7 @@ type sfJoystickAxis = JoystickAxis;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum MouseWheel {
     VerticalWheel,
@@ -310,6 +313,7 @@ pub enum MouseWheel {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum MouseButton {
     Left,
@@ -324,6 +328,7 @@ type sfMouseButton = MouseButton;
 /// Key codes known to SFML.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Key {
     Unknown = -1,
@@ -432,6 +437,7 @@ pub enum Key {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Scancode {
     Unknown = -1,
@@ -600,6 +606,7 @@ pub type sfWindowHandle = *mut c_void;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum sfSensorType {
     ///< Measures the raw acceleration (m/s^2)
     Accelerometer,
@@ -0,0 +1,389 @@
+use {
+    crate::{
+        graphics::{IntRect, Sprite, Texture, Transformable},
+        system::{Time, Vector2f, Vector2u},
+    },
+    std::collections::HashMap,
+};
+
+/// How an [`AnimationClip`] continues after its last frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum PlayMode {
+    /// Start over from the first frame
+    #[default]
+    Loop,
+    /// Stop on the last frame
+    Once,
+    /// Play backwards to the first frame, then forwards again, and so on
+    PingPong,
+}
+
+/// A sequence of frames of a [`SpriteSheet`], each shown for its own duration.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AnimationClip {
+    /// The frames of the clip, as indices in [`SpriteSheet::frames`], with how long each
+    /// one is shown
+    pub frames: Vec<(usize, Time)>,
+    /// What happens after the last frame
+    pub mode: PlayMode,
+    /// Named events, fired when the frame at a given position in the clip is shown
+    pub events: Vec<(usize, String)>,
+}
+
+impl AnimationClip {
+    /// Create a clip showing the sheet frames `frames`, each for `frame_duration`.
+    #[must_use]
+    pub fn new(
+        frames: impl IntoIterator<Item = usize>,
+        frame_duration: Time,
+        mode: PlayMode,
+    ) -> Self {
+        Self {
+            frames: frames.into_iter().map(|i| (i, frame_duration)).collect(),
+            mode,
+            events: Vec::new(),
+        }
+    }
+    /// Fire the event `name` when the frame at `position` in the clip is shown.
+    #[must_use]
+    pub fn with_event(mut self, position: usize, name: impl Into<String>) -> Self {
+        self.events.push((position, name.into()));
+        self
+    }
+    /// The time it takes to play every frame once
+    #[must_use]
+    pub fn duration(&self) -> Time {
+        self.frames
+            .iter()
+            .fold(Time::ZERO, |acc, &(_, duration)| acc + duration)
+    }
+}
+
+/// How a frame of a [`SpriteSheet`] is placed relative to the sprite showing it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FramePlacement {
+    /// The origin of the sprite, in the coordinates of the texture rect of the frame
+    pub origin: Vector2f,
+    /// The rotation of the sprite, in degrees
+    pub rotation: f32,
+}
+
+/// The frames of a texture, and the named animation clips using them.
+///
+/// A sheet only describes texture rects, so it can be shared by every sprite using the
+/// texture. The playback state of each sprite is kept in an [`AnimationPlayer`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let mut clock = Clock::start().unwrap();
+/// let texture = Texture::from_file("hero.png").unwrap();
+/// let sheet = SpriteSheet::from_texture_grid(&texture, Vector2u::new(32, 32))
+///     .with_clip("idle", AnimationClip::new(0..4, Time::milliseconds(150), PlayMode::PingPong))
+///     .with_clip(
+///         "attack",
+///         AnimationClip::new(4..10, Time::milliseconds(60), PlayMode::Once).with_event(3, "hit"),
+///     );
+/// let mut sprite = Sprite::with_texture(&texture);
+/// let mut player = AnimationPlayer::new();
+/// player.play("idle");
+/// loop {
+///     for event in player.animate(&sheet, &mut sprite, clock.restart()) {
+///         if event == "hit" {
+///             // Deal damage...
+///         }
+///     }
+///     if player.is_finished() {
+///         player.play("idle");
+///     }
+///     window.draw_sprite(&sprite, &RenderStates::DEFAULT);
+///     # break;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpriteSheet {
+    /// Texture rects of the frames
+    pub frames: Vec<IntRect>,
+    /// How to place each frame relative to the sprite showing it, for frames that were
+    /// trimmed or rotated when packed. Empty if all the frames are shown as they are.
+    pub placements: Vec<FramePlacement>,
+    /// Animation clips, by name
+    pub clips: HashMap<String, AnimationClip>,
+}
+
+impl SpriteSheet {
+    /// Create a sheet with the frames `frames`, and no clips.
+    #[must_use]
+    pub fn from_rects(frames: Vec<IntRect>) -> Self {
+        Self {
+            frames,
+            placements: Vec::new(),
+            clips: HashMap::new(),
+        }
+    }
+    /// Slice a texture of size `texture_size` into a grid of frames of size `frame_size`.
+    ///
+    /// Frames are numbered in row-major order, starting at the top left. Cells that don't
+    /// fit entirely in the texture are left out.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::{graphics::*, system::Vector2u};
+    /// let sheet = SpriteSheet::from_grid(Vector2u::new(64, 40), Vector2u::new(16, 16));
+    /// assert_eq!(sheet.frames.len(), 8);
+    /// assert_eq!(sheet.frames[5], IntRect::new(16, 16, 16, 16));
+    /// ```
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_wrap,
+        reason = "Texture sizes are far below i32::MAX"
+    )]
+    pub fn from_grid(texture_size: Vector2u, frame_size: Vector2u) -> Self {
+        if frame_size.x == 0 || frame_size.y == 0 {
+            return Self::default();
+        }
+        let (columns, rows) = (texture_size.x / frame_size.x, texture_size.y / frame_size.y);
+        let (w, h) = (frame_size.x as i32, frame_size.y as i32);
+        Self::from_rects(
+            (0..rows as i32)
+                .flat_map(|row| {
+                    (0..columns as i32).map(move |col| IntRect::new(col * w, row * h, w, h))
+                })
+                .collect(),
+        )
+    }
+    /// Slice `texture` into a grid of frames of size `frame_size`.
+    ///
+    /// See [`from_grid`](Self::from_grid).
+    #[must_use]
+    pub fn from_texture_grid(texture: &Texture, frame_size: Vector2u) -> Self {
+        Self::from_grid(texture.size(), frame_size)
+    }
+    /// Add the clip `clip` under the name `name`, replacing any clip with that name.
+    #[must_use]
+    pub fn with_clip(mut self, name: impl Into<String>, clip: AnimationClip) -> Self {
+        let _ = self.clips.insert(name.into(), clip);
+        self
+    }
+    /// The clip named `name`, if any
+    #[must_use]
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+}
+
+/// Plays the clips of a [`SpriteSheet`] on a [`Sprite`].
+///
+/// The player only holds the playback state: which clip is playing, on which frame, and for
+/// how long. Each animated sprite needs its own player, but they can share a sheet.
+///
+/// See [`SpriteSheet`] for an example.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationPlayer {
+    /// Playback speed factor. 2 plays twice as fast.
+    pub speed: f32,
+    clip: Option<String>,
+    position: usize,
+    elapsed: Time,
+    backwards: bool,
+    paused: bool,
+    finished: bool,
+    /// Whether the events of the current frame are still to be fired
+    entered: bool,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Playback control
+impl AnimationPlayer {
+    /// Create a player with no clip.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            speed: 1.,
+            clip: None,
+            position: 0,
+            elapsed: Time::ZERO,
+            backwards: false,
+            paused: false,
+            finished: false,
+            entered: false,
+        }
+    }
+    /// Play the clip named `name` from the start, unless it is already playing.
+    ///
+    /// This makes it possible to call `play` every frame with the clip matching the state of
+    /// a character. A finished clip is restarted.
+    pub fn play(&mut self, name: &str) {
+        if self.clip.as_deref() != Some(name) || self.finished {
+            self.clip = Some(name.to_owned());
+            self.restart();
+        }
+        self.paused = false;
+    }
+    /// Play the current clip from the start.
+    pub fn restart(&mut self) {
+        self.position = 0;
+        self.elapsed = Time::ZERO;
+        self.backwards = false;
+        self.finished = false;
+        self.entered = true;
+    }
+    /// Stop advancing, until [`resume`](Self::resume) or [`play`](Self::play) is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+    /// Continue after [`pause`](Self::pause).
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+    /// Whether the player is paused
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    /// Whether a clip in [`PlayMode::Once`] reached its last frame
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// The name of the current clip, if any
+    #[must_use]
+    pub fn clip(&self) -> Option<&str> {
+        self.clip.as_deref()
+    }
+    /// The position of the current frame in the current clip
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Updating
+impl AnimationPlayer {
+    /// Advance the current clip by `dt`, and return the names of the events of the frames
+    /// shown meanwhile, in order.
+    ///
+    /// Several frames can be skipped in one update if `dt` is long, and all their events
+    /// are returned. If `dt` spans several cycles of a repeating clip, the events of the
+    /// cycles skipped over are only returned once.
+    pub fn update<'s>(&mut self, sheet: &'s SpriteSheet, dt: Time) -> Vec<&'s str> {
+        let mut events = Vec::new();
+        let Some(clip) = self.clip.as_deref().and_then(|name| sheet.clip(name)) else {
+            return events;
+        };
+        let len = clip.frames.len();
+        if len == 0 || self.position >= len {
+            return events;
+        }
+        let fire = |events: &mut Vec<&'s str>, position: usize| {
+            events.extend(
+                clip.events
+                    .iter()
+                    .filter(|(at, _)| *at == position)
+                    .map(|(_, name)| name.as_str()),
+            );
+        };
+        if self.entered {
+            self.entered = false;
+            fire(&mut events, self.position);
+        }
+        if self.paused || self.finished {
+            return events;
+        }
+        self.elapsed += dt * self.speed;
+        // Without this, a clip with only empty frames would loop forever
+        let duration = clip.duration();
+        if duration <= Time::ZERO {
+            return events;
+        }
+        // Time after which a repeating clip is back in the same state
+        let period = match clip.mode {
+            PlayMode::Once => Time::ZERO,
+            PlayMode::Loop => duration,
+            PlayMode::PingPong if len == 1 => duration,
+            PlayMode::PingPong => duration * 2 - clip.frames[0].1 - clip.frames[len - 1].1,
+        };
+        // Skip whole cycles at once, but keep one so that every event still fires
+        if period > Time::ZERO && self.elapsed >= period * 2 {
+            self.elapsed = period + self.elapsed % period;
+        }
+        while self.elapsed >= clip.frames[self.position].1 {
+            self.elapsed -= clip.frames[self.position].1;
+            match clip.mode {
+                PlayMode::Loop => self.position = (self.position + 1) % len,
+                PlayMode::Once => {
+                    if self.position + 1 == len {
+                        self.finished = true;
+                        self.elapsed = Time::ZERO;
+                        break;
+                    }
+                    self.position += 1;
+                }
+                PlayMode::PingPong => {
+                    if len == 1 {
+                        continue;
+                    }
+                    if (self.backwards && self.position == 0)
+                        || (!self.backwards && self.position + 1 == len)
+                    {
+                        self.backwards = !self.backwards;
+                    }
+                    if self.backwards {
+                        self.position -= 1;
+                    } else {
+                        self.position += 1;
+                    }
+                }
+            }
+            fire(&mut events, self.position);
+        }
+        events
+    }
+    /// The index in [`SpriteSheet::frames`] of the current frame, if a clip is playing.
+    #[must_use]
+    pub fn frame_index(&self, sheet: &SpriteSheet) -> Option<usize> {
+        let clip = sheet.clip(self.clip.as_deref()?)?;
+        clip.frames.get(self.position).map(|&(index, _)| index)
+    }
+    /// The texture rect of the current frame, if a clip is playing.
+    #[must_use]
+    pub fn frame(&self, sheet: &SpriteSheet) -> Option<IntRect> {
+        sheet.frames.get(self.frame_index(sheet)?).copied()
+    }
+    /// Set the texture rect of `sprite` to the current frame, if a clip is playing.
+    ///
+    /// If the sheet has [placements](SpriteSheet::placements), the origin and rotation of
+    /// `sprite` are set too. To rotate the sprite further, call
+    /// [`rotate`](Transformable::rotate) afterwards.
+    pub fn apply(&self, sheet: &SpriteSheet, sprite: &mut Sprite) {
+        let Some(index) = self.frame_index(sheet) else {
+            return;
+        };
+        if let Some(&rect) = sheet.frames.get(index) {
+            sprite.set_texture_rect(rect);
+        }
+        if let Some(placement) = sheet.placements.get(index) {
+            sprite.set_origin(placement.origin);
+            sprite.set_rotation(placement.rotation);
+        }
+    }
+    /// [`update`](Self::update), then [`apply`](Self::apply).
+    pub fn animate<'s>(
+        &mut self,
+        sheet: &'s SpriteSheet,
+        sprite: &mut Sprite,
+        dt: Time,
+    ) -> Vec<&'s str> {
+        let events = self.update(sheet, dt);
+        self.apply(sheet, sprite);
+        events
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    graphics::{FloatRect, View},
+    system::{Time, Vector2f, Vector2i, Vector2u},
+};
+
+/// A 2D camera built on top of a [`View`].
+///
+/// Implements the usual camera behaviors of 2D games:
+///
+/// - Following a target smoothly, with a dead zone in which the target can move freely
+///   ([`follow`](Camera::follow))
+/// - Keeping the view inside the world ([`bounds`](Camera::bounds))
+/// - Zooming around a point, e.g. the mouse cursor ([`zoom_at`](Camera::zoom_at),
+///   [`zoom_at_pixel`](Camera::zoom_at_pixel))
+/// - Screen shake ([`shake`](Camera::shake))
+/// - Parallax layers ([`parallax_view`](Camera::parallax_view))
+///
+/// The camera logic operates on [`base`](Camera::base), and [`view`](Camera::view) returns the
+/// view to draw with, which has the screen shake applied on top.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let (player_pos, dt): (Vector2f, Time) = unimplemented!();
+/// let mut camera = Camera::new(View::new(player_pos, Vector2f::new(640., 360.)));
+/// camera.dead_zone = Vector2f::new(64., 32.);
+/// camera.bounds = Some(FloatRect::new(0., 0., 4096., 1024.));
+/// loop {
+///     camera.update(player_pos, dt);
+///     window.set_view(&camera.parallax_view(Vector2f::new(0.5, 0.5)));
+///     // Draw the background...
+///     window.set_view(&camera.view());
+///     // Draw the world...
+///     # break;
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// The view controlled by the camera, without screen shake
+    pub base: View,
+    /// Size of the area around the center of the view in which the followed target
+    /// can move without the camera following it
+    pub dead_zone: Vector2f,
+    /// How fast the camera catches up with the followed target.
+    ///
+    /// The camera covers about 63% of the remaining distance every `1 / follow_speed` seconds.
+    /// [`f32::INFINITY`] makes the camera snap to the target.
+    pub follow_speed: f32,
+    /// Area of the world the view is kept in, if any
+    ///
+    /// If the view is larger than the bounds, it is centered on them.
+    /// Rotation is not taken into account.
+    pub bounds: Option<FloatRect>,
+    /// Screen shake applied on top of the base view
+    pub shake: Shake,
+}
+
+impl Camera {
+    /// Create a camera controlling `base`, with no dead zone, no bounds and no shake.
+    #[must_use]
+    pub fn new(base: View) -> Self {
+        Self {
+            base,
+            dead_zone: Vector2f::default(),
+            follow_speed: 8.,
+            bounds: None,
+            shake: Shake::default(),
+        }
+    }
+    /// Follow `target`, clamp to the bounds and advance the screen shake, by `dt`.
+    ///
+    /// This is [`follow`](Camera::follow), then [`clamp_to_bounds`](Camera::clamp_to_bounds),
+    /// then [`Shake::update`].
+    pub fn update(&mut self, target: Vector2f, dt: Time) {
+        self.follow(target, dt);
+        self.clamp_to_bounds();
+        self.shake.update(dt);
+    }
+    /// Move the view towards `target`, by `dt`.
+    ///
+    /// The camera only moves if the target is out of the dead zone, and then only as far as
+    /// needed to bring it back to the edge of the dead zone.
+    pub fn follow(&mut self, target: Vector2f, dt: Time) {
+        let center = self.base.center();
+        let half = self.dead_zone / 2.;
+        let offset = target - center;
+        let excess = Vector2f::new(
+            offset.x - offset.x.clamp(-half.x, half.x),
+            offset.y - offset.y.clamp(-half.y, half.y),
+        );
+        // An infinite speed snaps, even when `dt` is zero and the decay would be NaN
+        let t = if self.follow_speed.is_infinite() {
+            1.
+        } else {
+            1. - (-self.follow_speed * dt.as_seconds()).exp()
+        };
+        self.base.set_center(center + excess * t);
+    }
+    /// Move the view back inside [`bounds`](Camera::bounds), if set.
+    pub fn clamp_to_bounds(&mut self) {
+        let Some(bounds) = self.bounds else {
+            return;
+        };
+        let half = self.base.size() / 2.;
+        let center = self.base.center();
+        let clamp = |c: f32, half: f32, min: f32, len: f32| {
+            if half * 2. >= len {
+                min + len / 2.
+            } else {
+                c.clamp(min + half, min + len - half)
+            }
+        };
+        self.base.set_center(Vector2f::new(
+            clamp(center.x, half.x, bounds.left, bounds.width),
+            clamp(center.y, half.y, bounds.top, bounds.height),
+        ));
+    }
+    /// Zoom by `factor`, keeping the world position `point` at the same place on screen.
+    ///
+    /// `factor` works like for [`View::zoom`]: values bigger than 1 zoom out.
+    pub fn zoom_at(&mut self, factor: f32, point: Vector2f) {
+        let center = self.base.center();
+        self.base.zoom(factor);
+        self.base.set_center(point + (center - point) * factor);
+        self.clamp_to_bounds();
+    }
+    /// Zoom by `factor`, keeping the world position under `pixel` (e.g. the mouse cursor)
+    /// at the same place on screen.
+    ///
+    /// `target_size` is the size of the render target the view is drawn to.
+    pub fn zoom_at_pixel(&mut self, factor: f32, pixel: Vector2i, target_size: Vector2u) {
+        let point = self.base.pixel_to_world(pixel, target_size);
+        self.zoom_at(factor, point);
+    }
+    /// The view to draw with: the base view with the screen shake applied.
+    #[must_use]
+    pub fn view(&self) -> View {
+        self.shake.apply(&self.base)
+    }
+    /// View for a parallax layer, moving `factor` times as fast as the camera.
+    ///
+    /// A factor of 0 makes a layer that doesn't move at all, 1 makes a layer that moves with
+    /// the world, and values in between make distant layers. The layer coordinates match world
+    /// coordinates when the camera is centered on the origin.
+    #[must_use]
+    pub fn parallax_view(&self, factor: Vector2f) -> View {
+        let mut view = self.view();
+        view.set_center(view.center().cwise_mul(factor));
+        view
+    }
+}
+
+/// Trauma-based screen shake.
+///
+/// Adding trauma (e.g. when something explodes) makes the view shake, and the trauma decays
+/// over time. The intensity of the shake is the square of the trauma, so small amounts of
+/// trauma give subtle shakes, and the shake fades out smoothly.
+///
+/// The shake follows smooth noise rather than jumping randomly every frame, and is
+/// deterministic for a given seed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shake {
+    /// Maximum offset of the view at full trauma, in world units
+    pub max_offset: Vector2f,
+    /// Maximum rotation of the view at full trauma, in degrees
+    pub max_rotation: f32,
+    /// Trauma lost per second
+    pub decay: f32,
+    /// How fast the view shakes, in oscillations per second
+    pub frequency: f32,
+    trauma: f32,
+    time: f32,
+    seed: u32,
+}
+
+impl Default for Shake {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Shake {
+    /// Create a screen shake with no trauma, using `seed` for the noise.
+    #[must_use]
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            max_offset: Vector2f::new(16., 16.),
+            max_rotation: 3.,
+            decay: 1.,
+            frequency: 15.,
+            trauma: 0.,
+            time: 0.,
+            seed,
+        }
+    }
+    /// Add `amount` of trauma. The trauma is kept in `[0, 1]`.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0., 1.);
+    }
+    /// The current trauma, in `[0, 1]`
+    #[must_use]
+    pub const fn trauma(&self) -> f32 {
+        self.trauma
+    }
+    /// Advance the shake and decay the trauma, by `dt`.
+    pub fn update(&mut self, dt: Time) {
+        let dt = dt.as_seconds();
+        self.trauma = (self.trauma - self.decay * dt).max(0.);
+        // Wrap around to keep precision, long after any shake faded out
+        self.time = (self.time + dt * self.frequency) % 65_536.;
+    }
+    /// The current offset and rotation (in degrees) of the view.
+    #[must_use]
+    pub fn offset(&self) -> (Vector2f, f32) {
+        let intensity = self.trauma * self.trauma;
+        if intensity == 0. {
+            return (Vector2f::default(), 0.);
+        }
+        let t = self.time;
+        let offset = Vector2f::new(
+            self.max_offset.x * intensity * noise(self.seed, t),
+            self.max_offset.y * intensity * noise(self.seed.wrapping_add(1), t),
+        );
+        let rotation = self.max_rotation * intensity * noise(self.seed.wrapping_add(2), t);
+        (offset, rotation)
+    }
+    /// Return `view` with the shake applied.
+    #[must_use]
+    pub fn apply(&self, view: &View) -> View {
+        let (offset, rotation) = self.offset();
+        let mut view = *view;
+        view.move_(offset);
+        view.rotate(rotation);
+        view
+    }
+}
+
+/// Smooth 1D value noise in `[-1, 1]`
+fn noise(seed: u32, t: f32) -> f32 {
+    let i = t.floor();
+    let f = t - i;
+    let s = f * f * (3. - 2. * f);
+    let a = hash(seed, i);
+    let b = hash(seed, i + 1.);
+    a + (b - a) * s
+}
+
+/// Hash of an integral float, in `[-1, 1]`
+fn hash(seed: u32, i: f32) -> f32 {
+    let mut x = i.to_bits() ^ seed.wrapping_mul(0x9E37_79B9);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    (x >> 8) as f32 / 8_388_608. - 1.
+}
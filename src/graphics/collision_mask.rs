@@ -0,0 +1,294 @@
+use crate::{
+    SfError, SfResult,
+    graphics::{FloatRect, IntRect, Sprite, Transform, Transformable},
+    system::{Vector2f, Vector2u},
+};
+
+/// Which pixels of a texture are solid, for pixel-perfect collisions.
+///
+/// The pixels of a [`Texture`] can't be read back cheaply, so the mask is built from the
+/// same image data the texture is loaded from. Pixels whose alpha is at least the alpha
+/// threshold are solid.
+///
+/// A mask describes a whole texture, so it can be shared by every sprite using that texture,
+/// whatever their texture rect.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::graphics::*;
+/// let texture = Texture::from_file("ship.png").unwrap();
+/// let mask = CollisionMask::from_file("ship.png", 128).unwrap();
+/// let mut a = Sprite::with_texture(&texture);
+/// let mut b = Sprite::with_texture(&texture);
+/// b.set_position((20., 10.));
+/// if CollisionMask::sprites_overlap(&a, &mask, &b, &mask) {
+///     println!("Boom");
+/// }
+/// ```
+///
+/// [`Texture`]: crate::graphics::Texture
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollisionMask {
+    size: Vector2u,
+    solid: Vec<bool>,
+}
+
+/// Creation
+impl CollisionMask {
+    /// Create a mask from an array of 32-bits RGBA pixels, like the ones passed to
+    /// [`Texture::update_from_pixels`].
+    ///
+    /// Pixels with an alpha of at least `alpha_threshold` are solid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `pixels` doesn't match `width` and `height`.
+    ///
+    /// [`Texture::update_from_pixels`]: crate::graphics::Texture::update_from_pixels
+    #[must_use]
+    pub fn from_pixels(pixels: &[u8], width: u32, height: u32, alpha_threshold: u8) -> Self {
+        assert_eq!(pixels.len(), width as usize * height as usize * 4);
+        Self {
+            size: Vector2u::new(width, height),
+            solid: pixels
+                .chunks_exact(4)
+                .map(|px| px[3] >= alpha_threshold)
+                .collect(),
+        }
+    }
+
+    /// Create a mask from an image file in memory.
+    ///
+    /// See [`from_pixels`](CollisionMask::from_pixels).
+    pub fn from_memory(mem: &[u8], alpha_threshold: u8) -> SfResult<Self> {
+        let img = image::load_from_memory(mem).map_err(|_| SfError::CallFailed)?;
+        Ok(Self::from_pixels(
+            &img.to_rgba8(),
+            img.width(),
+            img.height(),
+            alpha_threshold,
+        ))
+    }
+
+    /// Create a mask from an image file.
+    ///
+    /// See [`from_pixels`](CollisionMask::from_pixels).
+    pub fn from_file(filename: &str, alpha_threshold: u8) -> SfResult<Self> {
+        let img = image::open(filename).map_err(|_| SfError::CallFailed)?;
+        Ok(Self::from_pixels(
+            &img.to_rgba8(),
+            img.width(),
+            img.height(),
+            alpha_threshold,
+        ))
+    }
+}
+
+/// Query and modify pixels
+impl CollisionMask {
+    /// The size of the mask, in pixels
+    #[must_use]
+    pub const fn size(&self) -> Vector2u {
+        self.size
+    }
+
+    /// Whether the pixel at `(x, y)` is solid.
+    ///
+    /// Pixels outside of the mask are not solid.
+    #[must_use]
+    pub fn is_solid(&self, x: u32, y: u32) -> bool {
+        self.index(x, y).is_some_and(|i| self.solid[i])
+    }
+
+    /// Make the pixel at `(x, y)` solid or not.
+    ///
+    /// Does nothing for pixels outside of the mask.
+    pub fn set_solid(&mut self, x: u32, y: u32, solid: bool) {
+        if let Some(i) = self.index(x, y) {
+            self.solid[i] = solid;
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.size.x && y < self.size.y).then(|| y as usize * self.size.x as usize + x as usize)
+    }
+
+    /// Whether the texel under the world position `point` is solid, for a sprite using
+    /// the texture described by this mask.
+    ///
+    /// The transform and texture rect of `sprite` are taken into account, including
+    /// flipped texture rects.
+    #[must_use]
+    pub fn is_solid_at(&self, sprite: &Sprite, point: Vector2f) -> bool {
+        let local = sprite.inverse_transform().transform_point(point);
+        self.is_solid_local(sprite.texture_rect(), local)
+    }
+
+    /// Whether the texel under `local`, in the local space of a sprite showing `rect`,
+    /// is solid
+    fn is_solid_local(&self, rect: IntRect, local: Vector2f) -> bool {
+        let size = rect.size().as_other::<f32>();
+        if local.x < 0. || local.y < 0. || local.x >= size.x.abs() || local.y >= size.y.abs() {
+            return false;
+        }
+        let texel = rect.position().as_other::<f32>()
+            + Vector2f::new(local.x * size.x.signum(), local.y * size.y.signum());
+        let (x, y) = (texel.x.floor(), texel.y.floor());
+        if x < 0. || y < 0. {
+            return false;
+        }
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "Checked to be positive, and out of range values saturate"
+        )]
+        self.is_solid(x as u32, y as u32)
+    }
+}
+
+/// Collision tests
+impl CollisionMask {
+    /// Check if the solid pixels of two sprites overlap.
+    ///
+    /// `mask_a` and `mask_b` describe the textures of `a` and `b`. Their transforms and
+    /// texture rects are taken into account.
+    ///
+    /// The global bounds of the sprites are checked first, so sprites far from each
+    /// other are cheap to test. Then, the solid texels of `a` in the overlapping area are
+    /// sampled at the finer of the two texel sizes, and looked up in `b`.
+    #[must_use]
+    pub fn sprites_overlap(
+        a: &Sprite,
+        mask_a: &CollisionMask,
+        b: &Sprite,
+        mask_b: &CollisionMask,
+    ) -> bool {
+        Self::overlap(
+            (mask_a, a.texture_rect(), a.transform()),
+            (mask_b, b.texture_rect(), b.transform()),
+        )
+    }
+
+    fn overlap(
+        (mask_a, rect_a, tf_a): (&Self, IntRect, &Transform),
+        (mask_b, rect_b, tf_b): (&Self, IntRect, &Transform),
+    ) -> bool {
+        let local_bounds = |rect: IntRect| {
+            let size = rect.size().as_other::<f32>();
+            FloatRect::new(0., 0., size.x.abs(), size.y.abs())
+        };
+        let (local_a, local_b) = (local_bounds(rect_a), local_bounds(rect_b));
+        let Some(area) = tf_a
+            .transform_rect(local_a)
+            .intersection(&tf_b.transform_rect(local_b))
+        else {
+            return false;
+        };
+        // The overlapping area, in the local space of `a`, where one unit is one texel
+        let inv_a = tf_a.inverse();
+        let Some(area) = inv_a.transform_rect(area).intersection(&local_a) else {
+            return false;
+        };
+        let a_to_b = tf_b.inverse().combined(tf_a);
+        // The size of the texels of `b` in the local space of `a`
+        let b_to_a = inv_a.combined(tf_b);
+        let pitch = 1f32
+            .min(
+                b_to_a
+                    .transform_vector(Vector2f::new(1., 0.))
+                    .length_sq()
+                    .sqrt(),
+            )
+            .min(
+                b_to_a
+                    .transform_vector(Vector2f::new(0., 1.))
+                    .length_sq()
+                    .sqrt(),
+            );
+        if pitch.is_nan() || pitch <= 0. {
+            return false;
+        }
+        let (min, max) = (area.min_corner(), area.max_corner());
+        let mut y = min.y.floor();
+        while y < max.y {
+            let mut x = min.x.floor();
+            while x < max.x {
+                if mask_a.is_solid_local(rect_a, Vector2f::new(x + 0.5, y + 0.5)) {
+                    // Sample the part of the texel in the area, at the centers of cells
+                    // as small as the texels of `b`
+                    let lo = Vector2f::new(x.max(min.x), y.max(min.y));
+                    let size = Vector2f::new((x + 1.).min(max.x), (y + 1.).min(max.y)) - lo;
+                    let cells = Vector2f::new(
+                        (size.x / pitch).ceil().max(1.),
+                        (size.y / pitch).ceil().max(1.),
+                    );
+                    let mut j = 0.5;
+                    while j < cells.y {
+                        let mut i = 0.5;
+                        while i < cells.x {
+                            let point =
+                                lo + Vector2f::new(size.x * i / cells.x, size.y * j / cells.y);
+                            if mask_b.is_solid_local(rect_b, a_to_b.transform_point(point)) {
+                                return true;
+                            }
+                            i += 1.;
+                        }
+                        j += 1.;
+                    }
+                }
+                x += 1.;
+            }
+            y += 1.;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+fn solid_mask(width: u32, height: u32, solid: &[(u32, u32)]) -> CollisionMask {
+    let mut mask = CollisionMask::from_pixels(
+        &vec![0; width as usize * height as usize * 4],
+        width,
+        height,
+        1,
+    );
+    for &(x, y) in solid {
+        mask.set_solid(x, y, true);
+    }
+    mask
+}
+
+#[test]
+fn test_overlap_fractional_offset() {
+    let mask = solid_mask(1, 1, &[(0, 0)]);
+    let rect = IntRect::new(0, 0, 1, 1);
+    let at = |x: f32, y: f32| Transform::from_translation(Vector2f::new(x, y));
+    let a = (&mask, rect, &Transform::IDENTITY);
+    assert!(CollisionMask::overlap(a, (&mask, rect, &at(0.7, 0.7))));
+    assert!(CollisionMask::overlap(a, (&mask, rect, &at(-0.9, 0.3))));
+    assert!(!CollisionMask::overlap(a, (&mask, rect, &at(1., 0.5))));
+}
+
+#[test]
+fn test_overlap_scaled_down() {
+    // Only the last texel of `b` is solid, and `b` is four times smaller than `a`
+    let (mask_a, mask_b) = (solid_mask(1, 1, &[(0, 0)]), solid_mask(4, 4, &[(3, 3)]));
+    let mut tf_b = Transform::from_translation(Vector2f::new(0.1, 0.1));
+    tf_b.scale(0.25, 0.25);
+    assert!(CollisionMask::overlap(
+        (&mask_a, IntRect::new(0, 0, 1, 1), &Transform::IDENTITY),
+        (&mask_b, IntRect::new(0, 0, 4, 4), &tf_b),
+    ));
+    // Flipping `b` moves its solid texel out of `a`
+    let mut tf_b = Transform::from_translation(Vector2f::new(0.9, 0.9));
+    tf_b.scale(0.25, 0.25);
+    assert!(!CollisionMask::overlap(
+        (&mask_a, IntRect::new(0, 0, 1, 1), &Transform::IDENTITY),
+        (&mask_b, IntRect::new(0, 0, 4, 4), &tf_b),
+    ));
+    assert!(CollisionMask::overlap(
+        (&mask_a, IntRect::new(0, 0, 1, 1), &Transform::IDENTITY),
+        (&mask_b, IntRect::new(4, 4, -4, -4), &tf_b),
+    ));
+}
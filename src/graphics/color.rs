@@ -14,6 +14,16 @@ use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 /// let color2 = Color::rgba(255, 255, 255, 128); // from red/green/blue/alpha (transparency)
 /// let color3 = Color::GREEN; // from one of the associated color constants
 /// ```
+///
+/// Colors can also be parsed from CSS color strings, like `"#ff8800"` or `"tomato"`,
+/// and converted from and to the [`Hsv`], [`Hsl`] and [`Oklab`] color spaces.
+///
+/// [`Hsv`]: crate::graphics::Hsv
+/// [`Hsl`]: crate::graphics::Hsl
+/// [`Oklab`]: crate::graphics::Oklab
+///
+/// With the `serde` feature, colors are serialized as `"#rrggbbaa"` hex strings in
+/// human-readable formats, and as `u32`s in RGBA order otherwise.
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub struct Color {
@@ -0,0 +1,331 @@
+use {
+    crate::graphics::Color,
+    std::{error::Error, fmt::Display, str::FromStr},
+};
+
+/// Error when parsing a [`Color`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// A `#` color doesn't have 3, 4, 6 or 8 hex digits
+    InvalidHex,
+    /// A `rgb()` or `rgba()` color has invalid components
+    InvalidFunction,
+    /// The string is not a known color name
+    UnknownName(
+        /// The unknown name
+        String,
+    ),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHex => write!(f, "Hex colors must have 3, 4, 6 or 8 hex digits"),
+            Self::InvalidFunction => write!(f, "Invalid rgb() or rgba() color"),
+            Self::UnknownName(name) => write!(f, "Unknown color name \"{name}\""),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parse a color the way CSS does.
+    ///
+    /// The accepted forms are:
+    ///
+    /// - Hex colors: `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa`
+    /// - Functions: `rgb(255, 128, 0)`, `rgba(255, 128, 0, 0.5)`, and the space separated
+    ///   `rgb(255 128 0 / 50%)`. Components can be numbers in `[0, 255]` or percentages,
+    ///   and alpha can be a number in `[0, 1]` or a percentage.
+    /// - The [CSS named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color),
+    ///   and `transparent`
+    ///
+    /// Function and color names are case insensitive, and surrounding whitespace is ignored.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// assert_eq!("#f80".parse(), Ok(Color::rgb(255, 136, 0)));
+    /// assert_eq!("#ff880080".parse(), Ok(Color::rgba(255, 136, 0, 128)));
+    /// assert_eq!("rgb(255 136 0 / 50%)".parse(), Ok(Color::rgba(255, 136, 0, 128)));
+    /// assert_eq!("rgba(100%, 0, 0, 0.5)".parse(), Ok(Color::rgba(255, 0, 0, 128)));
+    /// assert_eq!("RebeccaPurple".parse(), Ok(Color::rgb(102, 51, 153)));
+    /// assert!("#12345".parse::<Color>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex).ok_or(ParseColorError::InvalidHex);
+        }
+        let lower = s.to_ascii_lowercase();
+        if let Some(args) = lower
+            .strip_prefix("rgba(")
+            .or_else(|| lower.strip_prefix("rgb("))
+        {
+            return args
+                .strip_suffix(')')
+                .and_then(parse_function)
+                .ok_or(ParseColorError::InvalidFunction);
+        }
+        Color::from_name(&lower).ok_or_else(|| ParseColorError::UnknownName(s.to_owned()))
+    }
+}
+
+/// Names and hex formatting
+impl Color {
+    /// Look up a [CSS named color](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color),
+    /// case insensitively.
+    ///
+    /// `transparent` is [`Color::TRANSPARENT`].
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// assert_eq!(Color::from_name("cornflowerblue"), Some(Color::rgb(100, 149, 237)));
+    /// assert_eq!(Color::from_name("blurple"), None);
+    /// ```
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "transparent" {
+            return Some(Self::TRANSPARENT);
+        }
+        let index = CSS_NAMES.binary_search_by(|(n, _)| (*n).cmp(&name)).ok()?;
+        Some(CSS_NAMES[index].1)
+    }
+
+    /// Format the color as `#rrggbb`, or `#rrggbbaa` if it isn't opaque.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// assert_eq!(Color::rgb(255, 136, 0).to_hex(), "#ff8800");
+    /// assert_eq!(Color::rgba(255, 136, 0, 128).to_hex(), "#ff880080");
+    /// ```
+    #[must_use]
+    pub fn to_hex(self) -> String {
+        let Self { r, g, b, a } = self;
+        if a == 255 {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    // Expand each digit of the short forms to two
+    let expand = |v: u32| {
+        (0..4).rev().fold(0, |acc, i| {
+            let digit = (v >> (i * 4)) & 0xf;
+            (acc << 8) | (digit * 0x11)
+        })
+    };
+    let rgba = match hex.len() {
+        3 => expand((value << 4) | 0xf),
+        4 => expand(value),
+        6 => (value << 8) | 0xff,
+        8 => value,
+        _ => return None,
+    };
+    Some(rgba.into())
+}
+
+fn parse_function(args: &str) -> Option<Color> {
+    let (rgb, alpha): (Vec<&str>, Option<&str>) = if args.contains(',') {
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = (parts.len() == 4).then(|| parts.pop()).flatten();
+        (parts, alpha)
+    } else {
+        let (rgb, alpha) = match args.split_once('/') {
+            Some((rgb, alpha)) => (rgb, Some(alpha.trim())),
+            None => (args, None),
+        };
+        (rgb.split_whitespace().collect(), alpha)
+    };
+    let [r, g, b] = rgb.as_slice() else {
+        return None;
+    };
+    let channel = |s: &str| parse_number(s, 255.).map(|v| v / 255.);
+    let alpha = match alpha {
+        Some(a) => parse_number(a, 1.)?,
+        None => 1.,
+    };
+    Some(Color::from_normalized([
+        channel(r)?,
+        channel(g)?,
+        channel(b)?,
+        alpha,
+    ]))
+}
+
+/// Parse a number, or a percentage of `full`
+fn parse_number(s: &str, full: f32) -> Option<f32> {
+    let value = match s.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().ok()? / 100. * full,
+        None => s.parse::<f32>().ok()?,
+    };
+    value.is_finite().then_some(value)
+}
+
+/// CSS named colors, sorted by name
+const CSS_NAMES: &[(&str, Color)] = &[
+    ("aliceblue", Color::rgb(0xf0, 0xf8, 0xff)),
+    ("antiquewhite", Color::rgb(0xfa, 0xeb, 0xd7)),
+    ("aqua", Color::rgb(0x00, 0xff, 0xff)),
+    ("aquamarine", Color::rgb(0x7f, 0xff, 0xd4)),
+    ("azure", Color::rgb(0xf0, 0xff, 0xff)),
+    ("beige", Color::rgb(0xf5, 0xf5, 0xdc)),
+    ("bisque", Color::rgb(0xff, 0xe4, 0xc4)),
+    ("black", Color::rgb(0x00, 0x00, 0x00)),
+    ("blanchedalmond", Color::rgb(0xff, 0xeb, 0xcd)),
+    ("blue", Color::rgb(0x00, 0x00, 0xff)),
+    ("blueviolet", Color::rgb(0x8a, 0x2b, 0xe2)),
+    ("brown", Color::rgb(0xa5, 0x2a, 0x2a)),
+    ("burlywood", Color::rgb(0xde, 0xb8, 0x87)),
+    ("cadetblue", Color::rgb(0x5f, 0x9e, 0xa0)),
+    ("chartreuse", Color::rgb(0x7f, 0xff, 0x00)),
+    ("chocolate", Color::rgb(0xd2, 0x69, 0x1e)),
+    ("coral", Color::rgb(0xff, 0x7f, 0x50)),
+    ("cornflowerblue", Color::rgb(0x64, 0x95, 0xed)),
+    ("cornsilk", Color::rgb(0xff, 0xf8, 0xdc)),
+    ("crimson", Color::rgb(0xdc, 0x14, 0x3c)),
+    ("cyan", Color::rgb(0x00, 0xff, 0xff)),
+    ("darkblue", Color::rgb(0x00, 0x00, 0x8b)),
+    ("darkcyan", Color::rgb(0x00, 0x8b, 0x8b)),
+    ("darkgoldenrod", Color::rgb(0xb8, 0x86, 0x0b)),
+    ("darkgray", Color::rgb(0xa9, 0xa9, 0xa9)),
+    ("darkgreen", Color::rgb(0x00, 0x64, 0x00)),
+    ("darkgrey", Color::rgb(0xa9, 0xa9, 0xa9)),
+    ("darkkhaki", Color::rgb(0xbd, 0xb7, 0x6b)),
+    ("darkmagenta", Color::rgb(0x8b, 0x00, 0x8b)),
+    ("darkolivegreen", Color::rgb(0x55, 0x6b, 0x2f)),
+    ("darkorange", Color::rgb(0xff, 0x8c, 0x00)),
+    ("darkorchid", Color::rgb(0x99, 0x32, 0xcc)),
+    ("darkred", Color::rgb(0x8b, 0x00, 0x00)),
+    ("darksalmon", Color::rgb(0xe9, 0x96, 0x7a)),
+    ("darkseagreen", Color::rgb(0x8f, 0xbc, 0x8f)),
+    ("darkslateblue", Color::rgb(0x48, 0x3d, 0x8b)),
+    ("darkslategray", Color::rgb(0x2f, 0x4f, 0x4f)),
+    ("darkslategrey", Color::rgb(0x2f, 0x4f, 0x4f)),
+    ("darkturquoise", Color::rgb(0x00, 0xce, 0xd1)),
+    ("darkviolet", Color::rgb(0x94, 0x00, 0xd3)),
+    ("deeppink", Color::rgb(0xff, 0x14, 0x93)),
+    ("deepskyblue", Color::rgb(0x00, 0xbf, 0xff)),
+    ("dimgray", Color::rgb(0x69, 0x69, 0x69)),
+    ("dimgrey", Color::rgb(0x69, 0x69, 0x69)),
+    ("dodgerblue", Color::rgb(0x1e, 0x90, 0xff)),
+    ("firebrick", Color::rgb(0xb2, 0x22, 0x22)),
+    ("floralwhite", Color::rgb(0xff, 0xfa, 0xf0)),
+    ("forestgreen", Color::rgb(0x22, 0x8b, 0x22)),
+    ("fuchsia", Color::rgb(0xff, 0x00, 0xff)),
+    ("gainsboro", Color::rgb(0xdc, 0xdc, 0xdc)),
+    ("ghostwhite", Color::rgb(0xf8, 0xf8, 0xff)),
+    ("gold", Color::rgb(0xff, 0xd7, 0x00)),
+    ("goldenrod", Color::rgb(0xda, 0xa5, 0x20)),
+    ("gray", Color::rgb(0x80, 0x80, 0x80)),
+    ("green", Color::rgb(0x00, 0x80, 0x00)),
+    ("greenyellow", Color::rgb(0xad, 0xff, 0x2f)),
+    ("grey", Color::rgb(0x80, 0x80, 0x80)),
+    ("honeydew", Color::rgb(0xf0, 0xff, 0xf0)),
+    ("hotpink", Color::rgb(0xff, 0x69, 0xb4)),
+    ("indianred", Color::rgb(0xcd, 0x5c, 0x5c)),
+    ("indigo", Color::rgb(0x4b, 0x00, 0x82)),
+    ("ivory", Color::rgb(0xff, 0xff, 0xf0)),
+    ("khaki", Color::rgb(0xf0, 0xe6, 0x8c)),
+    ("lavender", Color::rgb(0xe6, 0xe6, 0xfa)),
+    ("lavenderblush", Color::rgb(0xff, 0xf0, 0xf5)),
+    ("lawngreen", Color::rgb(0x7c, 0xfc, 0x00)),
+    ("lemonchiffon", Color::rgb(0xff, 0xfa, 0xcd)),
+    ("lightblue", Color::rgb(0xad, 0xd8, 0xe6)),
+    ("lightcoral", Color::rgb(0xf0, 0x80, 0x80)),
+    ("lightcyan", Color::rgb(0xe0, 0xff, 0xff)),
+    ("lightgoldenrodyellow", Color::rgb(0xfa, 0xfa, 0xd2)),
+    ("lightgray", Color::rgb(0xd3, 0xd3, 0xd3)),
+    ("lightgreen", Color::rgb(0x90, 0xee, 0x90)),
+    ("lightgrey", Color::rgb(0xd3, 0xd3, 0xd3)),
+    ("lightpink", Color::rgb(0xff, 0xb6, 0xc1)),
+    ("lightsalmon", Color::rgb(0xff, 0xa0, 0x7a)),
+    ("lightseagreen", Color::rgb(0x20, 0xb2, 0xaa)),
+    ("lightskyblue", Color::rgb(0x87, 0xce, 0xfa)),
+    ("lightslategray", Color::rgb(0x77, 0x88, 0x99)),
+    ("lightslategrey", Color::rgb(0x77, 0x88, 0x99)),
+    ("lightsteelblue", Color::rgb(0xb0, 0xc4, 0xde)),
+    ("lightyellow", Color::rgb(0xff, 0xff, 0xe0)),
+    ("lime", Color::rgb(0x00, 0xff, 0x00)),
+    ("limegreen", Color::rgb(0x32, 0xcd, 0x32)),
+    ("linen", Color::rgb(0xfa, 0xf0, 0xe6)),
+    ("magenta", Color::rgb(0xff, 0x00, 0xff)),
+    ("maroon", Color::rgb(0x80, 0x00, 0x00)),
+    ("mediumaquamarine", Color::rgb(0x66, 0xcd, 0xaa)),
+    ("mediumblue", Color::rgb(0x00, 0x00, 0xcd)),
+    ("mediumorchid", Color::rgb(0xba, 0x55, 0xd3)),
+    ("mediumpurple", Color::rgb(0x93, 0x70, 0xdb)),
+    ("mediumseagreen", Color::rgb(0x3c, 0xb3, 0x71)),
+    ("mediumslateblue", Color::rgb(0x7b, 0x68, 0xee)),
+    ("mediumspringgreen", Color::rgb(0x00, 0xfa, 0x9a)),
+    ("mediumturquoise", Color::rgb(0x48, 0xd1, 0xcc)),
+    ("mediumvioletred", Color::rgb(0xc7, 0x15, 0x85)),
+    ("midnightblue", Color::rgb(0x19, 0x19, 0x70)),
+    ("mintcream", Color::rgb(0xf5, 0xff, 0xfa)),
+    ("mistyrose", Color::rgb(0xff, 0xe4, 0xe1)),
+    ("moccasin", Color::rgb(0xff, 0xe4, 0xb5)),
+    ("navajowhite", Color::rgb(0xff, 0xde, 0xad)),
+    ("navy", Color::rgb(0x00, 0x00, 0x80)),
+    ("oldlace", Color::rgb(0xfd, 0xf5, 0xe6)),
+    ("olive", Color::rgb(0x80, 0x80, 0x00)),
+    ("olivedrab", Color::rgb(0x6b, 0x8e, 0x23)),
+    ("orange", Color::rgb(0xff, 0xa5, 0x00)),
+    ("orangered", Color::rgb(0xff, 0x45, 0x00)),
+    ("orchid", Color::rgb(0xda, 0x70, 0xd6)),
+    ("palegoldenrod", Color::rgb(0xee, 0xe8, 0xaa)),
+    ("palegreen", Color::rgb(0x98, 0xfb, 0x98)),
+    ("paleturquoise", Color::rgb(0xaf, 0xee, 0xee)),
+    ("palevioletred", Color::rgb(0xdb, 0x70, 0x93)),
+    ("papayawhip", Color::rgb(0xff, 0xef, 0xd5)),
+    ("peachpuff", Color::rgb(0xff, 0xda, 0xb9)),
+    ("peru", Color::rgb(0xcd, 0x85, 0x3f)),
+    ("pink", Color::rgb(0xff, 0xc0, 0xcb)),
+    ("plum", Color::rgb(0xdd, 0xa0, 0xdd)),
+    ("powderblue", Color::rgb(0xb0, 0xe0, 0xe6)),
+    ("purple", Color::rgb(0x80, 0x00, 0x80)),
+    ("rebeccapurple", Color::rgb(0x66, 0x33, 0x99)),
+    ("red", Color::rgb(0xff, 0x00, 0x00)),
+    ("rosybrown", Color::rgb(0xbc, 0x8f, 0x8f)),
+    ("royalblue", Color::rgb(0x41, 0x69, 0xe1)),
+    ("saddlebrown", Color::rgb(0x8b, 0x45, 0x13)),
+    ("salmon", Color::rgb(0xfa, 0x80, 0x72)),
+    ("sandybrown", Color::rgb(0xf4, 0xa4, 0x60)),
+    ("seagreen", Color::rgb(0x2e, 0x8b, 0x57)),
+    ("seashell", Color::rgb(0xff, 0xf5, 0xee)),
+    ("sienna", Color::rgb(0xa0, 0x52, 0x2d)),
+    ("silver", Color::rgb(0xc0, 0xc0, 0xc0)),
+    ("skyblue", Color::rgb(0x87, 0xce, 0xeb)),
+    ("slateblue", Color::rgb(0x6a, 0x5a, 0xcd)),
+    ("slategray", Color::rgb(0x70, 0x80, 0x90)),
+    ("slategrey", Color::rgb(0x70, 0x80, 0x90)),
+    ("snow", Color::rgb(0xff, 0xfa, 0xfa)),
+    ("springgreen", Color::rgb(0x00, 0xff, 0x7f)),
+    ("steelblue", Color::rgb(0x46, 0x82, 0xb4)),
+    ("tan", Color::rgb(0xd2, 0xb4, 0x8c)),
+    ("teal", Color::rgb(0x00, 0x80, 0x80)),
+    ("thistle", Color::rgb(0xd8, 0xbf, 0xd8)),
+    ("tomato", Color::rgb(0xff, 0x63, 0x47)),
+    ("turquoise", Color::rgb(0x40, 0xe0, 0xd0)),
+    ("violet", Color::rgb(0xee, 0x82, 0xee)),
+    ("wheat", Color::rgb(0xf5, 0xde, 0xb3)),
+    ("white", Color::rgb(0xff, 0xff, 0xff)),
+    ("whitesmoke", Color::rgb(0xf5, 0xf5, 0xf5)),
+    ("yellow", Color::rgb(0xff, 0xff, 0x00)),
+    ("yellowgreen", Color::rgb(0x9a, 0xcd, 0x32)),
+];
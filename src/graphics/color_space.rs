@@ -0,0 +1,358 @@
+use crate::graphics::Color;
+
+/// A color in the HSV (hue, saturation, value) color space.
+///
+/// Convert from and to [`Color`] with [`From`].
+///
+/// # Usage example
+///
+/// ```
+/// # use sf2g::graphics::{Color, Hsv};
+/// let mut hsv = Hsv::from(Color::RED);
+/// hsv.hue += 120.;
+/// assert_eq!(Color::from(hsv), Color::GREEN);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hsv {
+    /// Hue, in degrees. Any value is accepted, and wrapped to `[0, 360)`.
+    pub hue: f32,
+    /// Saturation, in `[0, 1]`
+    pub saturation: f32,
+    /// Value (brightness), in `[0, 1]`
+    pub value: f32,
+    /// Alpha (opacity), in `[0, 1]`
+    pub alpha: f32,
+}
+
+/// A color in the HSL (hue, saturation, lightness) color space.
+///
+/// Convert from and to [`Color`] with [`From`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hsl {
+    /// Hue, in degrees. Any value is accepted, and wrapped to `[0, 360)`.
+    pub hue: f32,
+    /// Saturation, in `[0, 1]`
+    pub saturation: f32,
+    /// Lightness, in `[0, 1]`
+    pub lightness: f32,
+    /// Alpha (opacity), in `[0, 1]`
+    pub alpha: f32,
+}
+
+/// A color in the [Oklab](https://bottosson.github.io/posts/oklab/) perceptual color space.
+///
+/// Distances and interpolations in Oklab match how different colors look, so it is the
+/// right space to build gradients and palettes in. See [`Color::mix`].
+///
+/// Convert from and to [`Color`] with [`From`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oklab {
+    /// Perceived lightness, in `[0, 1]`
+    pub l: f32,
+    /// Green-red axis, roughly in `[-0.4, 0.4]`
+    pub a: f32,
+    /// Blue-yellow axis, roughly in `[-0.4, 0.4]`
+    pub b: f32,
+    /// Alpha (opacity), in `[0, 1]`
+    pub alpha: f32,
+}
+
+/// Float conversions
+impl Color {
+    /// Construct a color from RGBA components in `[0, 1]`.
+    ///
+    /// Components out of range are clamped.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// assert_eq!(Color::from_normalized([1., 0.5, 0., 1.]), Color::rgb(255, 128, 0));
+    /// ```
+    #[must_use]
+    pub fn from_normalized([r, g, b, a]: [f32; 4]) -> Self {
+        Self::rgba(to_u8(r), to_u8(g), to_u8(b), to_u8(a))
+    }
+
+    /// The RGBA components of the color, in `[0, 1]`.
+    #[must_use]
+    pub fn to_normalized(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a].map(|c| f32::from(c) / 255.)
+    }
+
+    /// Construct a color from linear RGB components and alpha, in `[0, 1]`.
+    ///
+    /// The RGB components are converted to sRGB, the color space of `Color`.
+    #[must_use]
+    pub fn from_linear([r, g, b, a]: [f32; 4]) -> Self {
+        Self::from_normalized([linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a])
+    }
+
+    /// The linear RGB components and the alpha of the color, in `[0, 1]`.
+    ///
+    /// Blending and lighting math should be done on linear components, since sRGB
+    /// components are not proportional to light intensity.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// let [r, g, b, a] = Color::rgb(255, 128, 0).to_linear();
+    /// assert!((g - 0.2158).abs() < 0.001);
+    /// assert_eq!(Color::from_linear([r, g, b, a]), Color::rgb(255, 128, 0));
+    /// ```
+    #[must_use]
+    pub fn to_linear(self) -> [f32; 4] {
+        let [r, g, b, a] = self.to_normalized();
+        [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+    }
+
+    /// Construct an opaque color from HSV components.
+    ///
+    /// See [`Hsv`].
+    #[must_use]
+    pub fn hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        Hsv {
+            hue,
+            saturation,
+            value,
+            alpha: 1.,
+        }
+        .into()
+    }
+
+    /// Construct an opaque color from HSL components.
+    ///
+    /// See [`Hsl`].
+    #[must_use]
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        Hsl {
+            hue,
+            saturation,
+            lightness,
+            alpha: 1.,
+        }
+        .into()
+    }
+}
+
+/// Blending
+impl Color {
+    /// Linearly interpolate between two colors, component by component.
+    ///
+    /// `t` is clamped to `[0, 1]`: 0 gives `self`, and 1 gives `other`.
+    ///
+    /// This is cheap, but the colors in between can look dull. Use [`mix`](Color::mix)
+    /// for perceptually even results.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// assert_eq!(Color::BLACK.lerp(Color::WHITE, 0.5), Color::rgb(128, 128, 128));
+    /// ```
+    #[must_use]
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0., 1.);
+        let [r0, g0, b0, a0] = self.to_normalized();
+        let [r1, g1, b1, a1] = other.to_normalized();
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self::from_normalized([mix(r0, r1), mix(g0, g1), mix(b0, b1), mix(a0, a1)])
+    }
+
+    /// Interpolate between two colors in the [`Oklab`] color space.
+    ///
+    /// `t` is clamped to `[0, 1]`: 0 gives `self`, and 1 gives `other`.
+    /// The colors in between look evenly spaced, which makes this ideal for gradients.
+    #[must_use]
+    pub fn mix(self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0., 1.);
+        let (a, b) = (Oklab::from(self), Oklab::from(other));
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Oklab {
+            l: mix(a.l, b.l),
+            a: mix(a.a, b.a),
+            b: mix(a.b, b.b),
+            alpha: mix(a.alpha, b.alpha),
+        }
+        .into()
+    }
+
+    /// Multiply the RGB components by the alpha component.
+    ///
+    /// Premultiplied colors are needed by [`BlendMode`]s that expect premultiplied alpha.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Color;
+    /// let color = Color::rgba(255, 100, 0, 128);
+    /// assert_eq!(color.premultiply(), Color::rgba(128, 50, 0, 128));
+    /// ```
+    ///
+    /// [`BlendMode`]: crate::graphics::BlendMode
+    #[must_use]
+    pub fn premultiply(self) -> Self {
+        let a = u16::from(self.a);
+        let mul = |c: u8| div_round(u16::from(c) * a, 255);
+        Self::rgba(mul(self.r), mul(self.g), mul(self.b), self.a)
+    }
+
+    /// Divide the RGB components by the alpha component, undoing
+    /// [`premultiply`](Color::premultiply).
+    ///
+    /// Fully transparent colors become [`Color::TRANSPARENT`]. Since premultiplying loses
+    /// precision, the result may differ slightly from the original color.
+    #[must_use]
+    pub fn unpremultiply(self) -> Self {
+        if self.a == 0 {
+            return Self::TRANSPARENT;
+        }
+        let a = u16::from(self.a);
+        let div = |c: u8| div_round(u16::from(c) * 255, a);
+        Self::rgba(div(self.r), div(self.g), div(self.b), self.a)
+    }
+}
+
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        let [r, g, b, alpha] = color.to_normalized();
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        Self {
+            hue: hue(r, g, b, max, delta),
+            saturation: if max == 0. { 0. } else { delta / max },
+            value: max,
+            alpha,
+        }
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        let chroma = hsv.value * hsv.saturation;
+        let [r, g, b] = hue_to_rgb(hsv.hue, chroma, hsv.value - chroma);
+        Self::from_normalized([r, g, b, hsv.alpha])
+    }
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let [r, g, b, alpha] = color.to_normalized();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let lightness = (max + min) / 2.;
+        let saturation = if delta == 0. {
+            0.
+        } else {
+            delta / (1. - (2. * lightness - 1.).abs())
+        };
+        Self {
+            hue: hue(r, g, b, max, delta),
+            saturation,
+            lightness,
+            alpha,
+        }
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let chroma = (1. - (2. * hsl.lightness - 1.).abs()) * hsl.saturation;
+        let [r, g, b] = hue_to_rgb(hsl.hue, chroma, hsl.lightness - chroma / 2.);
+        Self::from_normalized([r, g, b, hsl.alpha])
+    }
+}
+
+impl From<Color> for Oklab {
+    fn from(color: Color) -> Self {
+        let [r, g, b, alpha] = color.to_linear();
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        Self {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+            alpha,
+        }
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(lab: Oklab) -> Self {
+        let l = (lab.l + 0.396_337_78 * lab.a + 0.215_803_76 * lab.b).powi(3);
+        let m = (lab.l - 0.105_561_346 * lab.a - 0.063_854_17 * lab.b).powi(3);
+        let s = (lab.l - 0.089_484_18 * lab.a - 1.291_485_5 * lab.b).powi(3);
+        Self::from_linear([
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+            lab.alpha,
+        ])
+    }
+}
+
+/// Hue in degrees of normalized RGB components, given their max and max - min
+fn hue(r: f32, g: f32, b: f32, max: f32, delta: f32) -> f32 {
+    if delta == 0. {
+        return 0.;
+    }
+    let hue = if max == r {
+        (g - b) / delta
+    } else if max == g {
+        (b - r) / delta + 2.
+    } else {
+        (r - g) / delta + 4.
+    };
+    (hue * 60.).rem_euclid(360.)
+}
+
+/// Normalized RGB components of a hue, given the chroma and the amount to add to
+/// every component
+fn hue_to_rgb(hue: f32, chroma: f32, offset: f32) -> [f32; 3] {
+    let h = hue.rem_euclid(360.) / 60.;
+    let x = chroma * (1. - (h % 2. - 1.).abs());
+    let [r, g, b] = match h {
+        h if h < 1. => [chroma, x, 0.],
+        h if h < 2. => [x, chroma, 0.],
+        h if h < 3. => [0., chroma, x],
+        h if h < 4. => [0., x, chroma],
+        h if h < 5. => [x, 0., chroma],
+        _ => [chroma, 0., x],
+    };
+    [r + offset, g + offset, b + offset]
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "Clamped to the range of u8"
+)]
+fn to_u8(c: f32) -> u8 {
+    (c.clamp(0., 1.) * 255.).round() as u8
+}
+
+fn div_round(num: u16, den: u16) -> u8 {
+    ((num + den / 2) / den).min(255) as u8
+}
@@ -0,0 +1,319 @@
+use {
+    crate::{
+        graphics::{Color, FloatRect, PrimitiveType, RenderStates, RenderTarget, Vertex},
+        system::{Vector2f, Vector2i},
+    },
+    std::{cell::RefCell, f32::consts::TAU},
+};
+
+/// Whether debug drawing is compiled in
+const ENABLED: bool = cfg!(any(debug_assertions, feature = "debug-draw"));
+/// Size of [`DebugDraw::debug_point`] squares, in pixels
+const POINT_SIZE: f32 = 4.;
+/// Length of the head of [`DebugDraw::debug_arrow`] arrows, in pixels
+const ARROW_HEAD: f32 = 8.;
+/// Size of a pixel of the debug font, in screen pixels
+const TEXT_SCALE: f32 = 2.;
+/// Maximum number of lines drawn by a single [`DebugDraw::debug_grid`] call
+const MAX_GRID_LINES: usize = 1024;
+
+enum Command {
+    Line(Vector2f, Vector2f, Color),
+    Circle(Vector2f, f32, Color),
+    Arrow(Vector2f, Vector2f, Color),
+    Point(Vector2f, Color),
+    Text(Vector2f, String, Color),
+}
+
+/// Commands queued on a render target
+struct Queue {
+    /// Address of the target
+    target: usize,
+    commands: Vec<Command>,
+}
+
+thread_local! {
+    static QUEUES: RefCell<Vec<Queue>> = const { RefCell::new(Vec::new()) };
+}
+
+fn target_key<T: ?Sized>(target: &T) -> usize {
+    std::ptr::from_ref(target).cast::<()>().addr()
+}
+
+fn push<T: ?Sized>(target: &T, command: Command) {
+    let target = target_key(target);
+    QUEUES.with_borrow_mut(
+        |queues| match queues.iter_mut().find(|queue| queue.target == target) {
+            Some(queue) => queue.commands.push(command),
+            None => queues.push(Queue {
+                target,
+                commands: vec![command],
+            }),
+        },
+    );
+}
+
+/// Take the commands queued on `target`
+fn take<T: ?Sized>(target: &T) -> Vec<Command> {
+    let target = target_key(target);
+    QUEUES.with_borrow_mut(|queues| {
+        queues
+            .iter_mut()
+            .find(|queue| queue.target == target)
+            .map(|queue| std::mem::take(&mut queue.commands))
+            .unwrap_or_default()
+    })
+}
+
+/// Immediate-mode debug drawing, for any [`RenderTarget`].
+///
+/// The `debug_*` methods queue shapes in a buffer for the target, and
+/// [`debug_flush`](DebugDraw::debug_flush) draws all of them with a single
+/// [`draw_primitives`](RenderTarget::draw_primitives) call, then empties the buffer.
+/// Call it once per frame, after drawing the scene.
+///
+/// Buffers are per thread, and identify targets by their address, so flush a target
+/// before dropping it, or its shapes could be drawn on a target later created at the
+/// same address.
+///
+/// Positions are in world coordinates, and are mapped with the view active when flushing.
+/// Line widths, point sizes and text sizes are in pixels, so they stay readable at any zoom.
+/// Text uses a small built-in font, so no [`Font`] is needed.
+///
+/// In release builds, all the methods are no-ops, unless the `debug-draw` feature is enabled.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::Vector2f};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let (pos, vel): (Vector2f, Vector2f) = unimplemented!();
+/// window.clear(Color::BLACK);
+/// // Draw the scene...
+/// window.debug_arrow(pos, pos + vel, Color::GREEN);
+/// window.debug_text(pos, &format!("{:.1} {:.1}", vel.x, vel.y), Color::WHITE);
+/// window.debug_flush();
+/// window.display();
+/// ```
+///
+/// [`Font`]: crate::graphics::Font
+pub trait DebugDraw: RenderTarget {
+    /// Queue a line from `a` to `b`.
+    fn debug_line(&mut self, a: Vector2f, b: Vector2f, color: Color) {
+        if ENABLED {
+            push(self, Command::Line(a, b, color));
+        }
+    }
+    /// Queue the outline of `rect`.
+    fn debug_rect(&mut self, rect: FloatRect, color: Color) {
+        if ENABLED {
+            let (min, max) = (rect.min_corner(), rect.max_corner());
+            let (tr, bl) = (Vector2f::new(max.x, min.y), Vector2f::new(min.x, max.y));
+            for (a, b) in [(min, tr), (tr, max), (max, bl), (bl, min)] {
+                push(self, Command::Line(a, b, color));
+            }
+        }
+    }
+    /// Queue the outline of a circle.
+    fn debug_circle(&mut self, center: Vector2f, radius: f32, color: Color) {
+        if ENABLED {
+            push(self, Command::Circle(center, radius, color));
+        }
+    }
+    /// Queue an arrow from `from` to `to`, with its head at `to`.
+    fn debug_arrow(&mut self, from: Vector2f, to: Vector2f, color: Color) {
+        if ENABLED {
+            push(self, Command::Arrow(from, to, color));
+        }
+    }
+    /// Queue grid lines covering `area`, every `spacing` units, aligned on the origin.
+    ///
+    /// Useful to show tile grids. At most 1024 lines are drawn in each direction.
+    fn debug_grid(&mut self, area: FloatRect, spacing: Vector2f, color: Color) {
+        if !ENABLED || spacing.x <= 0. || spacing.y <= 0. {
+            return;
+        }
+        let (min, max) = (area.min_corner(), area.max_corner());
+        let mut x = (min.x / spacing.x).ceil() * spacing.x;
+        for _ in 0..MAX_GRID_LINES {
+            if x > max.x {
+                break;
+            }
+            push(
+                self,
+                Command::Line(Vector2f::new(x, min.y), Vector2f::new(x, max.y), color),
+            );
+            x += spacing.x;
+        }
+        let mut y = (min.y / spacing.y).ceil() * spacing.y;
+        for _ in 0..MAX_GRID_LINES {
+            if y > max.y {
+                break;
+            }
+            push(
+                self,
+                Command::Line(Vector2f::new(min.x, y), Vector2f::new(max.x, y), color),
+            );
+            y += spacing.y;
+        }
+    }
+    /// Queue a small square centered on `point`.
+    fn debug_point(&mut self, point: Vector2f, color: Color) {
+        if ENABLED {
+            push(self, Command::Point(point, color));
+        }
+    }
+    /// Queue `text`, with its top left corner at `position`.
+    ///
+    /// The built-in font only has ASCII characters, and no lowercase letters: lowercase
+    /// letters are drawn in uppercase, and other characters as `?`. `\n` starts a new line.
+    fn debug_text(&mut self, position: Vector2f, text: &str, color: Color) {
+        if ENABLED {
+            push(self, Command::Text(position, text.to_owned(), color));
+        }
+    }
+    /// Draw everything queued since the last flush on this target, and empty the queue.
+    ///
+    /// Everything is drawn in a single [`draw_primitives`](RenderTarget::draw_primitives)
+    /// call, with the current view and default render states.
+    fn debug_flush(&mut self) {
+        if !ENABLED {
+            return;
+        }
+        let commands = take(self);
+        if commands.is_empty() {
+            return;
+        }
+        // Size of a pixel in world units, for the current view
+        let origin = self.map_pixel_to_coords_current_view(Vector2i::new(0, 0));
+        let px = (self.map_pixel_to_coords_current_view(Vector2i::new(1, 0)) - origin)
+            .length_sq()
+            .sqrt();
+        let mut out = Vec::new();
+        for command in &commands {
+            match *command {
+                Command::Line(a, b, color) => line(&mut out, a, b, px, color),
+                Command::Circle(center, radius, color) => {
+                    circle(&mut out, center, radius, px, color);
+                }
+                Command::Arrow(from, to, color) => arrow(&mut out, from, to, px, color),
+                Command::Point(point, color) => {
+                    let half = Vector2f::new(POINT_SIZE, POINT_SIZE) * (px / 2.);
+                    rect(&mut out, point - half, point + half, color);
+                }
+                Command::Text(position, ref text, color) => {
+                    text_quads(&mut out, position, text, px * TEXT_SCALE, color);
+                }
+            }
+        }
+        self.draw_primitives(&out, PrimitiveType::TRIANGLES, &RenderStates::DEFAULT);
+        // Give the allocation back, unless new commands were queued while drawing
+        let target = target_key(self);
+        QUEUES.with_borrow_mut(|queues| {
+            if let Some(queue) = queues.iter_mut().find(|queue| queue.target == target) {
+                if queue.commands.is_empty() {
+                    let mut commands = commands;
+                    commands.clear();
+                    queue.commands = commands;
+                }
+            }
+        });
+    }
+}
+
+impl<T: RenderTarget + ?Sized> DebugDraw for T {}
+
+fn quad(out: &mut Vec<Vertex>, [a, b, c, d]: [Vector2f; 4], color: Color) {
+    out.extend([a, b, c, a, c, d].map(|p| Vertex::with_pos_color(p, color)));
+}
+
+fn rect(out: &mut Vec<Vertex>, min: Vector2f, max: Vector2f, color: Color) {
+    let (tr, bl) = (Vector2f::new(max.x, min.y), Vector2f::new(min.x, max.y));
+    quad(out, [min, tr, max, bl], color);
+}
+
+fn line(out: &mut Vec<Vertex>, a: Vector2f, b: Vector2f, width: f32, color: Color) {
+    let len = (b - a).length_sq().sqrt();
+    if len == 0. {
+        return;
+    }
+    let n = (b - a).perpendicular() * (width / 2. / len);
+    quad(out, [a + n, b + n, b - n, a - n], color);
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss,
+    reason = "Segment counts are small and positive"
+)]
+fn circle(out: &mut Vec<Vertex>, center: Vector2f, radius: f32, px: f32, color: Color) {
+    // More segments for circles that are larger on screen
+    let segments = ((radius / px).abs().sqrt() * 4.).clamp(8., 64.) as usize;
+    let at = |i: usize| {
+        let (sin, cos) = (i as f32 * TAU / segments as f32).sin_cos();
+        center + Vector2f::new(cos, sin) * radius
+    };
+    for i in 0..segments {
+        line(out, at(i), at(i + 1), px, color);
+    }
+}
+
+fn arrow(out: &mut Vec<Vertex>, from: Vector2f, to: Vector2f, px: f32, color: Color) {
+    let len = (to - from).length_sq().sqrt();
+    if len == 0. {
+        return;
+    }
+    line(out, from, to, px, color);
+    let head = (ARROW_HEAD * px).min(len / 2.);
+    let back = (from - to) * (head / len);
+    let side = back.perpendicular() / 2.;
+    line(out, to, to + back + side, px, color);
+    line(out, to, to + back - side, px, color);
+}
+
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "Glyph coordinates are tiny numbers"
+)]
+fn text_quads(out: &mut Vec<Vertex>, position: Vector2f, text: &str, scale: f32, color: Color) {
+    let mut origin = position;
+    for ch in text.chars() {
+        if ch == '\n' {
+            origin = Vector2f::new(position.x, origin.y + 7. * scale);
+            continue;
+        }
+        let glyph = glyph(ch);
+        for row in 0..5 {
+            for col in 0..3 {
+                if (glyph >> (14 - (row * 3 + col))) & 1 == 1 {
+                    let min = origin + Vector2f::new(col as f32, row as f32) * scale;
+                    rect(out, min, min + Vector2f::new(scale, scale), color);
+                }
+            }
+        }
+        origin.x += 4. * scale;
+    }
+}
+
+/// The 3x5 bitmap of `ch`, one octal digit per row, from top to bottom
+fn glyph(ch: char) -> u16 {
+    let ch = ch.to_ascii_uppercase();
+    match u32::from(ch).checked_sub(u32::from(' ')) {
+        Some(i) if (i as usize) < FONT.len() => FONT[i as usize],
+        _ => FONT[usize::from(b'?' - b' ')],
+    }
+}
+
+/// Glyphs from `' '` to `'_'`
+#[rustfmt::skip]
+const FONT: [u16; 64] = [
+    0o00000, 0o22202, 0o55000, 0o57575, 0o36236, 0o51245, 0o25253, 0o22000,
+    0o12221, 0o42224, 0o05250, 0o02720, 0o00024, 0o00700, 0o00002, 0o11244,
+    0o75557, 0o26227, 0o71747, 0o71717, 0o55711, 0o74717, 0o74757, 0o71111,
+    0o75757, 0o75717, 0o02020, 0o02024, 0o12421, 0o07070, 0o42124, 0o71302,
+    0o75747, 0o25755, 0o65656, 0o34443, 0o65556, 0o74647, 0o74644, 0o34553,
+    0o55755, 0o72227, 0o11152, 0o55655, 0o44447, 0o57755, 0o65555, 0o25552,
+    0o65644, 0o25563, 0o65655, 0o34216, 0o72222, 0o55557, 0o55552, 0o55775,
+    0o55255, 0o55222, 0o71247, 0o64446, 0o44211, 0o31113, 0o25000, 0o00007,
+];
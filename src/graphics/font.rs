@@ -1,9 +1,10 @@
 use {
     crate::{
-        IntoSfResult, SfResult,
+        SfResult,
         cpp::FBox,
         ffi::graphics as ffi,
         graphics::{Glyph, Texture},
+        sf_call,
     },
     std::ffi::{CStr, CString},
 };
@@ -57,7 +58,7 @@ pub Font;
 impl Font {
     /// Creates a new (empty) font.
     pub fn new() -> SfResult<FBox<Self>> {
-        FBox::new(unsafe { ffi::sfFont_new() }).into_sf_result()
+        sf_call(|| FBox::new(unsafe { ffi::sfFont_new() }))
     }
     /// Creates a new `Font` from a file on the filesystem.
     ///
@@ -114,7 +115,7 @@ impl Font {
     /// ```
     pub fn load_from_file(&mut self, path: &str) -> SfResult<()> {
         let c_str = CString::new(path)?;
-        unsafe { ffi::sfFont_loadFromFile(self, c_str.as_ptr()) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfFont_loadFromFile(self, c_str.as_ptr()) })
     }
 
     /// Load the font from a file in memory.
@@ -132,7 +133,7 @@ impl Font {
     ///
     /// [`Font::from_file`], [`Font::from_stream`]
     pub unsafe fn load_from_memory(&mut self, data: &[u8]) -> SfResult<()> {
-        unsafe { ffi::sfFont_loadFromMemory(self, data.as_ptr(), data.len()) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfFont_loadFromMemory(self, data.as_ptr(), data.len()) })
     }
     /// Load the font from a file in static memory.
     ///
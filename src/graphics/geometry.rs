@@ -0,0 +1,454 @@
+//! Collision tests and geometry queries between shapes, polygons and rectangles.
+//!
+//! Polygons are slices of points in world coordinates, in order around the polygon.
+//! The overlap tests use the separating axis theorem, which requires convex polygons.
+//! Point and ray queries work with any simple polygon.
+//!
+//! Shapes are converted to polygons with [`world_points`], so their outline is not taken
+//! into account.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! # use sf2g::{cpp::FBox, graphics::{*, geometry}};
+//! # let mut player: FBox<RectangleShape> = unimplemented!();
+//! # let wall: FBox<ConvexShape> = unimplemented!();
+//! if let Some(contact) = geometry::shapes_overlap(&*player, &*wall) {
+//!     // Push the player out of the wall
+//!     player.move_(contact.mtv());
+//! }
+//! ```
+
+use crate::{
+    graphics::{FloatRect, Shape, Transform},
+    system::Vector2f,
+};
+
+/// The result of an overlap test between two objects `a` and `b`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Unit vector along which the objects overlap the least, pointing from `a` towards `b`
+    pub normal: Vector2f,
+    /// How deep the objects overlap along `normal`
+    pub depth: f32,
+}
+
+impl Contact {
+    /// The minimum translation vector: the smallest translation of `a` that separates
+    /// it from `b`.
+    #[must_use]
+    pub fn mtv(&self) -> Vector2f {
+        -self.normal * self.depth
+    }
+}
+
+/// Where a ray or segment hits an object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Distance from the origin of the cast to the hit point
+    pub distance: f32,
+    /// The hit point
+    pub point: Vector2f,
+    /// Unit normal of the edge that was hit, facing the origin of the cast.
+    ///
+    /// Zero if the origin is inside the object.
+    pub normal: Vector2f,
+}
+
+/// The points of `shape`, in world coordinates.
+#[must_use]
+pub fn world_points<'t, S: Shape<'t> + ?Sized>(shape: &S) -> Vec<Vector2f> {
+    let transform = shape.transform();
+    shape
+        .points()
+        .map(|p| transform.transform_point(p))
+        .collect()
+}
+
+/// The four corners of `rect`, clockwise from the top-left one.
+#[must_use]
+pub fn rect_points(rect: FloatRect) -> [Vector2f; 4] {
+    let (min, max) = (rect.min_corner(), rect.max_corner());
+    [
+        min,
+        Vector2f::new(max.x, min.y),
+        max,
+        Vector2f::new(min.x, max.y),
+    ]
+}
+
+/// Test two convex shapes for overlap.
+///
+/// Returns the contact with the smallest penetration depth, or `None` if they don't overlap.
+/// Shapes that only touch don't overlap.
+#[must_use]
+pub fn shapes_overlap<'a, 'b, A, B>(a: &A, b: &B) -> Option<Contact>
+where
+    A: Shape<'a> + ?Sized,
+    B: Shape<'b> + ?Sized,
+{
+    polygons_overlap(&world_points(a), &world_points(b))
+}
+
+/// Test two convex polygons for overlap.
+///
+/// Returns the contact with the smallest penetration depth, or `None` if they don't overlap.
+/// Polygons that only touch don't overlap.
+///
+/// # Usage example
+///
+/// ```
+/// # use sf2g::{graphics::{FloatRect, geometry}, system::Vector2f};
+/// let a = geometry::rect_points(FloatRect::new(0., 0., 4., 4.));
+/// let b = geometry::rect_points(FloatRect::new(3., 1., 4., 4.));
+/// let contact = geometry::polygons_overlap(&a, &b).unwrap();
+/// assert_eq!(contact.normal, Vector2f::new(1., 0.));
+/// assert_eq!(contact.mtv(), Vector2f::new(-1., 0.));
+/// ```
+#[must_use]
+pub fn polygons_overlap(a: &[Vector2f], b: &[Vector2f]) -> Option<Contact> {
+    let mut best: Option<Contact> = None;
+    for axis in edge_normals(a).chain(edge_normals(b)) {
+        let (min_a, max_a) = project(a, axis);
+        let (min_b, max_b) = project(b, axis);
+        if max_a <= min_b || max_b <= min_a {
+            return None;
+        }
+        // Push `a` out through whichever side of `b` is closest
+        let contact = if max_a - min_b < max_b - min_a {
+            Contact {
+                normal: axis,
+                depth: max_a - min_b,
+            }
+        } else {
+            Contact {
+                normal: -axis,
+                depth: max_b - min_a,
+            }
+        };
+        if best.is_none_or(|best| contact.depth < best.depth) {
+            best = Some(contact);
+        }
+    }
+    best
+}
+
+/// Test a circle against a rectangle for overlap.
+///
+/// The contact normal points from the circle towards the rectangle.
+///
+/// # Usage example
+///
+/// ```
+/// # use sf2g::{graphics::{FloatRect, geometry}, system::Vector2f};
+/// let rect = FloatRect::new(0., 0., 10., 10.);
+/// let contact = geometry::circle_rect(Vector2f::new(5., 12.), 3., rect).unwrap();
+/// assert_eq!(contact.normal, Vector2f::new(0., -1.));
+/// assert_eq!(contact.depth, 1.);
+/// assert!(geometry::circle_rect(Vector2f::new(14., 5.), 3., rect).is_none());
+/// ```
+#[must_use]
+pub fn circle_rect(center: Vector2f, radius: f32, rect: FloatRect) -> Option<Contact> {
+    let closest = rect.clamp_point(center);
+    let delta = closest - center;
+    let dist_sq = delta.length_sq();
+    if dist_sq >= radius * radius {
+        return None;
+    }
+    if dist_sq > 0. {
+        let dist = dist_sq.sqrt();
+        return Some(Contact {
+            normal: delta / dist,
+            depth: radius - dist,
+        });
+    }
+    // The center is inside the rectangle: leave through the closest side
+    let (min, max) = (rect.min_corner(), rect.max_corner());
+    let sides = [
+        (center.x - min.x, Vector2f::new(1., 0.)),
+        (max.x - center.x, Vector2f::new(-1., 0.)),
+        (center.y - min.y, Vector2f::new(0., 1.)),
+        (max.y - center.y, Vector2f::new(0., -1.)),
+    ];
+    let (dist, normal) = sides
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .unwrap_or_default();
+    Some(Contact {
+        normal,
+        depth: dist + radius,
+    })
+}
+
+/// Check if `point` is inside `polygon`.
+///
+/// Works with concave polygons too. Points on the edges may be considered inside or outside.
+///
+/// # Usage example
+///
+/// ```
+/// # use sf2g::{graphics::geometry, system::Vector2f};
+/// // A "C" shape
+/// let polygon = [(0., 0.), (3., 0.), (3., 1.), (1., 1.), (1., 2.), (3., 2.), (3., 3.), (0., 3.)]
+///     .map(Vector2f::from);
+/// assert!(geometry::point_in_polygon(Vector2f::new(0.5, 1.5), &polygon));
+/// assert!(!geometry::point_in_polygon(Vector2f::new(2., 1.5), &polygon));
+/// ```
+#[must_use]
+pub fn point_in_polygon(point: Vector2f, polygon: &[Vector2f]) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(polygon) {
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Check if the world position `point` is inside `shape`.
+#[must_use]
+pub fn shape_contains_point<'t, S: Shape<'t> + ?Sized>(shape: &S, point: Vector2f) -> bool {
+    let local = shape.inverse_transform().transform_point(point);
+    shape.local_bounds().contains(local)
+        && point_in_polygon(local, &shape.points().collect::<Vec<_>>())
+}
+
+/// Cast a ray from `origin` along `direction` against `polygon`, and return the first hit.
+///
+/// `direction` doesn't need to be normalized. Returns `None` if it is zero.
+#[must_use]
+pub fn ray_polygon(origin: Vector2f, direction: Vector2f, polygon: &[Vector2f]) -> Option<RayHit> {
+    let length = direction.length_sq().sqrt();
+    if length == 0. {
+        return None;
+    }
+    cast(origin, direction / length, f32::INFINITY, polygon)
+}
+
+/// Cast the segment from `start` to `end` against `polygon`, and return the hit closest
+/// to `start`.
+#[must_use]
+pub fn segment_polygon(start: Vector2f, end: Vector2f, polygon: &[Vector2f]) -> Option<RayHit> {
+    let delta = end - start;
+    let length = delta.length_sq().sqrt();
+    if length == 0. {
+        return point_in_polygon(start, polygon).then_some(inside_hit(start));
+    }
+    cast(start, delta / length, length, polygon)
+}
+
+/// Cast a ray from `origin` along `direction` against `rect`, and return the first hit.
+///
+/// `direction` doesn't need to be normalized. Returns `None` if it is zero.
+///
+/// # Usage example
+///
+/// ```
+/// # use sf2g::{graphics::{FloatRect, geometry}, system::Vector2f};
+/// let rect = FloatRect::new(10., 0., 5., 5.);
+/// let hit = geometry::ray_rect(Vector2f::new(0., 2.), Vector2f::new(2., 0.), rect).unwrap();
+/// assert_eq!(hit.distance, 10.);
+/// assert_eq!(hit.point, Vector2f::new(10., 2.));
+/// assert_eq!(hit.normal, Vector2f::new(-1., 0.));
+/// ```
+#[must_use]
+pub fn ray_rect(origin: Vector2f, direction: Vector2f, rect: FloatRect) -> Option<RayHit> {
+    ray_polygon(origin, direction, &rect_points(rect))
+}
+
+/// Cast the segment from `start` to `end` against `rect`, and return the hit closest
+/// to `start`.
+///
+/// # Usage example
+///
+/// ```
+/// # use sf2g::{graphics::{FloatRect, geometry}, system::Vector2f};
+/// let rect = FloatRect::new(10., 0., 5., 5.);
+/// let start = Vector2f::new(0., 2.);
+/// assert!(geometry::segment_rect(start, Vector2f::new(8., 2.), rect).is_none());
+/// assert!(geometry::segment_rect(start, Vector2f::new(12., 2.), rect).is_some());
+/// ```
+#[must_use]
+pub fn segment_rect(start: Vector2f, end: Vector2f, rect: FloatRect) -> Option<RayHit> {
+    segment_polygon(start, end, &rect_points(rect))
+}
+
+/// Cast a ray from `origin` along `direction` against `shape`, and return the first hit.
+///
+/// `direction` doesn't need to be normalized. Returns `None` if it is zero.
+#[must_use]
+pub fn ray_shape<'t, S: Shape<'t> + ?Sized>(
+    origin: Vector2f,
+    direction: Vector2f,
+    shape: &S,
+) -> Option<RayHit> {
+    ray_polygon(origin, direction, &world_points(shape))
+}
+
+/// Cast the segment from `start` to `end` against `shape`, and return the hit closest
+/// to `start`.
+#[must_use]
+pub fn segment_shape<'t, S: Shape<'t> + ?Sized>(
+    start: Vector2f,
+    end: Vector2f,
+    shape: &S,
+) -> Option<RayHit> {
+    segment_polygon(start, end, &world_points(shape))
+}
+
+/// A rectangle that can be rotated, e.g. the bounds of a rotated shape.
+///
+/// Unlike [`Shape::global_bounds`], this fits rotated objects tightly.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OrientedRect {
+    /// The center of the rectangle
+    pub center: Vector2f,
+    /// Half of the width and height of the rectangle, before rotation
+    pub half_size: Vector2f,
+    /// Rotation of the rectangle around its center, in degrees
+    pub rotation: f32,
+}
+
+impl OrientedRect {
+    /// Create an oriented rectangle from its center, half size and rotation (in degrees).
+    #[must_use]
+    pub const fn new(center: Vector2f, half_size: Vector2f, rotation: f32) -> Self {
+        Self {
+            center,
+            half_size,
+            rotation,
+        }
+    }
+    /// The rectangle `rect` transformed by `transform`.
+    ///
+    /// The result is exact for combinations of translations, rotations and scales,
+    /// like the transforms of [`Transformable`] objects. Shears are not representable,
+    /// and give an approximation.
+    ///
+    /// [`Transformable`]: crate::graphics::Transformable
+    #[must_use]
+    pub fn from_transformed_rect(rect: FloatRect, transform: &Transform) -> Self {
+        let x_axis = transform.transform_vector(Vector2f::new(rect.width / 2., 0.));
+        let y_axis = transform.transform_vector(Vector2f::new(0., rect.height / 2.));
+        Self {
+            center: transform.transform_point(rect.center()),
+            half_size: Vector2f::new(x_axis.length_sq().sqrt(), y_axis.length_sq().sqrt()),
+            rotation: x_axis.y.atan2(x_axis.x).to_degrees(),
+        }
+    }
+    /// The local bounds of `shape`, transformed to world coordinates.
+    #[must_use]
+    pub fn from_shape<'t, S: Shape<'t> + ?Sized>(shape: &S) -> Self {
+        Self::from_transformed_rect(shape.local_bounds(), shape.transform())
+    }
+    /// The unit vectors along the width and height of the rectangle
+    #[must_use]
+    pub fn axes(&self) -> (Vector2f, Vector2f) {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        (Vector2f::new(cos, sin), Vector2f::new(-sin, cos))
+    }
+    /// The four corners of the rectangle, clockwise from the top-left one before rotation.
+    #[must_use]
+    pub fn corners(&self) -> [Vector2f; 4] {
+        let (x, y) = self.axes();
+        let (x, y) = (x * self.half_size.x, y * self.half_size.y);
+        let c = self.center;
+        [c - x - y, c + x - y, c + x + y, c - x + y]
+    }
+    /// The axis-aligned bounding rectangle of the oriented rectangle
+    #[must_use]
+    pub fn bounds(&self) -> FloatRect {
+        FloatRect::from_points(self.corners()).unwrap_or_default()
+    }
+    /// Check if `point` is inside the rectangle.
+    #[must_use]
+    pub fn contains(&self, point: Vector2f) -> bool {
+        let (x, y) = self.axes();
+        let d = point - self.center;
+        d.dot(x).abs() <= self.half_size.x && d.dot(y).abs() <= self.half_size.y
+    }
+    /// Test two oriented rectangles for overlap.
+    ///
+    /// See [`polygons_overlap`].
+    #[must_use]
+    pub fn overlap(&self, other: &OrientedRect) -> Option<Contact> {
+        polygons_overlap(&self.corners(), &other.corners())
+    }
+}
+
+/// Iterate over the edges of `polygon`, including the closing one
+fn edges(polygon: &[Vector2f]) -> impl Iterator<Item = (Vector2f, Vector2f)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Unit normals of the non-degenerate edges of `polygon`
+fn edge_normals(polygon: &[Vector2f]) -> impl Iterator<Item = Vector2f> + '_ {
+    edges(polygon).filter_map(|(a, b)| {
+        let normal = (b - a).perpendicular();
+        let length = normal.length_sq().sqrt();
+        (length > 0.).then(|| normal / length)
+    })
+}
+
+/// Smallest and largest projection of the points of `polygon` on `axis`
+fn project(polygon: &[Vector2f], axis: Vector2f) -> (f32, f32) {
+    polygon
+        .iter()
+        .map(|p| p.dot(axis))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+            (min.min(d), max.max(d))
+        })
+}
+
+fn inside_hit(point: Vector2f) -> RayHit {
+    RayHit {
+        distance: 0.,
+        point,
+        normal: Vector2f::default(),
+    }
+}
+
+/// Cast a ray along the unit vector `direction`, up to `max_distance`
+fn cast(
+    origin: Vector2f,
+    direction: Vector2f,
+    max_distance: f32,
+    polygon: &[Vector2f],
+) -> Option<RayHit> {
+    if point_in_polygon(origin, polygon) {
+        return Some(inside_hit(origin));
+    }
+    let mut best: Option<RayHit> = None;
+    for (a, b) in edges(polygon) {
+        let edge = b - a;
+        let denom = direction.cross(edge);
+        if denom == 0. {
+            continue;
+        }
+        let to_edge = a - origin;
+        let distance = to_edge.cross(edge) / denom;
+        let u = to_edge.cross(direction) / denom;
+        if !(0. ..=1.).contains(&u)
+            || !(0. ..=max_distance).contains(&distance)
+            || best.is_some_and(|best| best.distance <= distance)
+        {
+            continue;
+        }
+        let mut normal = edge.perpendicular();
+        normal = normal / normal.length_sq().sqrt();
+        if normal.dot(direction) > 0. {
+            normal = -normal;
+        }
+        best = Some(RayHit {
+            distance,
+            point: origin + direction * distance,
+            normal,
+        });
+    }
+    best
+}
@@ -150,18 +150,7 @@ pub struct Mat3(pub [f32; 9]);
 
 impl From<crate::graphics::Transform> for Mat3 {
     fn from(src: crate::graphics::Transform) -> Self {
-        let src = src.matrix;
-        let mut dest = [0.0; 9];
-        dest[0] = src[0];
-        dest[1] = src[1];
-        dest[2] = src[3];
-        dest[3] = src[4];
-        dest[4] = src[5];
-        dest[5] = src[7];
-        dest[6] = src[12];
-        dest[7] = src[13];
-        dest[8] = src[15];
-        Mat3(dest)
+        Mat3(src.matrix3())
     }
 }
 
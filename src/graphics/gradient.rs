@@ -0,0 +1,274 @@
+use crate::{
+    graphics::{Color, FloatRect, Shape, Vertex},
+    system::Vector2f,
+};
+
+/// The geometry of a [`Gradient`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// Colors change along the line from `start` to `end`, and are constant on lines
+    /// perpendicular to it.
+    Linear {
+        /// Point where the gradient is at offset 0
+        start: Vector2f,
+        /// Point where the gradient is at offset 1
+        end: Vector2f,
+    },
+    /// Colors change with the distance to `center`.
+    Radial {
+        /// Point where the gradient is at offset 0
+        center: Vector2f,
+        /// Distance from `center` where the gradient is at offset 1
+        radius: f32,
+    },
+}
+
+/// How colors are interpolated between the stops of a [`Gradient`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientInterpolation {
+    /// Interpolate the sRGB components, like CSS. See [`Color::lerp`].
+    #[default]
+    Srgb,
+    /// Interpolate in the Oklab color space, for perceptually even gradients.
+    /// See [`Color::mix`].
+    Oklab,
+}
+
+/// A linear or radial gradient with any number of color stops.
+///
+/// A gradient maps every point to a color. It can color existing vertices with
+/// [`apply`](Gradient::apply), or build subdivided geometry for shapes, polygons and
+/// rectangles. Vertex colors are interpolated linearly across triangles, so large triangles
+/// would only show the colors at their corners: subdividing them reproduces the gradient
+/// faithfully, and avoids banding.
+///
+/// The gradient is in the coordinate system of the vertices it is applied to. For shapes,
+/// that's their local coordinates, so the gradient moves with the shape when drawn with its
+/// transform.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{cpp::FBox, graphics::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let shape: FBox<CircleShape> = unimplemented!();
+/// let gradient = Gradient::radial(
+///     shape.local_bounds().center(),
+///     shape.radius(),
+///     &[(0., Color::WHITE), (0.7, Color::YELLOW), (1., Color::RED)],
+/// );
+/// let vertices = gradient.fill_shape(&*shape, 8);
+/// let states = RenderStates {
+///     transform: *shape.transform(),
+///     ..Default::default()
+/// };
+/// window.draw_primitives(&vertices, PrimitiveType::TRIANGLES, &states);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    /// The geometry of the gradient
+    pub kind: GradientKind,
+    /// How colors are interpolated between stops
+    pub interpolation: GradientInterpolation,
+    stops: Vec<(f32, Color)>,
+}
+
+/// Creation
+impl Gradient {
+    /// Create a gradient of kind `kind`, with color stops as `(offset, color)` pairs.
+    ///
+    /// Offsets are usually in `[0, 1]`, and don't need to be sorted. Before the first stop
+    /// and after the last one, the gradient has the color of that stop.
+    #[must_use]
+    pub fn new(kind: GradientKind, stops: &[(f32, Color)]) -> Self {
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            kind,
+            interpolation: GradientInterpolation::default(),
+            stops,
+        }
+    }
+    /// Create a linear gradient from `start` to `end`.
+    #[must_use]
+    pub fn linear(start: Vector2f, end: Vector2f, stops: &[(f32, Color)]) -> Self {
+        Self::new(GradientKind::Linear { start, end }, stops)
+    }
+    /// Create a linear gradient covering `rect` in the direction `angle`.
+    ///
+    /// `angle` is in degrees, clockwise from the X axis, like [`Transformable`] rotations.
+    /// Like in CSS, the gradient is centered on `rect`, and just long enough for its ends
+    /// to touch the corners of `rect`.
+    ///
+    /// [`Transformable`]: crate::graphics::Transformable
+    #[must_use]
+    pub fn linear_with_angle(rect: FloatRect, angle: f32, stops: &[(f32, Color)]) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let half = (rect.width * cos).abs() / 2. + (rect.height * sin).abs() / 2.;
+        let offset = Vector2f::new(cos, sin) * half;
+        let center = rect.center();
+        Self::linear(center - offset, center + offset, stops)
+    }
+    /// Create a radial gradient around `center`.
+    #[must_use]
+    pub fn radial(center: Vector2f, radius: f32, stops: &[(f32, Color)]) -> Self {
+        Self::new(GradientKind::Radial { center, radius }, stops)
+    }
+    /// Set how colors are interpolated between stops.
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: GradientInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+}
+
+/// Sampling
+impl Gradient {
+    /// The color stops, as `(offset, color)` pairs sorted by offset
+    #[must_use]
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+    /// The offset of `point` along the gradient, before clamping to the stops.
+    #[must_use]
+    pub fn offset_at(&self, point: Vector2f) -> f32 {
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                let axis = end - start;
+                let len_sq = axis.length_sq();
+                if len_sq == 0. {
+                    0.
+                } else {
+                    (point - start).dot(axis) / len_sq
+                }
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius == 0. {
+                    0.
+                } else {
+                    (point - center).length_sq().sqrt() / radius
+                }
+            }
+        }
+    }
+    /// The color at `offset` along the gradient.
+    ///
+    /// A gradient without stops is transparent.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::{graphics::*, system::Vector2f};
+    /// let gradient = Gradient::linear(
+    ///     Vector2f::new(0., 0.),
+    ///     Vector2f::new(100., 0.),
+    ///     &[(0., Color::BLACK), (1., Color::WHITE)],
+    /// );
+    /// assert_eq!(gradient.color_at_offset(0.5), Color::rgb(128, 128, 128));
+    /// assert_eq!(gradient.color_at_offset(2.), Color::WHITE);
+    /// ```
+    #[must_use]
+    pub fn color_at_offset(&self, offset: f32) -> Color {
+        let Some(&(first_offset, first)) = self.stops.first() else {
+            return Color::TRANSPARENT;
+        };
+        if offset <= first_offset {
+            return first;
+        }
+        for pair in self.stops.windows(2) {
+            let [(o0, c0), (o1, c1)] = [pair[0], pair[1]];
+            if offset <= o1 {
+                let t = if o1 > o0 {
+                    (offset - o0) / (o1 - o0)
+                } else {
+                    1.
+                };
+                return match self.interpolation {
+                    GradientInterpolation::Srgb => c0.lerp(c1, t),
+                    GradientInterpolation::Oklab => c0.mix(c1, t),
+                };
+            }
+        }
+        self.stops.last().map_or(first, |stop| stop.1)
+    }
+    /// The color of the gradient at `point`.
+    #[must_use]
+    pub fn color_at(&self, point: Vector2f) -> Color {
+        self.color_at_offset(self.offset_at(point))
+    }
+}
+
+/// Geometry
+impl Gradient {
+    /// Set the color of each vertex to the color of the gradient at its position.
+    pub fn apply(&self, vertices: &mut [Vertex]) {
+        for vertex in vertices {
+            vertex.color = self.color_at(vertex.position);
+        }
+    }
+    /// Build triangles filling `rect`, split into a grid of `columns` by `rows` cells.
+    ///
+    /// The vertices are meant to be drawn with [`PrimitiveType::TRIANGLES`].
+    ///
+    /// [`PrimitiveType::TRIANGLES`]: crate::graphics::PrimitiveType::TRIANGLES
+    #[must_use]
+    pub fn fill_rect(&self, rect: FloatRect, columns: usize, rows: usize) -> Vec<Vertex> {
+        rect.split_grid(columns.max(1), rows.max(1))
+            .into_iter()
+            .flat_map(|cell| {
+                let (min, max) = (cell.min_corner(), cell.max_corner());
+                let (tr, bl) = (Vector2f::new(max.x, min.y), Vector2f::new(min.x, max.y));
+                [min, tr, bl, tr, max, bl].map(|p| Vertex::with_pos_color(p, self.color_at(p)))
+            })
+            .collect()
+    }
+    /// Build triangles filling the convex polygon `points`, as a fan around its centroid.
+    ///
+    /// Each triangle of the fan is split into `subdivisions²` smaller triangles.
+    ///
+    /// The vertices are meant to be drawn with [`PrimitiveType::TRIANGLES`].
+    ///
+    /// [`PrimitiveType::TRIANGLES`]: crate::graphics::PrimitiveType::TRIANGLES
+    #[must_use]
+    #[expect(clippy::cast_precision_loss, reason = "Subdivisions are small numbers")]
+    pub fn fill_polygon(&self, points: &[Vector2f], subdivisions: usize) -> Vec<Vertex> {
+        if points.len() < 3 {
+            return Vec::new();
+        }
+        let n = subdivisions.max(1);
+        let centroid =
+            points.iter().fold(Vector2f::default(), |acc, &p| acc + p) / points.len() as f32;
+        let mut vertices = Vec::with_capacity(points.len() * n * n * 3);
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            let (u, v) = ((a - centroid) / n as f32, (b - centroid) / n as f32);
+            let at = |i: usize, j: usize| {
+                let p = centroid + u * i as f32 + v * j as f32;
+                Vertex::with_pos_color(p, self.color_at(p))
+            };
+            for i in 0..n {
+                for j in 0..n - i {
+                    vertices.extend([at(i, j), at(i + 1, j), at(i, j + 1)]);
+                    if i + j + 1 < n {
+                        vertices.extend([at(i + 1, j), at(i + 1, j + 1), at(i, j + 1)]);
+                    }
+                }
+            }
+        }
+        vertices
+    }
+    /// Build triangles filling `shape`, in its local coordinates.
+    ///
+    /// Draw them with the transform of the shape to place them like the shape.
+    /// The outline and texture of the shape are not taken into account.
+    ///
+    /// See [`fill_polygon`](Gradient::fill_polygon).
+    #[must_use]
+    pub fn fill_shape<'t, S: Shape<'t> + ?Sized>(
+        &self,
+        shape: &S,
+        subdivisions: usize,
+    ) -> Vec<Vertex> {
+        self.fill_polygon(&shape.points().collect::<Vec<_>>(), subdivisions)
+    }
+}
@@ -2,31 +2,58 @@
 
 #[doc(inline)]
 pub use self::blend_mode::BlendMode;
+#[cfg(feature = "serde")]
+pub use self::sprite_atlas::{
+    AtlasFrame, AtlasSlice, AtlasTag, SliceKey, SpriteAtlas, TagDirection,
+};
+#[doc(hidden)]
+pub use self::uniforms::UniformField;
 #[cfg(feature = "text")]
 pub use self::{
     font::{Font, Info as FontInfo},
     text::Text,
     text_style::TextStyle,
 };
+#[cfg(feature = "derive")]
+pub use sf2g_derive::Uniforms;
 pub use {
     self::{
+        animation::{AnimationClip, AnimationPlayer, FramePlacement, PlayMode, SpriteSheet},
+        camera::{Camera, Shake},
         circle_shape::CircleShape,
+        collision_mask::CollisionMask,
         color::Color,
+        color_parse::ParseColorError,
+        color_space::{Hsl, Hsv, Oklab},
         convex_shape::ConvexShape,
+        debug_draw::DebugDraw,
         glyph::Glyph,
+        gradient::{Gradient, GradientInterpolation, GradientKind},
+        particle_system::{
+            EmitterShape, LifetimeCurve, Particle, ParticleAffector, ParticleEmitter,
+            ParticleSystem,
+        },
+        path::{Contour, FillRule, LineCap, LineJoin, Path, StrokeStyle},
         primitive_type::PrimitiveType,
         rect::{FloatRect, IntRect, Rect},
         rectangle_shape::RectangleShape,
+        reloadable_shader::{ReloadStatus, ReloadableShader, Uniform},
         render_states::RenderStates,
         render_target::RenderTarget,
         render_texture::RenderTexture,
         render_window::RenderWindow,
+        scaling::{LowResCanvas, Scaling, ScalingPolicy},
+        scene_graph::{NodeId, SceneDrawable, SceneGraph, SceneNode},
         shader::Shader,
+        shader_source::{PreprocessedSource, ShaderSource, ShaderSourceError},
         shape::Shape,
+        split_screen::{SplitLayout, SplitScreen},
         sprite::Sprite,
         texture::Texture,
-        transform::Transform,
+        transform::{Decomposition, Transform},
         transformable::Transformable,
+        tween::{Ease, Tween, TweenProperty, TweenValue, Tweenable},
+        uniforms::{CurrentTexture, IntoUniform, UniformArrayElem, Uniforms},
         vertex::Vertex,
         vertex_buffer::{VertexBuffer, VertexBufferUsage},
         view::View,
@@ -34,31 +61,52 @@ pub use {
     crate::ffi::graphics::ShaderType,
 };
 
+mod animation;
 pub mod blend_mode;
+mod camera;
 mod circle_shape;
+mod collision_mask;
 mod color;
+mod color_parse;
+mod color_space;
 mod convex_shape;
+mod debug_draw;
 #[cfg(feature = "text")]
 mod font;
+pub mod geometry;
 pub mod glsl;
 mod glyph;
+mod gradient;
+mod particle_system;
+mod path;
 mod primitive_type;
 mod rect;
 mod rectangle_shape;
+mod reloadable_shader;
 mod render_states;
 mod render_target;
 mod render_texture;
 mod render_window;
+mod scaling;
+mod scene_graph;
 mod shader;
+mod shader_source;
 mod shape;
+mod split_screen;
 mod sprite;
+#[cfg(feature = "serde")]
+mod sprite_atlas;
 #[cfg(feature = "text")]
 mod text;
 #[cfg(feature = "text")]
 mod text_style;
 mod texture;
+#[cfg(feature = "tiled")]
+pub mod tiled;
 mod transform;
 mod transformable;
+mod tween;
+mod uniforms;
 mod vertex;
 mod vertex_buffer;
 mod view;
@@ -0,0 +1,572 @@
+use {
+    crate::{
+        SfResult,
+        cpp::FBox,
+        graphics::{
+            Color, FloatRect, IntRect, PrimitiveType, RenderStates, RenderTarget, Shape, Vertex,
+            VertexBuffer, VertexBufferUsage, geometry,
+        },
+        system::{Time, Vector2f},
+    },
+    std::{f32::consts::TAU, ops::RangeInclusive},
+};
+
+/// A single particle of a [`ParticleSystem`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    /// Position of the center of the particle
+    pub position: Vector2f,
+    /// Velocity, in units per second
+    pub velocity: Vector2f,
+    /// Rotation, in degrees
+    pub rotation: f32,
+    /// Angular velocity, in degrees per second
+    pub angular_velocity: f32,
+    /// Scale factor applied to the size of the particle
+    pub scale: f32,
+    /// Color the particle is drawn with
+    pub color: Color,
+    /// Index of the texture frame of the particle, in [`ParticleSystem::frames`]
+    pub frame: usize,
+    /// Time since the particle was emitted
+    pub age: Time,
+    /// Age at which the particle dies
+    pub lifetime: Time,
+}
+
+impl Particle {
+    /// How far the particle is in its life, from 0 when emitted to 1 when dying
+    #[must_use]
+    pub fn life(&self) -> f32 {
+        if self.lifetime <= Time::ZERO {
+            1.
+        } else {
+            (self.age.as_seconds() / self.lifetime.as_seconds()).clamp(0., 1.)
+        }
+    }
+}
+
+/// The area in which a [`ParticleEmitter`] spawns particles
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmitterShape {
+    /// A single point
+    Point(Vector2f),
+    /// Anywhere inside a circle
+    Circle {
+        /// Center of the circle
+        center: Vector2f,
+        /// Radius of the circle
+        radius: f32,
+    },
+    /// Anywhere inside a rectangle
+    Rect(FloatRect),
+    /// Anywhere on the outline of a closed polygon
+    Outline(Vec<Vector2f>),
+}
+
+impl EmitterShape {
+    /// The outline of `shape`, in world coordinates.
+    #[must_use]
+    pub fn outline_of<'t, S: Shape<'t> + ?Sized>(shape: &S) -> Self {
+        Self::Outline(geometry::world_points(shape))
+    }
+    fn sample(&self, rng: &mut Rng) -> Vector2f {
+        match *self {
+            Self::Point(point) => point,
+            Self::Circle { center, radius } => {
+                // The square root makes the distribution uniform over the disc
+                let (sin, cos) = (rng.next_f32() * TAU).sin_cos();
+                center + Vector2f::new(cos, sin) * (radius * rng.next_f32().sqrt())
+            }
+            Self::Rect(rect) => Vector2f::new(
+                rect.left + rect.width * rng.next_f32(),
+                rect.top + rect.height * rng.next_f32(),
+            ),
+            Self::Outline(ref points) => {
+                let edge = |i: usize| (points[i], points[(i + 1) % points.len()]);
+                let len = |(a, b): (Vector2f, Vector2f)| (b - a).length_sq().sqrt();
+                let perimeter: f32 = (0..points.len()).map(|i| len(edge(i))).sum();
+                let mut at = rng.next_f32() * perimeter;
+                for i in 0..points.len() {
+                    let (a, b) = edge(i);
+                    let l = len((a, b));
+                    if at <= l && l > 0. {
+                        return a + (b - a) * (at / l);
+                    }
+                    at -= l;
+                }
+                points.first().copied().unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Emits particles into a [`ParticleSystem`], continuously or in bursts.
+///
+/// Every property of the emitted particles is picked uniformly at random in its range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleEmitter {
+    /// Where particles are emitted
+    pub shape: EmitterShape,
+    /// Number of particles emitted per second by [`ParticleSystem::update`]
+    pub rate: f32,
+    /// Lifetime of the particles
+    pub lifetime: RangeInclusive<Time>,
+    /// Speed of the particles, in units per second
+    pub speed: RangeInclusive<f32>,
+    /// Direction of the particles, in degrees, clockwise from the X axis
+    pub direction: RangeInclusive<f32>,
+    /// Initial rotation of the particles, in degrees
+    pub rotation: RangeInclusive<f32>,
+    /// Angular velocity of the particles, in degrees per second
+    pub angular_velocity: RangeInclusive<f32>,
+    /// Initial scale of the particles
+    pub scale: RangeInclusive<f32>,
+    /// Initial color of the particles
+    pub color: Color,
+    /// Texture frames the particles pick from, as indices in [`ParticleSystem::frames`]
+    pub frames: RangeInclusive<usize>,
+    /// Fraction of a particle left over by the last updates
+    pending: f32,
+}
+
+impl ParticleEmitter {
+    /// Create an emitter emitting white, 1 second long, motionless particles from `shape`.
+    ///
+    /// The emitter doesn't emit continuously until [`rate`](Self::rate) is set.
+    #[must_use]
+    pub fn new(shape: EmitterShape) -> Self {
+        Self {
+            shape,
+            rate: 0.,
+            lifetime: Time::seconds(1.)..=Time::seconds(1.),
+            speed: 0.0..=0.,
+            direction: 0.0..=360.,
+            rotation: 0.0..=0.,
+            angular_velocity: 0.0..=0.,
+            scale: 1.0..=1.,
+            color: Color::WHITE,
+            frames: 0..=0,
+            pending: 0.,
+        }
+    }
+    fn spawn(&self, rng: &mut Rng) -> Particle {
+        let (sin, cos) = rng.range(&self.direction).to_radians().sin_cos();
+        let lifetime = rng.range_time(&self.lifetime);
+        let frames = &self.frames;
+        let frame_count = frames.end().saturating_sub(*frames.start()) + 1;
+        Particle {
+            position: self.shape.sample(rng),
+            velocity: Vector2f::new(cos, sin) * rng.range(&self.speed),
+            rotation: rng.range(&self.rotation),
+            angular_velocity: rng.range(&self.angular_velocity),
+            scale: rng.range(&self.scale),
+            color: self.color,
+            frame: frames.start() + rng.below(frame_count),
+            age: Time::ZERO,
+            lifetime,
+        }
+    }
+}
+
+/// A value that changes over the life of a particle, for [`ParticleAffector`]s.
+///
+/// The curve is made of `(life, value)` keys, where `life` goes from 0 when the particle is
+/// emitted to 1 when it dies. Values are interpolated linearly between keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifetimeCurve<T> {
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: Copy> LifetimeCurve<T> {
+    /// Create a curve from `(life, value)` keys, which don't need to be sorted.
+    #[must_use]
+    pub fn new(keys: &[(f32, T)]) -> Self {
+        let mut keys = keys.to_vec();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { keys }
+    }
+    /// The keys of the curve, sorted by life
+    #[must_use]
+    pub fn keys(&self) -> &[(f32, T)] {
+        &self.keys
+    }
+    fn sample_with(&self, life: f32, lerp: impl Fn(T, T, f32) -> T) -> Option<T> {
+        let &(first_life, first) = self.keys.first()?;
+        if life <= first_life {
+            return Some(first);
+        }
+        for pair in self.keys.windows(2) {
+            let [(l0, v0), (l1, v1)] = [pair[0], pair[1]];
+            if life <= l1 {
+                let t = if l1 > l0 { (life - l0) / (l1 - l0) } else { 1. };
+                return Some(lerp(v0, v1, t));
+            }
+        }
+        self.keys.last().map(|key| key.1)
+    }
+}
+
+impl LifetimeCurve<f32> {
+    /// The value of the curve at `life`, or `None` if it has no keys.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::LifetimeCurve;
+    /// let curve = LifetimeCurve::new(&[(0., 1.), (1., 3.)]);
+    /// assert_eq!(curve.sample(0.5), Some(2.));
+    /// ```
+    #[must_use]
+    pub fn sample(&self, life: f32) -> Option<f32> {
+        self.sample_with(life, |a, b, t| a + (b - a) * t)
+    }
+}
+
+impl LifetimeCurve<Color> {
+    /// The color of the curve at `life`, or `None` if it has no keys.
+    #[must_use]
+    pub fn sample(&self, life: f32) -> Option<Color> {
+        self.sample_with(life, Color::lerp)
+    }
+}
+
+/// Changes the particles of a [`ParticleSystem`] at every update
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParticleAffector {
+    /// Accelerate all particles, in units per second squared
+    Gravity(Vector2f),
+    /// Slow particles down: their velocity decays exponentially at this rate, so
+    /// `e^-drag` of it is kept every second. For example, `Drag(0.5)` keeps about 61%.
+    Drag(f32),
+    /// Set the color of particles over their life
+    ColorOverLifetime(LifetimeCurve<Color>),
+    /// Set the scale of particles over their life
+    ScaleOverLifetime(LifetimeCurve<f32>),
+    /// Rotate particles, in degrees per second, on top of their angular velocity
+    Rotation(f32),
+    /// Rotate particles to face the direction they move in
+    AlignToVelocity,
+    /// Go through the frames in a range over the life of particles
+    FramesOverLifetime(RangeInclusive<usize>),
+    /// Pull particles towards `position`. A negative `strength` pushes them away.
+    Attractor {
+        /// Point particles are attracted to
+        position: Vector2f,
+        /// Acceleration of attracted particles, in units per second squared
+        strength: f32,
+        /// Particles further than this distance are not affected
+        radius: f32,
+    },
+}
+
+impl ParticleAffector {
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss,
+        reason = "Frame indices are small and positive"
+    )]
+    fn apply(&self, particle: &mut Particle, dt: f32) {
+        match *self {
+            Self::Gravity(acceleration) => particle.velocity += acceleration * dt,
+            Self::Drag(drag) => particle.velocity *= (-drag * dt).exp(),
+            Self::ColorOverLifetime(ref curve) => {
+                if let Some(color) = curve.sample(particle.life()) {
+                    particle.color = color;
+                }
+            }
+            Self::ScaleOverLifetime(ref curve) => {
+                if let Some(scale) = curve.sample(particle.life()) {
+                    particle.scale = scale;
+                }
+            }
+            Self::Rotation(speed) => particle.rotation += speed * dt,
+            Self::AlignToVelocity => {
+                if particle.velocity != Vector2f::default() {
+                    particle.rotation = particle.velocity.y.atan2(particle.velocity.x).to_degrees();
+                }
+            }
+            Self::FramesOverLifetime(ref frames) => {
+                let count = frames.end().saturating_sub(*frames.start()) + 1;
+                let index = (particle.life() * count as f32) as usize;
+                particle.frame = frames.start() + index.min(count - 1);
+            }
+            Self::Attractor {
+                position,
+                strength,
+                radius,
+            } => {
+                let offset = position - particle.position;
+                let dist_sq = offset.length_sq();
+                if dist_sq > 0. && dist_sq <= radius * radius {
+                    particle.velocity += offset * (strength * dt / dist_sq.sqrt());
+                }
+            }
+        }
+    }
+}
+
+/// A particle system, simulated on the CPU and drawn in a single draw call.
+///
+/// Each update:
+/// 1. ages the particles and removes the dead ones,
+/// 2. applies the [`affectors`](Self::affectors), in order,
+/// 3. moves and rotates the particles,
+/// 4. emits new particles from the [`emitters`](Self::emitters).
+///
+/// Particles are drawn as quads of [`size`](Self::size) scaled by their scale, or of the size of
+/// their texture frame if [`frames`](Self::frames) isn't empty. Draw them with
+/// [`draw`](Self::draw), or [`upload`](Self::upload) them to a vertex buffer created with
+/// [`create_vertex_buffer`](Self::create_vertex_buffer) and draw that.
+///
+/// Random values come from a generator seeded with [`seed`](Self::seed), so a system seeded and
+/// updated the same way always produces the same particles.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let texture: Texture = unimplemented!();
+/// # let mut clock = Clock::start().unwrap();
+/// let mut sparks = ParticleSystem::new(1000);
+/// sparks.frames = vec![IntRect::new(0, 0, 8, 8), IntRect::new(8, 0, 8, 8)];
+/// let mut emitter = ParticleEmitter::new(EmitterShape::Point(Vector2f::new(400., 300.)));
+/// emitter.rate = 200.;
+/// emitter.speed = 50.0..=150.;
+/// emitter.frames = 0..=1;
+/// sparks.emitters.push(emitter);
+/// sparks.affectors.push(ParticleAffector::Gravity(Vector2f::new(0., 200.)));
+/// sparks.affectors.push(ParticleAffector::ColorOverLifetime(LifetimeCurve::new(&[
+///     (0., Color::YELLOW),
+///     (1., Color::rgba(255, 0, 0, 0)),
+/// ])));
+/// let mut buffer = sparks.create_vertex_buffer().unwrap();
+/// loop {
+///     sparks.update(clock.restart());
+///     sparks.upload(&mut buffer).unwrap();
+///     let states = RenderStates {
+///         texture: Some(&texture),
+///         ..Default::default()
+///     };
+///     window.draw_vertex_buffer(&buffer, &states);
+///     # break;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParticleSystem {
+    /// Emitters adding particles at each update
+    pub emitters: Vec<ParticleEmitter>,
+    /// Affectors applied to the particles at each update, in order
+    pub affectors: Vec<ParticleAffector>,
+    /// Texture rects of the frames particles can use
+    pub frames: Vec<IntRect>,
+    /// Size of the particles if there are no frames
+    pub size: Vector2f,
+    max_particles: usize,
+    particles: Vec<Particle>,
+    vertices: Vec<Vertex>,
+    rng: Rng,
+}
+
+/// Creation and settings
+impl ParticleSystem {
+    /// Create an empty system, which can hold up to `max_particles` particles.
+    ///
+    /// The random generator is seeded with 0.
+    #[must_use]
+    pub fn new(max_particles: usize) -> Self {
+        Self {
+            emitters: Vec::new(),
+            affectors: Vec::new(),
+            frames: Vec::new(),
+            size: Vector2f::new(4., 4.),
+            max_particles,
+            particles: Vec::with_capacity(max_particles),
+            vertices: Vec::new(),
+            rng: Rng(0),
+        }
+    }
+    /// Reset the random generator with `seed`.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Rng(seed);
+    }
+    /// Maximum number of live particles. Emitting more particles than that does nothing.
+    #[must_use]
+    pub fn max_particles(&self) -> usize {
+        self.max_particles
+    }
+    /// Set the maximum number of live particles, removing the oldest extra particles.
+    ///
+    /// Vertex buffers created before must be recreated with
+    /// [`create_vertex_buffer`](Self::create_vertex_buffer) if the maximum grows.
+    pub fn set_max_particles(&mut self, max_particles: usize) {
+        self.max_particles = max_particles;
+        if self.particles.len() > max_particles {
+            let extra = self.particles.len() - max_particles;
+            drop(self.particles.drain(..extra));
+        }
+        self.update_vertices();
+    }
+}
+
+/// Simulation
+impl ParticleSystem {
+    /// The live particles, from oldest to newest
+    #[must_use]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+    /// The live particles, to modify them directly.
+    ///
+    /// Changes are visible in the vertices after the next update.
+    pub fn particles_mut(&mut self) -> &mut [Particle] {
+        &mut self.particles
+    }
+    /// Remove all the particles.
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.vertices.clear();
+    }
+    /// Emit `count` particles from `emitter` at once, for explosions and other bursts.
+    pub fn burst(&mut self, emitter: &ParticleEmitter, count: usize) {
+        let count = count.min(self.max_particles.saturating_sub(self.particles.len()));
+        for _ in 0..count {
+            self.particles.push(emitter.spawn(&mut self.rng));
+        }
+        self.update_vertices();
+    }
+    /// Advance the simulation by `dt`, and emit new particles from the emitters.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "The count is positive and small"
+    )]
+    pub fn update(&mut self, dt: Time) {
+        let seconds = dt.as_seconds();
+        self.particles.retain_mut(|p| {
+            p.age += dt;
+            p.age < p.lifetime
+        });
+        for particle in &mut self.particles {
+            for affector in &self.affectors {
+                affector.apply(particle, seconds);
+            }
+            particle.position += particle.velocity * seconds;
+            particle.rotation += particle.angular_velocity * seconds;
+        }
+        for emitter in &mut self.emitters {
+            emitter.pending += emitter.rate * seconds;
+            let count = emitter.pending.floor();
+            emitter.pending -= count;
+            for _ in 0..count as usize {
+                if self.particles.len() >= self.max_particles {
+                    break;
+                }
+                self.particles.push(emitter.spawn(&mut self.rng));
+            }
+        }
+        self.update_vertices();
+    }
+}
+
+/// Drawing
+impl ParticleSystem {
+    /// The quads of the particles, as of the last update, for [`PrimitiveType::TRIANGLES`].
+    #[must_use]
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+    /// Draw the particles with [`RenderTarget::draw_primitives`].
+    pub fn draw<RT: RenderTarget>(&self, target: &mut RT, states: &RenderStates) {
+        target.draw_primitives(&self.vertices, PrimitiveType::TRIANGLES, states);
+    }
+    /// Create a vertex buffer with [`VertexBufferUsage::STREAM`] usage, large enough for
+    /// [`max_particles`](Self::max_particles) particles.
+    pub fn create_vertex_buffer(&self) -> SfResult<FBox<VertexBuffer>> {
+        VertexBuffer::new(
+            PrimitiveType::TRIANGLES,
+            self.max_particles * 6,
+            VertexBufferUsage::STREAM,
+        )
+    }
+    /// Copy the quads of the particles to `buffer`.
+    ///
+    /// Vertex buffers always draw all their vertices, so the rest of the buffer is filled with
+    /// empty triangles. The buffer is grown if it is too small.
+    pub fn upload(&self, buffer: &mut VertexBuffer) -> SfResult<()> {
+        let unused = buffer.vertex_count().saturating_sub(self.vertices.len());
+        if unused == 0 {
+            return buffer.update(&self.vertices, 0);
+        }
+        let mut vertices = Vec::with_capacity(self.vertices.len() + unused);
+        vertices.extend_from_slice(&self.vertices);
+        vertices.resize(self.vertices.len() + unused, Vertex::DEFAULT);
+        buffer.update(&vertices, 0)
+    }
+    fn update_vertices(&mut self) {
+        self.vertices.clear();
+        for p in &self.particles {
+            let (size, tex) = match self.frames.get(p.frame) {
+                Some(frame) => {
+                    let frame = frame.as_other::<f32>();
+                    (
+                        Vector2f::new(frame.width.abs(), frame.height.abs()),
+                        [
+                            frame.position(),
+                            Vector2f::new(frame.left + frame.width, frame.top),
+                            frame.position() + frame.size(),
+                            Vector2f::new(frame.left, frame.top + frame.height),
+                        ],
+                    )
+                }
+                None => (self.size, [Vector2f::default(); 4]),
+            };
+            let (sin, cos) = p.rotation.to_radians().sin_cos();
+            let half = size * (p.scale / 2.);
+            let (x, y) = (
+                Vector2f::new(cos, sin) * half.x,
+                Vector2f::new(-sin, cos) * half.y,
+            );
+            let corners = [
+                p.position - x - y,
+                p.position + x - y,
+                p.position + x + y,
+                p.position - x + y,
+            ];
+            let vertex = |i: usize| Vertex::new(corners[i], p.color, tex[i]);
+            self.vertices.extend([0, 1, 2, 0, 2, 3].map(vertex));
+        }
+    }
+}
+
+/// `SplitMix64`, a small and fast generator, good enough for visual effects
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    /// Uniform in `[0, 1)`
+    #[expect(clippy::cast_precision_loss, reason = "24 bits fit in the mantissa")]
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1_u32 << 24) as f32
+    }
+    fn range(&mut self, range: &RangeInclusive<f32>) -> f32 {
+        range.start() + (range.end() - range.start()) * self.next_f32()
+    }
+    fn range_time(&mut self, range: &RangeInclusive<Time>) -> Time {
+        *range.start() + (*range.end() - *range.start()) * self.next_f32()
+    }
+    /// Uniform in `[0, n)`
+    #[expect(clippy::cast_possible_truncation, reason = "The result is below n")]
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
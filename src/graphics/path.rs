@@ -0,0 +1,965 @@
+use {
+    crate::{
+        graphics::{Color, FloatRect, Vertex},
+        system::Vector2f,
+    },
+    std::f32::consts::{PI, TAU},
+};
+
+/// How the inside of a self-intersecting or overlapping [`Path`] is determined when filling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FillRule {
+    /// A point is inside if the contours wind around it a non-zero number of times,
+    /// counting the direction of the contours. Overlapping contours with the same direction
+    /// are all filled.
+    #[default]
+    NonZero,
+    /// A point is inside if a ray from it crosses the contours an odd number of times.
+    /// Overlapping contours make holes.
+    EvenOdd,
+}
+
+/// How strokes are joined at the corners of a [`Path`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet, or bevel if that would exceed the miter limit
+    #[default]
+    Miter,
+    /// Round the corner with a circular arc
+    Round,
+    /// Cut the corner with a straight line
+    Bevel,
+}
+
+/// How strokes end at the ends of open contours of a [`Path`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LineCap {
+    /// Stop exactly at the end point
+    #[default]
+    Butt,
+    /// Add a half circle around the end point
+    Round,
+    /// Extend past the end point by half the width
+    Square,
+}
+
+/// How the outline of a [`Path`] is drawn by [`Path::stroke`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    /// Width of the stroke
+    pub width: f32,
+    /// How corners are joined
+    pub join: LineJoin,
+    /// How open contours end
+    pub cap: LineCap,
+    /// Maximum ratio between the length of a miter and the width, beyond which
+    /// [`LineJoin::Miter`] joins are beveled. The default is 4, like in SVG.
+    pub miter_limit: f32,
+    /// Alternating lengths of dashes and gaps. Empty for a solid stroke.
+    ///
+    /// A pattern with an odd number of lengths is repeated twice, like in SVG.
+    pub dashes: Vec<f32>,
+    /// Distance into the dash pattern at which the stroke starts
+    pub dash_offset: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self::new(1.)
+    }
+}
+
+impl StrokeStyle {
+    /// A solid stroke of width `width`, with miter joins and butt caps.
+    #[must_use]
+    pub const fn new(width: f32) -> Self {
+        Self {
+            width,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.,
+            dashes: Vec::new(),
+            dash_offset: 0.,
+        }
+    }
+}
+
+/// A contour of a flattened [`Path`]: a polyline, possibly closed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Contour {
+    /// The points of the contour
+    pub points: Vec<Vector2f>,
+    /// Whether the last point connects back to the first one
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    MoveTo(Vector2f),
+    LineTo(Vector2f),
+    QuadTo(Vector2f, Vector2f),
+    CubicTo(Vector2f, Vector2f, Vector2f),
+    Arc {
+        center: Vector2f,
+        radius: f32,
+        start: f32,
+        sweep: f32,
+    },
+    Close,
+}
+
+/// A vector path made of lines, curves and arcs, that can be filled and stroked.
+///
+/// Filling and stroking produce triangles of [`Vertex`], to be drawn with
+/// [`PrimitiveType::TRIANGLES`], or stored in a [`VertexBuffer`]. Curves are flattened into
+/// line segments first, with a maximum error of [`tolerance`](Path::tolerance).
+///
+/// Angles are in degrees, clockwise from the X axis, like [`Transformable`] rotations.
+///
+/// The triangles of a stroke overlap at corners and caps, so translucent strokes should be
+/// drawn to a [`RenderTexture`] first, then drawn translucent, to avoid darker overlaps.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::Vector2f};
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut path = Path::new();
+/// path.move_to(Vector2f::new(10., 10.))
+///     .line_to(Vector2f::new(100., 10.))
+///     .quad_to(Vector2f::new(150., 50.), Vector2f::new(100., 90.))
+///     .close();
+/// let mut style = StrokeStyle::new(6.);
+/// style.join = LineJoin::Round;
+/// let fill = path.fill(FillRule::NonZero, Color::rgb(60, 60, 120));
+/// let stroke = path.stroke(&style, Color::WHITE);
+/// window.draw_primitives(&fill, PrimitiveType::TRIANGLES, &RenderStates::DEFAULT);
+/// window.draw_primitives(&stroke, PrimitiveType::TRIANGLES, &RenderStates::DEFAULT);
+/// ```
+///
+/// [`PrimitiveType::TRIANGLES`]: crate::graphics::PrimitiveType::TRIANGLES
+/// [`VertexBuffer`]: crate::graphics::VertexBuffer
+/// [`Transformable`]: crate::graphics::Transformable
+/// [`RenderTexture`]: crate::graphics::RenderTexture
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    commands: Vec<Command>,
+    tolerance: f32,
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Building
+impl Path {
+    /// Create an empty path, with a tolerance of 0.25.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            tolerance: 0.25,
+        }
+    }
+    /// A path going through `points`, closed if `closed` is true.
+    #[must_use]
+    pub fn polyline(points: &[Vector2f], closed: bool) -> Self {
+        let mut path = Self::new();
+        let mut points = points.iter();
+        if let Some(&first) = points.next() {
+            let _ = path.move_to(first);
+            for &p in points {
+                let _ = path.line_to(p);
+            }
+            if closed {
+                let _ = path.close();
+            }
+        }
+        path
+    }
+    /// A closed path around `rect`.
+    #[must_use]
+    pub fn rect(rect: FloatRect) -> Self {
+        let (min, max) = (rect.min_corner(), rect.max_corner());
+        Self::polyline(
+            &[
+                min,
+                Vector2f::new(max.x, min.y),
+                max,
+                Vector2f::new(min.x, max.y),
+            ],
+            true,
+        )
+    }
+    /// A closed circle.
+    #[must_use]
+    pub fn circle(center: Vector2f, radius: f32) -> Self {
+        let mut path = Self::new();
+        let _ = path.arc(center, radius, 0., 360.).close();
+        path
+    }
+    /// The maximum distance between curves and the line segments they are flattened into
+    #[must_use]
+    pub const fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+    /// Set the maximum distance between curves and the line segments they are flattened into.
+    ///
+    /// Smaller values give smoother curves, and more vertices.
+    pub fn set_tolerance(&mut self, tolerance: f32) {
+        self.tolerance = tolerance;
+    }
+    /// Whether the path has no commands
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+    /// Remove all commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+    /// Start a new contour at `point`.
+    pub fn move_to(&mut self, point: Vector2f) -> &mut Self {
+        self.commands.push(Command::MoveTo(point));
+        self
+    }
+    /// Add a straight line to `point`.
+    pub fn line_to(&mut self, point: Vector2f) -> &mut Self {
+        self.commands.push(Command::LineTo(point));
+        self
+    }
+    /// Add a quadratic Bézier curve with control point `control`, ending at `point`.
+    pub fn quad_to(&mut self, control: Vector2f, point: Vector2f) -> &mut Self {
+        self.commands.push(Command::QuadTo(control, point));
+        self
+    }
+    /// Add a cubic Bézier curve with control points `control1` and `control2`,
+    /// ending at `point`.
+    pub fn cubic_to(
+        &mut self,
+        control1: Vector2f,
+        control2: Vector2f,
+        point: Vector2f,
+    ) -> &mut Self {
+        self.commands
+            .push(Command::CubicTo(control1, control2, point));
+        self
+    }
+    /// Add a circular arc around `center`, from the angle `start` and spanning `sweep`
+    /// degrees. Negative sweeps go counter-clockwise.
+    ///
+    /// Like in the HTML canvas, a straight line connects the current point to the start
+    /// of the arc.
+    pub fn arc(&mut self, center: Vector2f, radius: f32, start: f32, sweep: f32) -> &mut Self {
+        self.commands.push(Command::Arc {
+            center,
+            radius,
+            start,
+            sweep,
+        });
+        self
+    }
+    /// Close the current contour with a straight line to its first point.
+    ///
+    /// The next contour starts at that point, unless it starts with [`move_to`](Path::move_to).
+    pub fn close(&mut self) -> &mut Self {
+        self.commands.push(Command::Close);
+        self
+    }
+}
+
+/// Tessellation
+impl Path {
+    /// Flatten the curves of the path into line segments.
+    ///
+    /// Consecutive duplicate points are removed, and contours with a single point are kept,
+    /// so stroking can draw them as dots.
+    #[must_use]
+    pub fn contours(&self) -> Vec<Contour> {
+        let mut contours = Vec::new();
+        let mut current = Contour::default();
+        // Where the next contour starts after a `close`
+        let mut reopen_at = None;
+        let tol = self.tolerance.max(1e-3);
+        for &command in &self.commands {
+            match command {
+                Command::MoveTo(p) => {
+                    flush(&mut contours, &mut current);
+                    current.points.push(p);
+                }
+                Command::LineTo(p) => {
+                    let _ = begin(&mut current, reopen_at, p);
+                    push_point(&mut current.points, p);
+                }
+                Command::QuadTo(c, p) => {
+                    let p0 = begin(&mut current, reopen_at, c);
+                    let n = wang(&[p0, c, p], 2, tol);
+                    for t in steps(n) {
+                        let mt = 1. - t;
+                        let point = p0 * (mt * mt) + c * (2. * mt * t) + p * (t * t);
+                        push_point(&mut current.points, point);
+                    }
+                }
+                Command::CubicTo(c1, c2, p) => {
+                    let p0 = begin(&mut current, reopen_at, c1);
+                    let n = wang(&[p0, c1, c2, p], 3, tol);
+                    for t in steps(n) {
+                        let mt = 1. - t;
+                        let point = p0 * (mt * mt * mt)
+                            + c1 * (3. * mt * mt * t)
+                            + c2 * (3. * mt * t * t)
+                            + p * (t * t * t);
+                        push_point(&mut current.points, point);
+                    }
+                }
+                Command::Arc {
+                    center,
+                    radius,
+                    start,
+                    sweep,
+                } => {
+                    let points =
+                        arc_points(center, radius, start.to_radians(), sweep.to_radians(), tol);
+                    for point in points {
+                        let _ = begin(&mut current, reopen_at, point);
+                        push_point(&mut current.points, point);
+                    }
+                }
+                Command::Close => {
+                    if let Some(&first) = current.points.first() {
+                        if current.points.len() > 1 && current.points.last() == Some(&first) {
+                            let _ = current.points.pop();
+                        }
+                        current.closed = true;
+                        reopen_at = Some(first);
+                        flush(&mut contours, &mut current);
+                    }
+                }
+            }
+        }
+        flush(&mut contours, &mut current);
+        contours
+    }
+
+    /// Tessellate the inside of the path into triangles of color `color`.
+    ///
+    /// All contours are treated as closed. Concave and self-intersecting contours, and
+    /// holes, are supported, according to `rule`.
+    #[must_use]
+    pub fn fill(&self, rule: FillRule, color: Color) -> Vec<Vertex> {
+        fill_contours(&self.contours(), rule, color)
+    }
+
+    /// Tessellate the outline of the path into triangles of color `color`.
+    #[must_use]
+    pub fn stroke(&self, style: &StrokeStyle, color: Color) -> Vec<Vertex> {
+        let mut out = Vec::new();
+        let half = style.width / 2.;
+        if half <= 0. {
+            return out;
+        }
+        let stroker = Stroker {
+            style,
+            half,
+            tolerance: self.tolerance.max(1e-3),
+            color,
+        };
+        for contour in self.contours() {
+            match dash_contour(&contour, style) {
+                Some(dashes) => {
+                    for dash in dashes {
+                        stroker.polyline(&dash, false, &mut out);
+                    }
+                }
+                None => stroker.polyline(&contour.points, contour.closed, &mut out),
+            }
+        }
+        out
+    }
+}
+
+/// Start a contour if there is none, and return the current point
+fn begin(current: &mut Contour, reopen_at: Option<Vector2f>, first: Vector2f) -> Vector2f {
+    if current.points.is_empty() {
+        current.points.push(reopen_at.unwrap_or(first));
+    }
+    current.points[current.points.len() - 1]
+}
+
+fn flush(contours: &mut Vec<Contour>, current: &mut Contour) {
+    if !current.points.is_empty() {
+        contours.push(std::mem::take(current));
+    }
+}
+
+/// The parameters `1/n, 2/n, ..., 1`
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "Curves are split in few segments"
+)]
+fn steps(n: usize) -> impl Iterator<Item = f32> {
+    (1..=n).map(move |i| i as f32 / n as f32)
+}
+
+fn push_point(points: &mut Vec<Vector2f>, p: Vector2f) {
+    if points.last() != Some(&p) {
+        points.push(p);
+    }
+}
+
+fn length(v: Vector2f) -> f32 {
+    v.length_sq().sqrt()
+}
+
+fn normalize(v: Vector2f) -> Vector2f {
+    let len = length(v);
+    if len == 0. { v } else { v / len }
+}
+
+/// Number of segments needed to flatten a Bézier curve of degree `degree` within
+/// `tolerance`, using Wang's formula
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss,
+    reason = "Clamped to a small positive range"
+)]
+fn wang(points: &[Vector2f], degree: usize, tolerance: f32) -> usize {
+    let max_dd = points
+        .windows(3)
+        .map(|w| length(w[0] - w[1] * 2. + w[2]))
+        .fold(0., f32::max);
+    let factor = (degree * (degree - 1)) as f32 / 8.;
+    ((factor * max_dd / tolerance).sqrt().ceil() as usize).clamp(1, 256)
+}
+
+/// Angle step to flatten an arc of radius `radius` within `tolerance`
+fn arc_step(radius: f32, tolerance: f32) -> f32 {
+    if radius <= tolerance {
+        PI / 2.
+    } else {
+        (2. * (1. - tolerance / radius).acos()).clamp(0.01, PI / 2.)
+    }
+}
+
+/// Points of an arc, including both ends. Angles are in radians.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss,
+    reason = "Clamped to a small positive range"
+)]
+fn arc_points(
+    center: Vector2f,
+    radius: f32,
+    start: f32,
+    sweep: f32,
+    tolerance: f32,
+) -> impl Iterator<Item = Vector2f> {
+    let sweep = sweep.clamp(-TAU, TAU);
+    let n = ((sweep.abs() / arc_step(radius, tolerance)).ceil() as usize).clamp(1, 1024);
+    (0..=n).map(move |i| {
+        let angle = start + sweep * i as f32 / n as f32;
+        let (sin, cos) = angle.sin_cos();
+        center + Vector2f::new(cos, sin) * radius
+    })
+}
+
+/// Split a contour into the "on" parts of the dash pattern, or `None` for a solid stroke
+fn dash_contour(contour: &Contour, style: &StrokeStyle) -> Option<Vec<Vec<Vector2f>>> {
+    if style.dashes.is_empty() || style.dashes.iter().any(|&d| d < 0. || !d.is_finite()) {
+        return None;
+    }
+    let mut pattern = style.dashes.clone();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let total: f32 = pattern.iter().sum();
+    if total <= 0. {
+        return None;
+    }
+    // Find where the offset falls in the pattern
+    let mut index = 0;
+    let mut remaining = style.dash_offset.rem_euclid(total);
+    // Tiny negative offsets round up to `total`, which is the same phase as zero
+    if remaining >= total {
+        remaining = 0.;
+    }
+    while remaining >= pattern[index] {
+        remaining -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    remaining = pattern[index] - remaining;
+
+    let mut points = contour.points.clone();
+    if contour.closed && points.len() > 1 {
+        points.push(points[0]);
+    }
+    let mut dashes = Vec::new();
+    let mut current: Vec<Vector2f> = Vec::new();
+    if index % 2 == 0 {
+        current.extend(points.first());
+    }
+    for seg in points.windows(2) {
+        let (mut a, b) = (seg[0], seg[1]);
+        let mut seg_len = length(b - a);
+        while seg_len > remaining {
+            let p = a + (b - a) * (remaining / seg_len);
+            if index % 2 == 0 {
+                push_point(&mut current, p);
+                dashes.push(std::mem::take(&mut current));
+            } else {
+                current.push(p);
+            }
+            seg_len -= remaining;
+            a = p;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= seg_len;
+        if index % 2 == 0 {
+            push_point(&mut current, b);
+        }
+    }
+    if index % 2 == 0 && !current.is_empty() {
+        dashes.push(current);
+    }
+    Some(dashes)
+}
+
+struct Stroker<'a> {
+    style: &'a StrokeStyle,
+    half: f32,
+    tolerance: f32,
+    color: Color,
+}
+
+impl Stroker<'_> {
+    fn tri(&self, out: &mut Vec<Vertex>, a: Vector2f, b: Vector2f, c: Vector2f) {
+        out.extend([a, b, c].map(|p| Vertex::with_pos_color(p, self.color)));
+    }
+    fn quad(&self, out: &mut Vec<Vertex>, a: Vector2f, b: Vector2f, c: Vector2f, d: Vector2f) {
+        self.tri(out, a, b, c);
+        self.tri(out, a, c, d);
+    }
+    /// Triangle fan around `center`, along an arc of radius `half`
+    fn fan(&self, out: &mut Vec<Vertex>, center: Vector2f, start: f32, sweep: f32) {
+        let mut points = arc_points(center, self.half, start, sweep, self.tolerance);
+        if let Some(mut prev) = points.next() {
+            for p in points {
+                self.tri(out, center, prev, p);
+                prev = p;
+            }
+        }
+    }
+    fn polyline(&self, points: &[Vector2f], closed: bool, out: &mut Vec<Vertex>) {
+        let h = self.half;
+        if points.len() == 1 {
+            // A dot, drawn by the caps
+            let p = points[0];
+            match self.style.cap {
+                LineCap::Butt => {}
+                LineCap::Round => self.fan(out, p, 0., TAU),
+                LineCap::Square => {
+                    let (x, y) = (Vector2f::new(h, 0.), Vector2f::new(0., h));
+                    self.quad(out, p - x - y, p + x - y, p + x + y, p - x + y);
+                }
+            }
+            return;
+        }
+        let closed = closed && points.len() > 2;
+        let count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let dir = |i: usize| normalize(points[(i + 1) % points.len()] - points[i]);
+        for i in 0..count {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            let n = dir(i).perpendicular() * h;
+            self.quad(out, a + n, b + n, b - n, a - n);
+        }
+        let joins = if closed {
+            0..points.len()
+        } else {
+            1..points.len() - 1
+        };
+        for i in joins {
+            let prev = (i + points.len() - 1) % points.len();
+            self.join(out, points[i], dir(prev), dir(i));
+        }
+        if !closed {
+            let last = points.len() - 1;
+            self.cap(out, points[0], -dir(0));
+            self.cap(out, points[last], dir(last - 1));
+        }
+    }
+    fn join(&self, out: &mut Vec<Vertex>, p: Vector2f, d0: Vector2f, d1: Vector2f) {
+        let cross = d0.cross(d1);
+        if cross.abs() < 1e-6 && d0.dot(d1) > 0. {
+            return;
+        }
+        // The outer side of the corner is opposite to the direction of the turn
+        let side = if cross > 0. { -1. } else { 1. };
+        let n0 = d0.perpendicular() * side;
+        let n1 = d1.perpendicular() * side;
+        let (o0, o1) = (p + n0 * self.half, p + n1 * self.half);
+        match self.style.join {
+            LineJoin::Bevel => self.tri(out, p, o0, o1),
+            LineJoin::Round => {
+                let start = n0.y.atan2(n0.x);
+                let sweep = n0.cross(n1).atan2(n0.dot(n1));
+                self.fan(out, p, start, sweep);
+            }
+            LineJoin::Miter => {
+                let m = normalize(n0 + n1);
+                let cos = m.dot(n0);
+                if cos > 0. && 1. / cos <= self.style.miter_limit {
+                    let tip = p + m * (self.half / cos);
+                    self.quad(out, p, o0, tip, o1);
+                } else {
+                    self.tri(out, p, o0, o1);
+                }
+            }
+        }
+    }
+    /// Cap at `p`, for a stroke going out in direction `d`
+    fn cap(&self, out: &mut Vec<Vertex>, p: Vector2f, d: Vector2f) {
+        let n = d.perpendicular() * self.half;
+        match self.style.cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                let e = d * self.half;
+                self.quad(out, p + n, p + n + e, p - n + e, p - n);
+            }
+            LineCap::Round => {
+                let start = n.y.atan2(n.x);
+                self.fan(out, p, start, -PI);
+            }
+        }
+    }
+}
+
+/// An edge of a filled polygon, going down, with its original direction
+#[derive(Clone, Copy)]
+struct Edge {
+    top: Vector2f,
+    bottom: Vector2f,
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        let dy = self.bottom.y - self.top.y;
+        self.top.x + (self.bottom.x - self.top.x) * (y - self.top.y) / dy
+    }
+}
+
+/// Fill contours by splitting them into horizontal slabs at every vertex and every
+/// intersection. In each slab, edges don't cross, so the inside is made of trapezoids.
+fn fill_contours(contours: &[Contour], rule: FillRule, color: Color) -> Vec<Vertex> {
+    let mut edges = Vec::new();
+    for contour in contours {
+        let points = &contour.points;
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            if a.y == b.y {
+                continue;
+            }
+            edges.push(if a.y < b.y {
+                Edge {
+                    top: a,
+                    bottom: b,
+                    winding: 1,
+                }
+            } else {
+                Edge {
+                    top: b,
+                    bottom: a,
+                    winding: -1,
+                }
+            });
+        }
+    }
+    let mut ys: Vec<f32> = edges.iter().flat_map(|e| [e.top.y, e.bottom.y]).collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if let Some(y) = intersection_y(a, b) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(f32::total_cmp);
+    ys.dedup();
+    edges.sort_by(|a, b| a.top.y.total_cmp(&b.top.y));
+
+    let mut out = Vec::new();
+    let mut active: Vec<Edge> = Vec::new();
+    let mut next = 0;
+    let mut crossing: Vec<(f32, f32, f32, i32)> = Vec::new();
+    for slab in ys.windows(2) {
+        let (y0, y1) = (slab[0], slab[1]);
+        if y1 - y0 <= 1e-5 * y0.abs().max(1.) {
+            continue;
+        }
+        let mid = (y0 + y1) / 2.;
+        active.retain(|e| e.bottom.y > mid);
+        while next < edges.len() && edges[next].top.y < mid {
+            if edges[next].bottom.y > mid {
+                active.push(edges[next]);
+            }
+            next += 1;
+        }
+        crossing.clear();
+        crossing.extend(
+            active
+                .iter()
+                .map(|e| (e.x_at(mid), e.x_at(y0), e.x_at(y1), e.winding)),
+        );
+        crossing.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut winding = 0;
+        let mut left = None;
+        for &(_, x0, x1, w) in &crossing {
+            let was_inside = inside(winding, rule);
+            winding += w;
+            match (was_inside, inside(winding, rule)) {
+                (false, true) => left = Some((x0, x1)),
+                (true, false) => {
+                    if let Some((lx0, lx1)) = left.take() {
+                        let quad = [
+                            Vector2f::new(lx0, y0),
+                            Vector2f::new(x0, y0),
+                            Vector2f::new(x1, y1),
+                            Vector2f::new(lx1, y1),
+                        ]
+                        .map(|p| Vertex::with_pos_color(p, color));
+                        out.extend([quad[0], quad[1], quad[2], quad[0], quad[2], quad[3]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+const fn inside(winding: i32, rule: FillRule) -> bool {
+    match rule {
+        FillRule::NonZero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// The y coordinate where two edges cross, strictly inside both
+fn intersection_y(a: &Edge, b: &Edge) -> Option<f32> {
+    let (da, db) = (a.bottom - a.top, b.bottom - b.top);
+    let denom = da.cross(db);
+    if denom == 0. {
+        return None;
+    }
+    let offset = b.top - a.top;
+    let t = offset.cross(db) / denom;
+    let u = offset.cross(da) / denom;
+    (t > 0. && t < 1. && u > 0. && u < 1.).then_some(a.top.y + da.y * t)
+}
+
+#[cfg(test)]
+fn v(x: f32, y: f32) -> Vector2f {
+    Vector2f::new(x, y)
+}
+
+/// Total area of a list of triangles
+#[cfg(test)]
+fn area(triangles: &[Vertex]) -> f32 {
+    triangles
+        .chunks_exact(3)
+        .map(|t| {
+            (t[1].position - t[0].position)
+                .cross(t[2].position - t[0].position)
+                .abs()
+                / 2.
+        })
+        .sum()
+}
+
+/// Whether `p` is strictly inside one of the triangles
+#[cfg(test)]
+fn covers(triangles: &[Vertex], p: Vector2f) -> bool {
+    triangles.chunks_exact(3).any(|t| {
+        let (a, b, c) = (t[0].position, t[1].position, t[2].position);
+        let d = [
+            (b - a).cross(p - a),
+            (c - b).cross(p - b),
+            (a - c).cross(p - c),
+        ];
+        d.iter().all(|&d| d > 0.) || d.iter().all(|&d| d < 0.)
+    })
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used)]
+fn dashes(len: f32, dashes: &[f32], offset: f32) -> Vec<(f32, f32)> {
+    let contour = Contour {
+        points: vec![v(0., 0.), v(len, 0.)],
+        closed: false,
+    };
+    let mut style = StrokeStyle::new(1.);
+    style.dashes = dashes.to_vec();
+    style.dash_offset = offset;
+    dash_contour(&contour, &style)
+        .unwrap()
+        .iter()
+        .map(|dash| (dash[0].x, dash[dash.len() - 1].x))
+        .collect()
+}
+
+#[cfg(test)]
+fn assert_dashes(actual: &[(f32, f32)], expected: &[(f32, f32)]) {
+    assert_eq!(actual.len(), expected.len(), "{actual:?}");
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (a.0 - e.0).abs() < 1e-4 && (a.1 - e.1).abs() < 1e-4,
+            "{actual:?}"
+        );
+    }
+}
+
+#[test]
+fn test_dash_phase() {
+    let pattern = [2., 1.];
+    let from_start = [(0., 2.), (3., 5.), (6., 8.), (9., 10.)];
+    assert_dashes(&dashes(10., &pattern, 0.), &from_start);
+    // Starting inside a dash
+    assert_dashes(
+        &dashes(10., &pattern, 1.),
+        &[(0., 1.), (2., 4.), (5., 7.), (8., 10.)],
+    );
+    // Starting inside a gap
+    assert_dashes(
+        &dashes(10., &pattern, 2.5),
+        &[(0.5, 2.5), (3.5, 5.5), (6.5, 8.5), (9.5, 10.)],
+    );
+    // Offsets wrap around the pattern, in both directions
+    assert_dashes(&dashes(10., &pattern, 30.), &from_start);
+    assert_dashes(&dashes(10., &pattern, -1.), &[(1., 3.), (4., 6.), (7., 9.)]);
+    // Odd patterns are repeated twice
+    assert_dashes(&dashes(10., &[3.], 0.), &[(0., 3.), (6., 9.)]);
+}
+
+#[test]
+fn test_dash_tiny_negative_offset() {
+    // The offset wraps to the total length of the pattern, which is the same phase as
+    // zero. Walking the whole pattern instead ends in the gap, just before the first dash.
+    let pattern = [0.3, 0.2];
+    let expected = dashes(1., &pattern, 0.);
+    let actual = dashes(1., &pattern, -1e-9);
+    assert_dashes(&actual, &expected);
+    assert_eq!(actual[0].0, 0.);
+}
+
+#[test]
+fn test_dash_closed_contour() {
+    #![expect(clippy::unwrap_used)]
+    let path = Path::rect(FloatRect::new(0., 0., 4., 4.));
+    let mut style = StrokeStyle::new(1.);
+    style.dashes = vec![3., 1.];
+    let dashes = dash_contour(&path.contours()[0], &style).unwrap();
+    // Each dash turns a corner, and the last one ends at the starting point
+    assert_eq!(dashes.len(), 4);
+    assert_eq!(dashes[0], [v(0., 0.), v(3., 0.)]);
+    assert_eq!(dashes[1], [v(4., 0.), v(4., 3.)]);
+    assert_eq!(dashes[3], [v(0., 4.), v(0., 1.)]);
+}
+
+#[test]
+fn test_fill_bow_tie() {
+    let mut path = Path::new();
+    let _ = path
+        .move_to(v(0., 0.))
+        .line_to(v(10., 10.))
+        .line_to(v(10., 0.))
+        .line_to(v(0., 10.))
+        .close();
+    for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+        let fill = path.fill(rule, Color::WHITE);
+        assert!((area(&fill) - 50.).abs() < 1e-3, "{rule:?}");
+        // The lobes are on the left and right, where the contour crosses itself
+        assert!(covers(&fill, v(1.7, 5.3)));
+        assert!(covers(&fill, v(8.4, 4.7)));
+        assert!(!covers(&fill, v(5.3, 1.7)));
+        assert!(!covers(&fill, v(4.7, 8.4)));
+    }
+}
+
+#[test]
+fn test_fill_hole() {
+    let square = |path: &mut Path, min: f32, max: f32, clockwise: bool| {
+        let (b, d) = if clockwise {
+            (v(max, min), v(min, max))
+        } else {
+            (v(min, max), v(max, min))
+        };
+        let _ = path
+            .move_to(v(min, min))
+            .line_to(b)
+            .line_to(v(max, max))
+            .line_to(d)
+            .close();
+    };
+    // Off the diagonals of the triangles
+    let center = v(5.1, 4.8);
+    for inner_clockwise in [true, false] {
+        let mut path = Path::new();
+        square(&mut path, 0., 10., true);
+        square(&mut path, 3., 7., inner_clockwise);
+        let fill = path.fill(FillRule::EvenOdd, Color::WHITE);
+        assert!((area(&fill) - 84.).abs() < 1e-3);
+        assert!(!covers(&fill, center));
+        assert!(covers(&fill, v(1.1, 5.2)));
+        // Nonzero only makes a hole if the contours go in opposite directions
+        let fill = path.fill(FillRule::NonZero, Color::WHITE);
+        let expected = if inner_clockwise { 100. } else { 84. };
+        assert!((area(&fill) - expected).abs() < 1e-3);
+        assert_eq!(covers(&fill, center), inner_clockwise);
+    }
+}
+
+#[test]
+fn test_miter_limit() {
+    let stroke = |points: &[Vector2f], limit: f32| {
+        let mut style = StrokeStyle::new(2.);
+        style.miter_limit = limit;
+        Path::polyline(points, false).stroke(&style, Color::WHITE)
+    };
+    // Two segments, and a join: a quad for a miter, a triangle for a bevel
+    let right_angle = [v(0., 0.), v(10., 0.), v(10., 10.)];
+    assert_eq!(stroke(&right_angle, 4.).len(), 18);
+    assert_eq!(stroke(&right_angle, 1.4).len(), 15);
+    let sharp = [v(0., 0.), v(10., 0.), v(0., 1.)];
+    assert_eq!(stroke(&sharp, 4.).len(), 15);
+    assert_eq!(stroke(&sharp, 100.).len(), 18);
+}
+
+#[test]
+fn test_curve_flattening() {
+    let mut path = Path::new();
+    // Straight curves need a single segment
+    let _ = path.move_to(v(0., 0.)).quad_to(v(5., 0.), v(10., 0.));
+    assert_eq!(path.contours()[0].points, [v(0., 0.), v(10., 0.)]);
+    // Wang's formula: ceil(sqrt(2 * 1 / 8 * 200 / 0.25)) = 15 segments
+    let (p0, c, p1) = (v(0., 0.), v(50., 100.), v(100., 0.));
+    path.clear();
+    let _ = path.move_to(p0).quad_to(c, p1);
+    let points = &path.contours()[0].points;
+    assert_eq!(points.len(), 16);
+    // Each segment stays within the tolerance of the curve
+    for (i, seg) in points.windows(2).enumerate() {
+        #[expect(clippy::cast_precision_loss, reason = "Small count")]
+        let t = (i as f32 + 0.5) / 15.;
+        let mt = 1. - t;
+        let on_curve = p0 * (mt * mt) + c * (2. * mt * t) + p1 * (t * t);
+        assert!(length(on_curve - (seg[0] + seg[1]) / 2.) <= 0.25);
+    }
+}
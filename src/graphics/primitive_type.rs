@@ -8,6 +8,8 @@ use crate::ffi;
 /// See: [`RenderWindow::draw_primitives`](crate::graphics::RenderTarget::draw_primitives)
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct PrimitiveType(pub(super) ffi::graphics::sfPrimitiveType);
 
 impl PrimitiveType {
@@ -1,12 +1,13 @@
 use {
     crate::system::Vector2,
-    num_traits::AsPrimitive,
+    num_traits::{AsPrimitive, Num, NumCast},
     std::ops::{Add, Sub},
 };
 
 /// Utility type for manipulating 2D axis-aligned rectangles.
 #[repr(C)]
 #[derive(Clone, PartialEq, Eq, Debug, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect<T> {
     /// Left coordinate of the rectangle.
     pub left: T,
@@ -252,6 +253,402 @@ impl<T: PartialOrd + Add<Output = T> + Sub<Output = T> + Copy> Rect<T> {
     }
 }
 
+impl<T: Num + PartialOrd + Copy> Rect<T> {
+    /// Construct a rectangle from its top-left and bottom-right corners.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// let rect = Rect::from_min_max(Vector2::new(1, 2), Vector2::new(4, 6));
+    /// assert_eq!(rect, Rect::new(1, 2, 3, 4));
+    /// ```
+    pub fn from_min_max(min: Vector2<T>, max: Vector2<T>) -> Self {
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Construct the smallest rectangle containing all `points`.
+    ///
+    /// Returns `None` if there are no points.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// let points = [Vector2::new(3, 1), Vector2::new(-1, 4), Vector2::new(2, 2)];
+    /// assert_eq!(Rect::from_points(points), Some(Rect::new(-1, 1, 4, 3)));
+    /// assert_eq!(Rect::<i32>::from_points([]), None);
+    /// ```
+    pub fn from_points<I: IntoIterator<Item = Vector2<T>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min_corner, max_corner) = points.fold((first, first), |(lo, hi), p| {
+            (
+                Vector2::new(min(lo.x, p.x), min(lo.y, p.y)),
+                Vector2::new(max(hi.x, p.x), max(hi.y, p.y)),
+            )
+        });
+        Some(Self::from_min_max(min_corner, max_corner))
+    }
+
+    /// Get the top-left corner of the rectangle.
+    ///
+    /// Unlike [`position`](Rect::position), this is the corner with the smallest
+    /// coordinates even if the rectangle has negative dimensions.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// assert_eq!(Rect::new(1, 2, 3, 4).min_corner(), Vector2::new(1, 2));
+    /// assert_eq!(Rect::new(1, 2, -3, 4).min_corner(), Vector2::new(-2, 2));
+    /// ```
+    pub fn min_corner(self) -> Vector2<T> {
+        Vector2::new(
+            min(self.left, self.left + self.width),
+            min(self.top, self.top + self.height),
+        )
+    }
+
+    /// Get the bottom-right corner of the rectangle.
+    ///
+    /// This is the corner with the largest coordinates, even if the rectangle has
+    /// negative dimensions.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// assert_eq!(Rect::new(1, 2, 3, 4).max_corner(), Vector2::new(4, 6));
+    /// assert_eq!(Rect::new(1, 2, -3, 4).max_corner(), Vector2::new(1, 6));
+    /// ```
+    pub fn max_corner(self) -> Vector2<T> {
+        Vector2::new(
+            max(self.left, self.left + self.width),
+            max(self.top, self.top + self.height),
+        )
+    }
+
+    /// Get the rectangle with the same area, but non-negative dimensions.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// assert_eq!(Rect::new(4, 6, -3, -4).normalized(), Rect::new(1, 2, 3, 4));
+    /// ```
+    pub fn normalized(self) -> Self {
+        Self::from_min_max(self.min_corner(), self.max_corner())
+    }
+
+    /// Get the center of the rectangle.
+    ///
+    /// For integer rectangles, the center is rounded towards the position.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// assert_eq!(Rect::new(0., 0., 3., 4.).center(), Vector2::new(1.5, 2.));
+    /// assert_eq!(Rect::new(0, 0, 3, 4).center(), Vector2::new(1, 2));
+    /// ```
+    pub fn center(self) -> Vector2<T> {
+        let two = T::one() + T::one();
+        Vector2::new(self.left + self.width / two, self.top + self.height / two)
+    }
+
+    /// Get the area of the rectangle.
+    ///
+    /// The area is negative if exactly one of the dimensions is negative.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// assert_eq!(Rect::new(1, 2, 3, 4).area(), 12);
+    /// ```
+    pub fn area(self) -> T {
+        self.width * self.height
+    }
+
+    /// Whether the rectangle has no area, i.e. one of its dimensions is zero.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// assert!(Rect::new(1, 2, 0, 4).is_empty());
+    /// assert!(!Rect::new(1, 2, 3, 4).is_empty());
+    /// ```
+    pub fn is_empty(self) -> bool {
+        self.width == T::zero() || self.height == T::zero()
+    }
+
+    /// Check if two rectangles overlap.
+    ///
+    /// Rectangles that only share an edge don't overlap.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// let a = Rect::new(0, 0, 2, 2);
+    /// assert!(a.intersects(&Rect::new(1, 1, 2, 2)));
+    /// assert!(!a.intersects(&Rect::new(2, 0, 2, 2)));
+    /// ```
+    pub fn intersects(self, other: &Rect<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Check if `other` is entirely inside the rectangle.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// let a = Rect::new(0, 0, 4, 4);
+    /// assert!(a.contains_rect(&Rect::new(1, 1, 3, 3)));
+    /// assert!(!a.contains_rect(&Rect::new(1, 1, 4, 4)));
+    /// ```
+    pub fn contains_rect(self, other: &Rect<T>) -> bool {
+        let (min_a, max_a) = (self.min_corner(), self.max_corner());
+        let (min_b, max_b) = (other.min_corner(), other.max_corner());
+        min_b.x >= min_a.x && min_b.y >= min_a.y && max_b.x <= max_a.x && max_b.y <= max_a.y
+    }
+
+    /// Get the smallest rectangle containing both rectangles.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// let a = Rect::new(0, 0, 2, 2);
+    /// let b = Rect::new(3, 1, 2, 2);
+    /// assert_eq!(a.union(&b), Rect::new(0, 0, 5, 3));
+    /// ```
+    pub fn union(self, other: &Rect<T>) -> Self {
+        let (min_a, max_a) = (self.min_corner(), self.max_corner());
+        let (min_b, max_b) = (other.min_corner(), other.max_corner());
+        Self::from_min_max(
+            Vector2::new(min(min_a.x, min_b.x), min(min_a.y, min_b.y)),
+            Vector2::new(max(max_a.x, max_b.x), max(max_a.y, max_b.y)),
+        )
+    }
+
+    /// Get the rectangle moved by `offset`.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// let a = Rect::new(1, 2, 3, 4);
+    /// assert_eq!(a.translate(Vector2::new(10, -2)), Rect::new(11, 0, 3, 4));
+    /// ```
+    pub fn translate(self, offset: Vector2<T>) -> Self {
+        Rect::new(
+            self.left + offset.x,
+            self.top + offset.y,
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Get the rectangle grown by `amount` on every side.
+    ///
+    /// The center stays the same, and each dimension grows by twice the amount.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// let a = Rect::new(1, 2, 3, 4);
+    /// assert_eq!(a.inflate(Vector2::new(1, 2)), Rect::new(0, 0, 5, 8));
+    /// ```
+    pub fn inflate(self, amount: Vector2<T>) -> Self {
+        Rect::new(
+            self.left - amount.x,
+            self.top - amount.y,
+            self.width + amount.x + amount.x,
+            self.height + amount.y + amount.y,
+        )
+    }
+
+    /// Get the rectangle shrunk by `amount` on every side.
+    ///
+    /// The center stays the same. Dimensions that would become negative are set to
+    /// zero instead.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// let a = Rect::new(0., 0., 4., 4.);
+    /// assert_eq!(a.deflate(Vector2::new(1., 3.)), Rect::new(1., 2., 2., 0.));
+    /// ```
+    pub fn deflate(self, amount: Vector2<T>) -> Self {
+        let rect = self.normalized();
+        let center = rect.center();
+        let two = T::one() + T::one();
+        // Compared before subtracting, so unsigned rectangles don't underflow
+        let (left, width) = if amount.x > rect.width / two {
+            (center.x, T::zero())
+        } else {
+            (rect.left + amount.x, rect.width - amount.x - amount.x)
+        };
+        let (top, height) = if amount.y > rect.height / two {
+            (center.y, T::zero())
+        } else {
+            (rect.top + amount.y, rect.height - amount.y - amount.y)
+        };
+        Rect::new(left, top, width, height)
+    }
+
+    /// Get the point of the rectangle closest to `point`.
+    ///
+    /// Points inside the rectangle are returned unchanged. The right and bottom
+    /// edges are included.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// # use sf2g::system::Vector2;
+    /// let a = Rect::new(0., 0., 4., 4.);
+    /// assert_eq!(a.clamp_point(Vector2::new(2., 9.)), Vector2::new(2., 4.));
+    /// assert_eq!(a.clamp_point(Vector2::new(-1., 1.)), Vector2::new(0., 1.));
+    /// ```
+    pub fn clamp_point(self, point: Vector2<T>) -> Vector2<T> {
+        let (lo, hi) = (self.min_corner(), self.max_corner());
+        Vector2::new(min(max(point.x, lo.x), hi.x), min(max(point.y, lo.y), hi.y))
+    }
+
+    /// Split the rectangle in two at `offset` from its left edge.
+    ///
+    /// The offset is clamped to the rectangle, so one of the halves can be empty.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// let (left, right) = Rect::new(0, 0, 10, 4).split_at_x(3);
+    /// assert_eq!(left, Rect::new(0, 0, 3, 4));
+    /// assert_eq!(right, Rect::new(3, 0, 7, 4));
+    /// ```
+    pub fn split_at_x(self, offset: T) -> (Self, Self) {
+        let rect = self.normalized();
+        let offset = min(max(offset, T::zero()), rect.width);
+        (
+            Rect::new(rect.left, rect.top, offset, rect.height),
+            Rect::new(
+                rect.left + offset,
+                rect.top,
+                rect.width - offset,
+                rect.height,
+            ),
+        )
+    }
+
+    /// Split the rectangle in two at `offset` from its top edge.
+    ///
+    /// The offset is clamped to the rectangle, so one of the halves can be empty.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// let (top, bottom) = Rect::new(0, 0, 4, 10).split_at_y(3);
+    /// assert_eq!(top, Rect::new(0, 0, 4, 3));
+    /// assert_eq!(bottom, Rect::new(0, 3, 4, 7));
+    /// ```
+    pub fn split_at_y(self, offset: T) -> (Self, Self) {
+        let rect = self.normalized();
+        let offset = min(max(offset, T::zero()), rect.height);
+        (
+            Rect::new(rect.left, rect.top, rect.width, offset),
+            Rect::new(
+                rect.left,
+                rect.top + offset,
+                rect.width,
+                rect.height - offset,
+            ),
+        )
+    }
+
+    /// Split the rectangle into a grid of `columns` by `rows` cells, in row-major order.
+    ///
+    /// The cells cover the whole rectangle without overlapping. For integer rectangles
+    /// that don't divide evenly, the cells differ in size by at most one.
+    ///
+    /// Returns an empty `Vec` if `columns` or `rows` is zero, or can't be represented as `T`.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// let cells = Rect::new(0, 0, 10, 4).split_grid(3, 2);
+    /// assert_eq!(cells.len(), 6);
+    /// assert_eq!(cells[0], Rect::new(0, 0, 3, 2));
+    /// assert_eq!(cells[2], Rect::new(6, 0, 4, 2));
+    /// assert_eq!(cells[4], Rect::new(3, 2, 3, 2));
+    /// ```
+    pub fn split_grid(self, columns: usize, rows: usize) -> Vec<Self>
+    where
+        T: NumCast,
+    {
+        let rect = self.normalized();
+        let edges = |start: T, len: T, count: usize| -> Option<Vec<T>> {
+            // `len * i / count` would overflow for large integer rectangles, so `len` is
+            // split into a multiple of `count` and a remainder, whose share is computed in f64
+            let n = T::from(count)?;
+            let rem = len % n;
+            let quot = (len - rem) / n;
+            (0..=count)
+                .map(|i| {
+                    let share = T::from(rem.to_f64()? * i as f64 / count as f64)?;
+                    Some(start + quot * T::from(i)? + share)
+                })
+                .collect()
+        };
+        if columns == 0 || rows == 0 {
+            return Vec::new();
+        }
+        let (Some(xs), Some(ys)) = (
+            edges(rect.left, rect.width, columns),
+            edges(rect.top, rect.height, rows),
+        ) else {
+            return Vec::new();
+        };
+        ys.windows(2)
+            .flat_map(|y| {
+                xs.windows(2)
+                    .map(move |x| Rect::new(x[0], y[0], x[1] - x[0], y[1] - y[0]))
+            })
+            .collect()
+    }
+}
+
+impl<T: AsPrimitive<f32>> Rect<T> {
+    /// Get the aspect ratio of the rectangle, i.e. its width divided by its height.
+    ///
+    /// # Usage Example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Rect;
+    /// assert_eq!(Rect::new(0, 0, 16, 9).aspect_ratio(), 16. / 9.);
+    /// ```
+    pub fn aspect_ratio(self) -> f32 {
+        self.width.as_() / self.height.as_()
+    }
+}
+
 #[inline]
 fn min<T: PartialOrd>(a: T, b: T) -> T {
     if a < b { a } else { b }
@@ -266,3 +663,42 @@ fn max<T: PartialOrd>(a: T, b: T) -> T {
 fn min_max<T: PartialOrd + Copy>(a: T, b: T) -> (T, T) {
     (min(a, b), max(a, b))
 }
+
+#[test]
+fn test_deflate_unsigned() {
+    let rect: Rect<u32> = Rect::new(10, 10, 5, 4);
+    assert_eq!(rect.deflate(Vector2::new(2, 1)), Rect::new(12, 11, 1, 2));
+    assert_eq!(rect.deflate(Vector2::new(3, 2)), Rect::new(12, 12, 0, 0));
+    assert_eq!(
+        rect.deflate(Vector2::new(u32::MAX, 0)),
+        Rect::new(12, 10, 0, 4)
+    );
+    assert_eq!(
+        Rect::<u8>::new(0, 0, 0, 0).deflate(Vector2::new(1, 1)),
+        Rect::default()
+    );
+}
+
+#[test]
+fn test_split_grid_large() {
+    let rect: Rect<u32> = Rect::new(0, 0, u32::MAX, 10);
+    let cells = rect.split_grid(3, 1);
+    assert_eq!(cells.len(), 3);
+    assert_eq!(cells[0], Rect::new(0, 0, 1_431_655_765, 10));
+    assert_eq!(cells[2].left + cells[2].width, u32::MAX);
+    assert!(
+        cells
+            .windows(2)
+            .all(|w| w[0].left + w[0].width == w[1].left)
+    );
+    // Remainder shares have to fit in small types too
+    let cells = Rect::<u8>::new(0, 0, 250, 1).split_grid(20, 1);
+    assert_eq!(
+        cells
+            .iter()
+            .map(|c| Into::<u32>::into(c.width))
+            .sum::<u32>(),
+        250
+    );
+    assert!(cells.iter().all(|c| c.width == 12 || c.width == 13));
+}
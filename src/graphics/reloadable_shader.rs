@@ -0,0 +1,344 @@
+use {
+    super::ShaderType,
+    crate::{
+        SfError, SfResult,
+        cpp::FBox,
+        graphics::{Color, CurrentTexture, IntoUniform, Shader, Texture, Transform, glsl},
+        system::Time,
+    },
+    std::{collections::HashMap, time::SystemTime},
+};
+
+/// Value of a shader uniform, as recorded by [`ReloadableShader`].
+///
+/// Each variant corresponds to one of the `set_uniform_*` methods of [`Shader`].
+#[derive(Debug, Clone)]
+pub enum Uniform<'texture> {
+    /// `float`
+    Float(f32),
+    /// `vec2`
+    Vec2(glsl::Vec2),
+    /// `vec3`
+    Vec3(glsl::Vec3),
+    /// `vec4`
+    Vec4(glsl::Vec4),
+    /// `int`
+    Int(i32),
+    /// `ivec2`
+    IVec2(glsl::IVec2),
+    /// `ivec3`
+    IVec3(glsl::IVec3),
+    /// `ivec4`
+    IVec4(glsl::IVec4),
+    /// `bool`
+    Bool(bool),
+    /// `bvec2`
+    BVec2(glsl::BVec2),
+    /// `bvec3`
+    BVec3(glsl::BVec3),
+    /// `bvec4`
+    BVec4(glsl::BVec4),
+    /// `mat3`
+    Mat3(glsl::Mat3),
+    /// `mat4`
+    Mat4(glsl::Mat4),
+    /// `sampler2D`
+    Texture(&'texture Texture),
+    /// `sampler2D` bound to the texture of the object being drawn
+    CurrentTexture,
+    /// `float[]`
+    FloatArray(Vec<f32>),
+    /// `vec2[]`
+    Vec2Array(Vec<glsl::Vec2>),
+    /// `vec3[]`
+    Vec3Array(Vec<glsl::Vec3>),
+    /// `vec4[]`
+    Vec4Array(Vec<glsl::Vec4>),
+    /// `mat3[]`
+    Mat3Array(Vec<glsl::Mat3>),
+    /// `mat4[]`
+    Mat4Array(Vec<glsl::Mat4>),
+}
+
+impl<'texture> Uniform<'texture> {
+    /// Set this value as the uniform `name` of `shader`.
+    pub fn apply(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        match self {
+            Self::Float(v) => shader.set_uniform_float(name, *v),
+            Self::Vec2(v) => shader.set_uniform_vec2(name, *v),
+            Self::Vec3(v) => shader.set_uniform_vec3(name, *v),
+            Self::Vec4(v) => shader.set_uniform_vec4(name, *v),
+            Self::Int(v) => shader.set_uniform_int(name, *v),
+            Self::IVec2(v) => shader.set_uniform_ivec2(name, *v),
+            Self::IVec3(v) => shader.set_uniform_ivec3(name, *v),
+            Self::IVec4(v) => shader.set_uniform_ivec4(name, *v),
+            Self::Bool(v) => shader.set_uniform_bool(name, *v),
+            Self::BVec2(v) => shader.set_uniform_bvec2(name, *v),
+            Self::BVec3(v) => shader.set_uniform_bvec3(name, *v),
+            Self::BVec4(v) => shader.set_uniform_bvec4(name, *v),
+            Self::Mat3(v) => shader.set_uniform_mat3(name, *v),
+            Self::Mat4(v) => shader.set_uniform_mat4(name, *v),
+            Self::Texture(tex) => shader.set_uniform_texture(name, tex),
+            Self::CurrentTexture => shader.set_uniform_current_texture(name),
+            Self::FloatArray(v) => shader.set_uniform_array_float(name, v),
+            Self::Vec2Array(v) => shader.set_uniform_array_vec2(name, v),
+            Self::Vec3Array(v) => shader.set_uniform_array_vec3(name, v),
+            Self::Vec4Array(v) => shader.set_uniform_array_vec4(name, v),
+            Self::Mat3Array(v) => shader.set_uniform_array_mat3(name, v),
+            Self::Mat4Array(v) => shader.set_uniform_array_mat4(name, v),
+        }
+    }
+}
+
+macro_rules! impl_from_for_uniform {
+    ($($t:ty => $variant:ident,)*) => {
+        $(
+            impl From<$t> for Uniform<'_> {
+                fn from(value: $t) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_uniform! {
+    f32 => Float,
+    glsl::Vec2 => Vec2,
+    glsl::Vec3 => Vec3,
+    glsl::Vec4 => Vec4,
+    i32 => Int,
+    glsl::IVec2 => IVec2,
+    glsl::IVec3 => IVec3,
+    glsl::IVec4 => IVec4,
+    bool => Bool,
+    glsl::BVec2 => BVec2,
+    glsl::BVec3 => BVec3,
+    glsl::BVec4 => BVec4,
+    glsl::Mat3 => Mat3,
+    glsl::Mat4 => Mat4,
+    Color => Vec4,
+    Transform => Mat4,
+    Vec<f32> => FloatArray,
+    Vec<glsl::Vec2> => Vec2Array,
+    Vec<glsl::Vec3> => Vec3Array,
+    Vec<glsl::Vec4> => Vec4Array,
+    Vec<glsl::Mat3> => Mat3Array,
+    Vec<glsl::Mat4> => Mat4Array,
+}
+
+impl From<Time> for Uniform<'_> {
+    fn from(value: Time) -> Self {
+        Self::Float(value.as_seconds())
+    }
+}
+
+impl<'texture> From<&'texture Texture> for Uniform<'texture> {
+    fn from(value: &'texture Texture) -> Self {
+        Self::Texture(value)
+    }
+}
+
+impl From<CurrentTexture> for Uniform<'_> {
+    fn from(_: CurrentTexture) -> Self {
+        Self::CurrentTexture
+    }
+}
+
+impl<'texture> IntoUniform<'texture> for &Uniform<'texture> {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        self.apply(shader, name)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Sources {
+    Single {
+        path: String,
+        type_: ShaderType,
+    },
+    VertFrag {
+        vert: String,
+        frag: String,
+    },
+    All {
+        vert: String,
+        geom: String,
+        frag: String,
+    },
+}
+
+impl Sources {
+    fn paths(&self) -> Vec<&str> {
+        match self {
+            Self::Single { path, .. } => vec![path],
+            Self::VertFrag { vert, frag } => vec![vert, frag],
+            Self::All { vert, geom, frag } => vec![vert, geom, frag],
+        }
+    }
+    fn load<'texture>(&self) -> SfResult<FBox<Shader<'texture>>> {
+        match self {
+            Self::Single { path, type_ } => Shader::from_file(path, *type_),
+            Self::VertFrag { vert, frag } => Shader::from_file_vert_frag(vert, frag),
+            Self::All { vert, geom, frag } => Shader::from_file_all(vert, geom, frag),
+        }
+    }
+    fn mtimes(&self) -> Vec<Option<SystemTime>> {
+        self.paths()
+            .into_iter()
+            .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+}
+
+/// Outcome of [`ReloadableShader::poll`].
+#[derive(Debug)]
+pub enum ReloadStatus {
+    /// None of the source files changed since the last check
+    Unchanged,
+    /// The sources changed, and the shader was recompiled successfully
+    Reloaded,
+    /// The sources changed, but compilation failed.
+    ///
+    /// The last successfully compiled program stays active.
+    Failed(SfError),
+}
+
+/// A [`Shader`] loaded from files, which can be recompiled when the files change on disk.
+///
+/// Meant for iterating on shaders during development without restarting the application.
+/// Call [`poll`] regularly (e.g. once per frame); it checks the modification times of the
+/// source files, and recompiles the shader if any of them changed.
+///
+/// If compilation fails, the previously compiled program stays active, and the error is
+/// reported (with the compile log SFML emitted, see [`crate::system::err`]).
+///
+/// Uniforms set through [`set_uniform`] are recorded, and applied again to the new program
+/// after every successful reload.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let sprite: Sprite = unimplemented!();
+/// let mut shader = ReloadableShader::from_file("blur.frag", ShaderType::Fragment).unwrap();
+/// shader.set_uniform("blur_radius", 0.5).unwrap();
+/// loop {
+///     if let ReloadStatus::Failed(e) = shader.poll() {
+///         eprintln!("Failed to reload shader: {e}");
+///     }
+///     let mut states = RenderStates::default();
+///     states.shader = Some(shader.shader());
+///     window.draw_sprite(&sprite, &states);
+///     # break;
+/// }
+/// ```
+///
+/// [`poll`]: ReloadableShader::poll
+/// [`set_uniform`]: ReloadableShader::set_uniform
+#[derive(Debug)]
+pub struct ReloadableShader<'texture> {
+    shader: FBox<Shader<'texture>>,
+    sources: Sources,
+    mtimes: Vec<Option<SystemTime>>,
+    uniforms: HashMap<String, Uniform<'texture>>,
+    last_error: Option<SfError>,
+}
+
+impl<'texture> ReloadableShader<'texture> {
+    fn load(sources: Sources) -> SfResult<Self> {
+        let mtimes = sources.mtimes();
+        Ok(Self {
+            shader: sources.load()?,
+            sources,
+            mtimes,
+            uniforms: HashMap::new(),
+            last_error: None,
+        })
+    }
+    /// Load the vertex, geometry or fragment shader from a file.
+    ///
+    /// See [`Shader::from_file`].
+    pub fn from_file(path: &str, type_: ShaderType) -> SfResult<Self> {
+        Self::load(Sources::Single {
+            path: path.to_owned(),
+            type_,
+        })
+    }
+    /// Load both the vertex and fragment shaders from files.
+    ///
+    /// See [`Shader::from_file_vert_frag`].
+    pub fn from_file_vert_frag(vert: &str, frag: &str) -> SfResult<Self> {
+        Self::load(Sources::VertFrag {
+            vert: vert.to_owned(),
+            frag: frag.to_owned(),
+        })
+    }
+    /// Load the vertex, geometry and fragment shaders from files.
+    ///
+    /// See [`Shader::from_file_all`].
+    pub fn from_file_all(vert: &str, geom: &str, frag: &str) -> SfResult<Self> {
+        Self::load(Sources::All {
+            vert: vert.to_owned(),
+            geom: geom.to_owned(),
+            frag: frag.to_owned(),
+        })
+    }
+    /// Recompile the shader if any of its source files changed since the last check.
+    ///
+    /// Files that can't be accessed (for example, while an editor is replacing them) are
+    /// skipped until they are available again.
+    pub fn poll(&mut self) -> ReloadStatus {
+        let mtimes = self.sources.mtimes();
+        if mtimes.iter().any(Option::is_none) || mtimes == self.mtimes {
+            return ReloadStatus::Unchanged;
+        }
+        self.mtimes = mtimes;
+        match self.reload() {
+            Ok(()) => ReloadStatus::Reloaded,
+            Err(e) => ReloadStatus::Failed(e),
+        }
+    }
+    /// Recompile the shader from its source files, regardless of whether they changed.
+    ///
+    /// On failure, the last successfully compiled program stays active.
+    pub fn reload(&mut self) -> SfResult<()> {
+        let result = self.sources.load().and_then(|mut shader| {
+            for (name, value) in &self.uniforms {
+                value.apply(&mut shader, name)?;
+            }
+            Ok(shader)
+        });
+        match result {
+            Ok(shader) => {
+                self.shader = shader;
+                self.last_error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.last_error = Some(e.clone());
+                Err(e)
+            }
+        }
+    }
+    /// Set the uniform `name` to `value`, and remember it to apply it again after reloading.
+    pub fn set_uniform(&mut self, name: &str, value: impl Into<Uniform<'texture>>) -> SfResult<()> {
+        let value = value.into();
+        value.apply(&mut self.shader, name)?;
+        let _ = self.uniforms.insert(name.to_owned(), value);
+        Ok(())
+    }
+    /// The currently active shader.
+    ///
+    /// This is the last successfully compiled program.
+    #[must_use]
+    pub fn shader(&self) -> &Shader<'texture> {
+        &self.shader
+    }
+    /// The error from the last reload attempt, if it failed.
+    #[must_use]
+    pub fn last_error(&self) -> Option<&SfError> {
+        self.last_error.as_ref()
+    }
+}
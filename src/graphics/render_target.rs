@@ -31,10 +31,10 @@ pub trait RenderTarget {
     fn clear(&mut self, color: Color);
 
     /// return the current view
-    fn view(&self) -> &View;
+    fn view(&self) -> View;
 
     /// get the default view for the render target
-    fn default_view(&self) -> &View;
+    fn default_view(&self) -> View;
 
     /// set a new view to the target
     fn set_view(&mut self, view: &View);
@@ -1,11 +1,12 @@
 use crate::{
-    IntoSfResult, SfResult,
+    SfResult,
     cpp::FBox,
     ffi::graphics as ffi,
     graphics::{
         CircleShape, Color, ConvexShape, IntRect, PrimitiveType, RectangleShape, RenderStates,
         RenderTarget, Sprite, Texture, Vertex, VertexBuffer, View,
     },
+    sf_call,
     system::{Vector2f, Vector2i, Vector2u},
     window::ContextSettings,
 };
@@ -40,7 +41,7 @@ impl RenderTexture {
         height: u32,
         settings: &ContextSettings,
     ) -> SfResult<FBox<Self>> {
-        let mut new = FBox::new(unsafe { ffi::sfRenderTexture_new() }).into_sf_result()?;
+        let mut new = sf_call(|| FBox::new(unsafe { ffi::sfRenderTexture_new() }))?;
         new.recreate(width, height, settings)?;
         Ok(new)
     }
@@ -51,7 +52,7 @@ impl RenderTexture {
         height: u32,
         settings: &ContextSettings,
     ) -> SfResult<()> {
-        unsafe { ffi::sfRenderTexture_create(self, width, height, settings) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfRenderTexture_create(self, width, height, settings) })
     }
 
     /// Update the contents of the target texture
@@ -124,7 +125,7 @@ impl RenderTexture {
     /// completed and display has been called. Not calling display after subsequent drawing
     /// will lead to __undefined behavior__ if a mipmap had been previously generated.
     pub unsafe fn generate_mipmap(&mut self) -> SfResult<()> {
-        unsafe { ffi::sfRenderTexture_generateMipmap(self) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfRenderTexture_generateMipmap(self) })
     }
 
     /// Get the maximum anti-aliasing level supported by the system.
@@ -144,11 +145,11 @@ impl RenderTarget for RenderTexture {
     fn set_view(&mut self, view: &View) {
         unsafe { ffi::sfRenderTexture_setView(self, view) }
     }
-    fn view(&self) -> &View {
-        unsafe { &*(ffi::sfRenderTexture_getView(self)) }
+    fn view(&self) -> View {
+        unsafe { ffi::sfRenderTexture_getView(self) }
     }
-    fn default_view(&self) -> &View {
-        unsafe { &*(ffi::sfRenderTexture_getDefaultView(self)) }
+    fn default_view(&self) -> View {
+        unsafe { ffi::sfRenderTexture_getDefaultView(self) }
     }
     fn viewport(&self, view: &View) -> IntRect {
         unsafe { ffi::sfRenderTexture_getViewport(self, view) }
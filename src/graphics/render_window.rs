@@ -1,11 +1,12 @@
 use crate::{
-    IntoSfResult, SfError, SfResult,
+    SfResult,
     cpp::FBox,
     ffi::graphics as ffi,
     graphics::{
         CircleShape, Color, ConvexShape, IntRect, PrimitiveType, RectangleShape, RenderStates,
         RenderTarget, Sprite, Vertex, VertexBuffer, View,
     },
+    sf_call,
     system::{SfStrConv, Vector2f, Vector2i, Vector2u},
     window::{ContextSettings, Cursor, Event, Handle, Style, VideoMode, thread_safety},
 };
@@ -50,10 +51,16 @@ impl RenderWindow {
         thread_safety::set_window_thread();
 
         title.with_as_sfstr(|sfstr| {
-            let ptr = unsafe {
-                ffi::sfRenderWindow_new_mtss(mode.into(), sfstr.as_ptr(), style.bits(), settings)
-            };
-            FBox::new(ptr).ok_or(SfError::CallFailed)
+            sf_call(|| {
+                FBox::new(unsafe {
+                    ffi::sfRenderWindow_new_mtss(
+                        mode.into(),
+                        sfstr.as_ptr(),
+                        style.bits(),
+                        settings,
+                    )
+                })
+            })
         })
     }
     /// Recreate with new settings. See [`Self::new`] for more information.
@@ -94,8 +101,7 @@ impl RenderWindow {
     /// * settings - Additional settings for the underlying OpenGL context
     pub unsafe fn from_handle(handle: Handle, settings: &ContextSettings) -> SfResult<FBox<Self>> {
         thread_safety::set_window_thread();
-        let ptr = unsafe { ffi::sfRenderWindow_new_handle_settings(handle, settings) };
-        FBox::new(ptr).ok_or(SfError::CallFailed)
+        sf_call(|| FBox::new(unsafe { ffi::sfRenderWindow_new_handle_settings(handle, settings) }))
     }
 }
 
@@ -210,7 +216,7 @@ impl RenderWindow {
     /// # Arguments
     /// * active - true to activate, false to deactivate
     pub fn set_active(&mut self, enabled: bool) -> SfResult<()> {
-        unsafe { ffi::sfRenderWindow_setActive(self, enabled) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfRenderWindow_setActive(self, enabled) })
     }
 }
 
@@ -428,11 +434,11 @@ impl RenderTarget for RenderWindow {
     fn set_view(&mut self, view: &View) {
         unsafe { ffi::sfRenderWindow_setView(self, view) }
     }
-    fn view(&self) -> &View {
-        unsafe { &*(ffi::sfRenderWindow_getView(self)) }
+    fn view(&self) -> View {
+        unsafe { ffi::sfRenderWindow_getView(self) }
     }
-    fn default_view(&self) -> &View {
-        unsafe { &*(ffi::sfRenderWindow_getDefaultView(self)) }
+    fn default_view(&self) -> View {
+        unsafe { ffi::sfRenderWindow_getDefaultView(self) }
     }
     fn map_pixel_to_coords(&self, point: Vector2i, view: &View) -> Vector2f {
         unsafe { ffi::sfRenderWindow_mapPixelToCoords_View(self, point, view) }
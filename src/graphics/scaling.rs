@@ -0,0 +1,280 @@
+use crate::{
+    SfResult,
+    cpp::FBox,
+    graphics::{FloatRect, RenderStates, RenderTarget, RenderTexture, Sprite, View},
+    system::{Vector2f, Vector2i, Vector2u},
+};
+
+/// How a fixed logical resolution is fitted into a render target of a different size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScalingPolicy {
+    /// Scale uniformly as much as possible while showing the whole logical area,
+    /// adding bars on the sides (pillarbox) or on the top and bottom (letterbox).
+    #[default]
+    Letterbox,
+    /// Like [`Letterbox`](ScalingPolicy::Letterbox), but only scale by whole numbers,
+    /// so every logical pixel covers the same number of target pixels.
+    ///
+    /// If the target is smaller than the logical resolution, this falls back to `Letterbox`.
+    PixelPerfect,
+    /// Stretch the logical area over the whole target, ignoring the aspect ratio.
+    Stretch,
+    /// Scale uniformly like [`Letterbox`](ScalingPolicy::Letterbox), but show more of the
+    /// world instead of adding bars.
+    ///
+    /// The logical area is always visible, and centered.
+    Expand,
+}
+
+/// Computes views that keep a fixed logical resolution on render targets of any size.
+///
+/// Typically, the view is recomputed whenever the window is resized.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, window::Event, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// let scaling = Scaling::new(Vector2u::new(320, 180), ScalingPolicy::PixelPerfect);
+/// scaling.apply(&mut window);
+/// while let Some(event) = window.poll_event() {
+///     if let Event::Resized { .. } = event {
+///         scaling.apply(&mut window);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scaling {
+    /// The logical resolution
+    pub resolution: Vector2u,
+    /// How the logical resolution is fitted into the target
+    pub policy: ScalingPolicy,
+}
+
+impl Scaling {
+    /// Create a scaling helper for the logical resolution `resolution`.
+    #[must_use]
+    pub const fn new(resolution: Vector2u, policy: ScalingPolicy) -> Self {
+        Self { resolution, policy }
+    }
+    /// Number of target pixels per logical unit, on each axis, for a target of size
+    /// `target_size`.
+    #[must_use]
+    pub fn scale(&self, target_size: Vector2u) -> Vector2f {
+        let target = target_size.as_other::<f32>();
+        let res = self.resolution.as_other::<f32>();
+        let fit = (target.x / res.x).min(target.y / res.y);
+        match self.policy {
+            ScalingPolicy::Stretch => target.cwise_div(res),
+            ScalingPolicy::PixelPerfect if fit >= 1. => Vector2f::new(fit.floor(), fit.floor()),
+            ScalingPolicy::Letterbox | ScalingPolicy::PixelPerfect | ScalingPolicy::Expand => {
+                Vector2f::new(fit, fit)
+            }
+        }
+    }
+    /// The viewport (in target pixels) the logical area is displayed in, for a target of
+    /// size `target_size`.
+    ///
+    /// With [`ScalingPolicy::Expand`], this is the whole target, even though the logical area
+    /// only covers part of it.
+    ///
+    /// The viewport is empty if `target_size` is empty, like when the window is minimized.
+    #[must_use]
+    pub fn viewport_pixels(&self, target_size: Vector2u) -> FloatRect {
+        if target_size.x == 0 || target_size.y == 0 {
+            return FloatRect::default();
+        }
+        let target = target_size.as_other::<f32>();
+        if matches!(self.policy, ScalingPolicy::Stretch | ScalingPolicy::Expand) {
+            return FloatRect::new(0., 0., target.x, target.y);
+        }
+        let size = self
+            .resolution
+            .as_other::<f32>()
+            .cwise_mul(self.scale(target_size));
+        // Whole pixel offsets, so pixel-perfect scaling stays aligned to the target pixels
+        let offset = (target - size) / 2.;
+        let offset = Vector2f::new(offset.x.floor(), offset.y.floor());
+        FloatRect::from_vecs(offset, size)
+    }
+    /// Set the size and viewport of `view` for a target of size `target_size`,
+    /// keeping its center and rotation.
+    ///
+    /// This is useful to combine scaling with a moving view, like a [`Camera`].
+    ///
+    /// `view` is left unchanged if `target_size` is empty, like when the window is
+    /// minimized.
+    ///
+    /// [`Camera`]: crate::graphics::Camera
+    pub fn apply_to_view(&self, view: &mut View, target_size: Vector2u) {
+        if target_size.x == 0 || target_size.y == 0 {
+            return;
+        }
+        let target = target_size.as_other::<f32>();
+        let vp = self.viewport_pixels(target_size);
+        let size = vp.size().cwise_div(self.scale(target_size));
+        view.set_size(size);
+        view.set_viewport(FloatRect::new(
+            vp.left / target.x,
+            vp.top / target.y,
+            vp.width / target.x,
+            vp.height / target.y,
+        ));
+    }
+    /// The view showing the logical area `(0, 0, resolution.x, resolution.y)`, for a target
+    /// of size `target_size`.
+    #[must_use]
+    pub fn view(&self, target_size: Vector2u) -> View {
+        let res = self.resolution.as_other::<f32>();
+        let mut view = View::new(res / 2., res);
+        self.apply_to_view(&mut view, target_size);
+        view
+    }
+    /// Set the view of `target` to [`Scaling::view`] for its current size.
+    pub fn apply<T: RenderTarget + ?Sized>(&self, target: &mut T) {
+        let view = self.view(target.size());
+        target.set_view(&view);
+    }
+}
+
+/// Renders at a low logical resolution, and upscales the result to the target.
+///
+/// Drawing happens on an off-screen [`RenderTexture`] of the logical resolution, which is then
+/// scaled up with nearest neighbor filtering by [`present`](LowResCanvas::present), according
+/// to a [`ScalingPolicy`]. This guarantees that everything is drawn on the logical pixel
+/// grid, unlike [`Scaling`] alone, which only scales the view.
+///
+/// [`ScalingPolicy::Expand`] needs a canvas that changes size with the target, so it
+/// behaves like [`ScalingPolicy::Letterbox`] here.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let sprite: Sprite = unimplemented!();
+/// let mut canvas = LowResCanvas::new(Vector2u::new(320, 180), ScalingPolicy::PixelPerfect).unwrap();
+/// loop {
+///     canvas.target().clear(Color::rgb(30, 30, 60));
+///     canvas.target().draw_sprite(&sprite, &RenderStates::DEFAULT);
+///     window.clear(Color::BLACK);
+///     canvas.present(&mut window);
+///     window.display();
+///     let mouse = canvas.pixel_to_world(window.mouse_position(), window.size());
+///     # break;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LowResCanvas {
+    texture: FBox<RenderTexture>,
+    policy: ScalingPolicy,
+}
+
+impl LowResCanvas {
+    /// Create a canvas of logical resolution `resolution`.
+    pub fn new(resolution: Vector2u, policy: ScalingPolicy) -> SfResult<Self> {
+        let mut texture = RenderTexture::new(resolution.x, resolution.y)?;
+        texture.set_smooth(false);
+        Ok(Self { texture, policy })
+    }
+    /// The render texture to draw on.
+    ///
+    /// Its view can be changed freely, e.g. to scroll the world.
+    pub fn target(&mut self) -> &mut RenderTexture {
+        &mut self.texture
+    }
+    /// The logical resolution of the canvas
+    #[must_use]
+    pub fn resolution(&self) -> Vector2u {
+        self.texture.size()
+    }
+    /// The scaling policy used by [`present`](LowResCanvas::present)
+    #[must_use]
+    pub const fn policy(&self) -> ScalingPolicy {
+        self.policy
+    }
+    /// Set the scaling policy used by [`present`](LowResCanvas::present)
+    pub fn set_policy(&mut self, policy: ScalingPolicy) {
+        self.policy = policy;
+    }
+    fn scaling(&self) -> Scaling {
+        let policy = match self.policy {
+            ScalingPolicy::Expand => ScalingPolicy::Letterbox,
+            policy => policy,
+        };
+        Scaling::new(self.resolution(), policy)
+    }
+    /// Finish drawing on the canvas, and draw it scaled up on `target`.
+    ///
+    /// The areas of `target` not covered by the canvas are left untouched, so `target`
+    /// should usually be cleared before. The view of `target` is restored afterwards.
+    pub fn present<T: RenderTarget + ?Sized>(&mut self, target: &mut T) {
+        self.texture.display();
+        let old_view = target.view();
+        self.scaling().apply(target);
+        target.draw_sprite(
+            &Sprite::with_texture(self.texture.texture()),
+            &RenderStates::DEFAULT,
+        );
+        target.set_view(&old_view);
+    }
+    /// Convert a pixel of a target of size `target_size` (e.g. the mouse position in the
+    /// window) to a pixel of the canvas.
+    ///
+    /// Returns `None` if the pixel is outside of the canvas, e.g. in the letterbox bars.
+    #[must_use]
+    pub fn target_to_canvas_pixel(
+        &self,
+        pixel: Vector2i,
+        target_size: Vector2u,
+    ) -> Option<Vector2i> {
+        if target_size.x == 0 || target_size.y == 0 {
+            return None;
+        }
+        let point = self
+            .scaling()
+            .view(target_size)
+            .pixel_to_world(pixel, target_size);
+        let res = self.resolution().as_other::<f32>();
+        if point.x < 0. || point.y < 0. || point.x >= res.x || point.y >= res.y {
+            return None;
+        }
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Checked to be in the canvas"
+        )]
+        Some(Vector2i::new(point.x as i32, point.y as i32))
+    }
+    /// Convert a pixel of a target of size `target_size` (e.g. the mouse position in the
+    /// window) to world coordinates, through the current view of the canvas.
+    ///
+    /// Returns `None` if the pixel is outside of the canvas, e.g. in the letterbox bars.
+    #[must_use]
+    pub fn pixel_to_world(&self, pixel: Vector2i, target_size: Vector2u) -> Option<Vector2f> {
+        let pixel = self.target_to_canvas_pixel(pixel, target_size)?;
+        Some(self.texture.view().pixel_to_world(pixel, self.resolution()))
+    }
+}
+
+#[test]
+fn test_empty_target() {
+    for policy in [
+        ScalingPolicy::Letterbox,
+        ScalingPolicy::PixelPerfect,
+        ScalingPolicy::Stretch,
+        ScalingPolicy::Expand,
+    ] {
+        let scaling = Scaling::new(Vector2u::new(320, 180), policy);
+        let mut view = scaling.view(Vector2u::new(640, 360));
+        let before = view;
+        for size in [
+            Vector2u::new(0, 0),
+            Vector2u::new(0, 360),
+            Vector2u::new(640, 0),
+        ] {
+            assert_eq!(scaling.viewport_pixels(size), FloatRect::default());
+            scaling.apply_to_view(&mut view, size);
+            assert_eq!(view, before);
+        }
+    }
+}
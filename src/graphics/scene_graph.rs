@@ -0,0 +1,648 @@
+#[cfg(feature = "text")]
+use crate::graphics::Text;
+use {
+    crate::{
+        graphics::{
+            CircleShape, Color, ConvexShape, PrimitiveType, RectangleShape, RenderStates,
+            RenderTarget, Shape, Sprite, Texture, Transform, Transformable, TweenProperty,
+            TweenValue, Tweenable, Vertex, transformable::TransformableData,
+        },
+        system::Vector2f,
+    },
+    std::ops::{Index, IndexMut},
+};
+
+/// What a [`SceneNode`] draws
+#[derive(Debug, Default)]
+pub enum SceneDrawable<'s> {
+    /// Nothing, for nodes that only group their children
+    #[default]
+    None,
+    /// A sprite
+    Sprite(Sprite<'s>),
+    /// A rectangle
+    Rectangle(RectangleShape<'s>),
+    /// A circle
+    Circle(CircleShape<'s>),
+    /// A convex polygon
+    Convex(ConvexShape<'s>),
+    /// A text
+    #[cfg(feature = "text")]
+    Text(Text<'s>),
+    /// Arbitrary vertices
+    Vertices {
+        /// The vertices
+        vertices: Vec<Vertex>,
+        /// How the vertices are assembled
+        primitive_type: PrimitiveType,
+        /// The texture the vertices are drawn with
+        texture: Option<&'s Texture>,
+    },
+}
+
+impl SceneDrawable<'_> {
+    /// The address of the texture, to group draws by texture
+    fn texture_key(&self) -> usize {
+        let texture = match self {
+            Self::None => None,
+            Self::Sprite(sprite) => sprite.texture(),
+            Self::Rectangle(shape) => shape.texture(),
+            Self::Circle(shape) => shape.texture(),
+            Self::Convex(shape) => shape.texture(),
+            #[cfg(feature = "text")]
+            Self::Text(text) => {
+                return text
+                    .font()
+                    .map_or(0, |font| std::ptr::from_ref(font).addr());
+            }
+            Self::Vertices { texture, .. } => *texture,
+        };
+        texture.map_or(0, |texture| std::ptr::from_ref(texture).addr())
+    }
+    /// Draw with the colors modulated by `tint`.
+    fn draw<RT: RenderTarget>(&mut self, target: &mut RT, states: &RenderStates, tint: Color) {
+        // The colors are modulated for the draw call only, and restored afterwards
+        let tinted = tint != Color::WHITE;
+        match self {
+            Self::None => {}
+            Self::Sprite(sprite) => {
+                let color = sprite.color();
+                if tinted {
+                    sprite.set_color(color * tint);
+                }
+                target.draw_sprite(sprite, states);
+                if tinted {
+                    sprite.set_color(color);
+                }
+            }
+            Self::Rectangle(shape) => {
+                let colors = tinted.then(|| tint_shape(shape, tint));
+                target.draw_rectangle_shape(shape, states);
+                restore_shape(shape, colors);
+            }
+            Self::Circle(shape) => {
+                let colors = tinted.then(|| tint_shape(shape, tint));
+                target.draw_circle_shape(shape, states);
+                restore_shape(shape, colors);
+            }
+            Self::Convex(shape) => {
+                let colors = tinted.then(|| tint_shape(shape, tint));
+                target.draw_convex_shape(shape, states);
+                restore_shape(shape, colors);
+            }
+            #[cfg(feature = "text")]
+            Self::Text(text) => {
+                let colors = (text.fill_color(), text.outline_color());
+                if tinted {
+                    text.set_fill_color(colors.0 * tint);
+                    text.set_outline_color(colors.1 * tint);
+                }
+                text.draw(target, states);
+                if tinted {
+                    text.set_fill_color(colors.0);
+                    text.set_outline_color(colors.1);
+                }
+            }
+            Self::Vertices {
+                vertices,
+                primitive_type,
+                texture,
+            } => {
+                let states = RenderStates {
+                    texture: *texture,
+                    ..*states
+                };
+                if tinted {
+                    let vertices: Vec<_> = vertices
+                        .iter()
+                        .map(|v| Vertex {
+                            color: v.color * tint,
+                            ..*v
+                        })
+                        .collect();
+                    target.draw_primitives(&vertices, *primitive_type, &states);
+                } else {
+                    target.draw_primitives(vertices, *primitive_type, &states);
+                }
+            }
+        }
+    }
+}
+
+/// Modulate the fill and outline colors of `shape` by `tint`, and return the original ones.
+fn tint_shape<'s>(shape: &mut impl Shape<'s>, tint: Color) -> (Color, Color) {
+    let colors = (shape.fill_color(), shape.outline_color());
+    shape.set_fill_color(colors.0 * tint);
+    shape.set_outline_color(colors.1 * tint);
+    colors
+}
+
+fn restore_shape<'s>(shape: &mut impl Shape<'s>, colors: Option<(Color, Color)>) {
+    if let Some((fill, outline)) = colors {
+        shape.set_fill_color(fill);
+        shape.set_outline_color(outline);
+    }
+}
+
+impl<'s> From<Sprite<'s>> for SceneDrawable<'s> {
+    fn from(sprite: Sprite<'s>) -> Self {
+        Self::Sprite(sprite)
+    }
+}
+
+impl<'s> From<RectangleShape<'s>> for SceneDrawable<'s> {
+    fn from(shape: RectangleShape<'s>) -> Self {
+        Self::Rectangle(shape)
+    }
+}
+
+impl<'s> From<CircleShape<'s>> for SceneDrawable<'s> {
+    fn from(shape: CircleShape<'s>) -> Self {
+        Self::Circle(shape)
+    }
+}
+
+impl<'s> From<ConvexShape<'s>> for SceneDrawable<'s> {
+    fn from(shape: ConvexShape<'s>) -> Self {
+        Self::Convex(shape)
+    }
+}
+
+#[cfg(feature = "text")]
+impl<'s> From<Text<'s>> for SceneDrawable<'s> {
+    fn from(text: Text<'s>) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// A node of a [`SceneGraph`]: a local transform, a drawable, and how it is drawn.
+///
+/// The transform of the node is relative to its parent, and applied on top of the transform
+/// of the drawable itself.
+#[derive(Debug)]
+pub struct SceneNode<'s> {
+    /// What the node draws
+    pub drawable: SceneDrawable<'s>,
+    /// Color modulating the colors of the node and its children
+    pub color: Color,
+    /// Draw layer. Nodes on higher layers are drawn on top, whatever their `z`.
+    pub layer: i32,
+    /// Order within the layer. Nodes with a higher `z` are drawn on top.
+    pub z: i32,
+    /// Whether the node and its children are drawn
+    pub visible: bool,
+    tf: TransformableData,
+    transform: Transform,
+    inverse_transform: Transform,
+}
+
+impl Default for SceneNode<'_> {
+    fn default() -> Self {
+        Self::new(SceneDrawable::None)
+    }
+}
+
+impl<'s> SceneNode<'s> {
+    /// Create a visible node drawing `drawable`, with an identity transform.
+    #[must_use]
+    pub fn new(drawable: impl Into<SceneDrawable<'s>>) -> Self {
+        Self {
+            drawable: drawable.into(),
+            color: Color::WHITE,
+            layer: 0,
+            z: 0,
+            visible: true,
+            tf: TransformableData::default(),
+            transform: Transform::IDENTITY,
+            inverse_transform: Transform::IDENTITY,
+        }
+    }
+    /// Set the [`layer`](Self::layer) of the node.
+    #[must_use]
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+    /// Set the [`z`](Self::z) order of the node.
+    #[must_use]
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+    fn update_transform(&mut self) {
+        self.transform = self.tf.get();
+        self.inverse_transform = self.transform.inverse();
+    }
+}
+
+impl Transformable for SceneNode<'_> {
+    fn set_position<P: Into<Vector2f>>(&mut self, position: P) {
+        self.tf.position = position.into().into();
+        self.update_transform();
+    }
+    fn set_rotation(&mut self, angle: f32) {
+        self.tf.rotation = angle.rem_euclid(360.);
+        self.update_transform();
+    }
+    fn set_scale<S: Into<Vector2f>>(&mut self, scale: S) {
+        self.tf.scale = scale.into().into();
+        self.update_transform();
+    }
+    fn set_origin<O: Into<Vector2f>>(&mut self, origin: O) {
+        self.tf.origin = origin.into().into();
+        self.update_transform();
+    }
+    fn position(&self) -> Vector2f {
+        self.tf.position.into()
+    }
+    fn rotation(&self) -> f32 {
+        self.tf.rotation
+    }
+    fn get_scale(&self) -> Vector2f {
+        self.tf.scale.into()
+    }
+    fn origin(&self) -> Vector2f {
+        self.tf.origin.into()
+    }
+    fn move_<O: Into<Vector2f>>(&mut self, offset: O) {
+        self.set_position(self.position() + offset.into());
+    }
+    fn rotate(&mut self, angle: f32) {
+        self.set_rotation(self.tf.rotation + angle);
+    }
+    fn scale<F: Into<Vector2f>>(&mut self, factors: F) {
+        self.set_scale(self.get_scale().cwise_mul(factors.into()));
+    }
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+    fn inverse_transform(&self) -> &Transform {
+        &self.inverse_transform
+    }
+}
+
+impl Tweenable for SceneNode<'_> {
+    fn tween_value(&self, property: TweenProperty) -> Option<TweenValue> {
+        Some(match property {
+            TweenProperty::Position => self.position().into(),
+            TweenProperty::Rotation => self.rotation().into(),
+            TweenProperty::Scale => self.get_scale().into(),
+            TweenProperty::Origin => self.origin().into(),
+            TweenProperty::Color => self.color.into(),
+        })
+    }
+    fn set_tween_value(&mut self, property: TweenProperty, value: TweenValue) {
+        match (property, value) {
+            (TweenProperty::Position, TweenValue::Vector(v)) => self.set_position(v),
+            (TweenProperty::Rotation, TweenValue::Float(v)) => self.set_rotation(v),
+            (TweenProperty::Scale, TweenValue::Vector(v)) => self.set_scale(v),
+            (TweenProperty::Origin, TweenValue::Vector(v)) => self.set_origin(v),
+            (TweenProperty::Color, TweenValue::Color(v)) => self.color = v,
+            _ => {}
+        }
+    }
+}
+
+/// Identifies a node of a [`SceneGraph`].
+///
+/// Ids of removed nodes are never reused, so they can't refer to another node by mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Debug)]
+struct Slot<'s> {
+    generation: u32,
+    entry: Option<Entry<'s>>,
+}
+
+#[derive(Debug)]
+struct Entry<'s> {
+    node: SceneNode<'s>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+/// A node queued for drawing
+#[derive(Debug, Clone, Copy)]
+struct DrawItem {
+    layer: i32,
+    z: i32,
+    texture: usize,
+    index: u32,
+    transform: Transform,
+    tint: Color,
+}
+
+/// A tree of [`SceneNode`]s, where children move with their parent.
+///
+/// Each node is drawn with the transforms of all its ancestors applied, and its colors
+/// modulated by their [`color`](SceneNode::color). Hiding a node hides its children too.
+///
+/// Nodes are drawn by [`layer`](SceneNode::layer), then by [`z`](SceneNode::z), then grouped by
+/// texture to minimize texture changes, and finally in tree order, parents before children.
+/// Give overlapping nodes different `z` values if their order matters.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let (hero_texture, sword_texture): (Texture, Texture) = unimplemented!();
+/// let mut scene = SceneGraph::new();
+/// let hero = scene.add(SceneNode::new(Sprite::with_texture(&hero_texture)));
+/// let sword = scene
+///     .add_child(hero, SceneNode::new(Sprite::with_texture(&sword_texture)).with_z(1))
+///     .unwrap();
+/// scene[sword].set_position((24., 8.));
+/// loop {
+///     // The sword follows the hero, and rotates around its hilt
+///     scene[hero].move_((1., 0.));
+///     scene[sword].rotate(5.);
+///     scene.draw(&mut window, &RenderStates::DEFAULT);
+///     # break;
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct SceneGraph<'s> {
+    slots: Vec<Slot<'s>>,
+    free: Vec<u32>,
+    roots: Vec<NodeId>,
+    len: usize,
+    /// Reused between draws to avoid allocations
+    queue: Vec<DrawItem>,
+}
+
+/// Building the tree
+impl<'s> SceneGraph<'s> {
+    /// Create an empty scene graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add `node` at the root of the tree.
+    pub fn add(&mut self, node: SceneNode<'s>) -> NodeId {
+        let id = self.insert(node, None);
+        self.roots.push(id);
+        id
+    }
+    /// Add `node` as the last child of `parent`, or return `None` if `parent` isn't in the
+    /// graph.
+    pub fn add_child(&mut self, parent: NodeId, node: SceneNode<'s>) -> Option<NodeId> {
+        if !self.contains(parent) {
+            return None;
+        }
+        let id = self.insert(node, Some(parent));
+        self.entry_mut(parent)?.children.push(id);
+        Some(id)
+    }
+    fn insert(&mut self, node: SceneNode<'s>, parent: Option<NodeId>) -> NodeId {
+        let entry = Entry {
+            node,
+            parent,
+            children: Vec::new(),
+        };
+        self.len += 1;
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.entry = Some(entry);
+            NodeId {
+                index,
+                generation: slot.generation,
+            }
+        } else {
+            let index = u32::try_from(self.slots.len()).expect("Too many scene nodes");
+            self.slots.push(Slot {
+                generation: 0,
+                entry: Some(entry),
+            });
+            NodeId {
+                index,
+                generation: 0,
+            }
+        }
+    }
+    /// Remove the node `id` and all its children, and return the node.
+    pub fn remove(&mut self, id: NodeId) -> Option<SceneNode<'s>> {
+        let parent = self.entry(id)?.parent;
+        self.detach(id, parent);
+        let entry = self.take(id)?;
+        let mut stack = entry.children;
+        while let Some(child) = stack.pop() {
+            if let Some(entry) = self.take(child) {
+                stack.extend(entry.children);
+            }
+        }
+        Some(entry.node)
+    }
+    fn take(&mut self, id: NodeId) -> Option<Entry<'s>> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let entry = slot.entry.take()?;
+        // Slots whose generation would overflow are retired
+        if let Some(generation) = slot.generation.checked_add(1) {
+            slot.generation = generation;
+            self.free.push(id.index);
+        }
+        self.len -= 1;
+        Some(entry)
+    }
+    /// Remove `id` from the children of `parent`, or from the roots.
+    fn detach(&mut self, id: NodeId, parent: Option<NodeId>) {
+        let siblings = match parent.and_then(|parent| self.entry_mut(parent)) {
+            Some(parent) => &mut parent.children,
+            None => &mut self.roots,
+        };
+        siblings.retain(|&sibling| sibling != id);
+    }
+    /// Move the node `id` under `parent`, or to the root if `parent` is `None`.
+    ///
+    /// The local transform of the node is kept, so it moves with its new parent.
+    /// Returns `false`, and leaves the tree unchanged, if a node isn't in the graph, or if
+    /// `parent` is `id` or one of its descendants.
+    pub fn set_parent(&mut self, id: NodeId, parent: Option<NodeId>) -> bool {
+        let Some(old_parent) = self.entry(id).map(|entry| entry.parent) else {
+            return false;
+        };
+        if let Some(parent) = parent {
+            if parent == id || !self.contains(parent) || self.ancestors(parent).any(|a| a == id) {
+                return false;
+            }
+        }
+        self.detach(id, old_parent);
+        match parent.and_then(|parent| self.entry_mut(parent)) {
+            Some(parent) => parent.children.push(id),
+            None => self.roots.push(id),
+        }
+        if let Some(entry) = self.entry_mut(id) {
+            entry.parent = parent;
+        }
+        true
+    }
+    /// Remove every node.
+    pub fn clear(&mut self) {
+        for id in std::mem::take(&mut self.roots) {
+            let _ = self.remove(id);
+        }
+    }
+}
+
+/// Queries
+impl<'s> SceneGraph<'s> {
+    fn entry(&self, id: NodeId) -> Option<&Entry<'s>> {
+        let slot = self.slots.get(id.index as usize)?;
+        (slot.generation == id.generation)
+            .then_some(slot.entry.as_ref())
+            .flatten()
+    }
+    fn entry_mut(&mut self, id: NodeId) -> Option<&mut Entry<'s>> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        (slot.generation == id.generation)
+            .then_some(slot.entry.as_mut())
+            .flatten()
+    }
+    /// The node `id`, if it is in the graph
+    #[must_use]
+    pub fn get(&self, id: NodeId) -> Option<&SceneNode<'s>> {
+        self.entry(id).map(|entry| &entry.node)
+    }
+    /// The node `id`, if it is in the graph
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut SceneNode<'s>> {
+        self.entry_mut(id).map(|entry| &mut entry.node)
+    }
+    /// Whether the node `id` is in the graph
+    #[must_use]
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.entry(id).is_some()
+    }
+    /// Number of nodes in the graph
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Whether the graph has no nodes
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The nodes at the root of the tree
+    #[must_use]
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+    /// The parent of the node `id`, or `None` for root nodes and nodes not in the graph
+    #[must_use]
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.entry(id)?.parent
+    }
+    /// The children of the node `id`, empty for nodes not in the graph
+    #[must_use]
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        self.entry(id).map_or(&[], |entry| &entry.children)
+    }
+    /// The ancestors of the node `id`, from its parent to its root
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |&id| self.parent(id))
+    }
+    /// The transform from the local coordinates of the node `id` to the coordinates of
+    /// the scene, with the transforms of its ancestors applied
+    #[must_use]
+    pub fn world_transform(&self, id: NodeId) -> Option<Transform> {
+        let mut transform = *self.get(id)?.transform();
+        for ancestor in self.ancestors(id) {
+            transform = self[ancestor].transform().combined(&transform);
+        }
+        Some(transform)
+    }
+    /// The color of the node `id`, modulated by the colors of its ancestors
+    #[must_use]
+    pub fn world_color(&self, id: NodeId) -> Option<Color> {
+        let color = self.get(id)?.color;
+        Some(
+            self.ancestors(id)
+                .fold(color, |color, ancestor| color * self[ancestor].color),
+        )
+    }
+}
+
+/// Drawing
+impl SceneGraph<'_> {
+    /// Draw the visible nodes, sorted by layer, z and texture.
+    ///
+    /// The transform of `states` is applied to the whole scene.
+    pub fn draw<RT: RenderTarget>(&mut self, target: &mut RT, states: &RenderStates) {
+        let mut queue = std::mem::take(&mut self.queue);
+        queue.clear();
+        let mut stack: Vec<_> = self
+            .roots
+            .iter()
+            .rev()
+            .map(|&id| (id, Transform::IDENTITY, Color::WHITE))
+            .collect();
+        while let Some((id, parent_transform, parent_tint)) = stack.pop() {
+            let Some(entry) = self.entry(id) else {
+                continue;
+            };
+            let node = &entry.node;
+            if !node.visible {
+                continue;
+            }
+            let transform = parent_transform.combined(&node.transform);
+            let tint = parent_tint * node.color;
+            if !matches!(node.drawable, SceneDrawable::None) {
+                queue.push(DrawItem {
+                    layer: node.layer,
+                    z: node.z,
+                    texture: node.drawable.texture_key(),
+                    index: id.index,
+                    transform,
+                    tint,
+                });
+            }
+            stack.extend(
+                entry
+                    .children
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, transform, tint)),
+            );
+        }
+        // Stable, so that tree order is kept among equal keys
+        queue.sort_by_key(|item| (item.layer, item.z, item.texture));
+        for item in &queue {
+            let Some(entry) = self.slots[item.index as usize].entry.as_mut() else {
+                continue;
+            };
+            let states = RenderStates {
+                transform: states.transform.combined(&item.transform),
+                ..*states
+            };
+            entry.node.drawable.draw(target, &states, item.tint);
+        }
+        self.queue = queue;
+    }
+}
+
+impl<'s> Index<NodeId> for SceneGraph<'s> {
+    type Output = SceneNode<'s>;
+
+    /// # Panics
+    ///
+    /// Panics if the node isn't in the graph.
+    fn index(&self, id: NodeId) -> &SceneNode<'s> {
+        self.get(id).expect("No scene node with this id")
+    }
+}
+
+impl IndexMut<NodeId> for SceneGraph<'_> {
+    /// # Panics
+    ///
+    /// Panics if the node isn't in the graph.
+    fn index_mut(&mut self, id: NodeId) -> &mut Self::Output {
+        self.get_mut(id).expect("No scene node with this id")
+    }
+}
@@ -1,10 +1,11 @@
 use {
     super::ShaderType,
     crate::{
-        IntoSfResult, SfResult,
+        SfError, SfResult,
         cpp::FBox,
         ffi::graphics as ffi,
-        graphics::{Texture, glsl},
+        graphics::{IntoUniform, Texture, Uniforms, glsl},
+        sf_call,
     },
     std::{
         ffi::CString,
@@ -43,8 +44,8 @@ use {
 /// - [`Color`] as a 4D vector (`vec4`)
 /// - [`Transform`] as matrices (`mat3` or `mat4`)
 ///
-/// Every uniform variable in a shader can be set through one of the
-/// `set_uniform_*()` or `set_uniform_array_*()` methods.
+/// Every uniform variable in a shader can be set through [`Shader::set_uniform`],
+/// or one of the type-specific `set_uniform_*()` or `set_uniform_array_*()` methods.
 /// For example, if you have a shader with the following uniforms:
 ///
 /// ```glsl
@@ -144,7 +145,7 @@ impl std::fmt::Debug for Shader<'_> {
 
 impl<'texture> Shader<'texture> {
     fn new() -> SfResult<FBox<Self>> {
-        FBox::new(unsafe { ffi::sfShader_new() }.cast()).into_sf_result()
+        sf_call(|| FBox::new(unsafe { ffi::sfShader_new() }.cast()))
     }
     /// Load the vertex, geometry or fragment shader from a file.
     ///
@@ -156,8 +157,7 @@ impl<'texture> Shader<'texture> {
     pub fn from_file(path: &str, type_: ShaderType) -> SfResult<FBox<Self>> {
         let path = CString::new(path)?;
         let mut new = Self::new()?;
-        unsafe { ffi::sfShader_loadFromFile_1(new.raw_mut(), path.as_ptr(), type_) }
-            .into_sf_result()?;
+        sf_call(|| unsafe { ffi::sfShader_loadFromFile_1(new.raw_mut(), path.as_ptr(), type_) })?;
         Ok(new)
     }
 
@@ -171,10 +171,9 @@ impl<'texture> Shader<'texture> {
         let vert = CString::new(vert)?;
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
-        unsafe {
+        sf_call(|| unsafe {
             ffi::sfShader_loadFromFile_vert_frag(new.raw_mut(), vert.as_ptr(), frag.as_ptr())
-        }
-        .into_sf_result()?;
+        })?;
         Ok(new)
     }
 
@@ -189,15 +188,14 @@ impl<'texture> Shader<'texture> {
         let geom = CString::new(geom)?;
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
-        unsafe {
+        sf_call(|| unsafe {
             ffi::sfShader_loadFromFile_all(
                 new.raw_mut(),
                 vert.as_ptr(),
                 geom.as_ptr(),
                 frag.as_ptr(),
             )
-        }
-        .into_sf_result()?;
+        })?;
         Ok(new)
     }
 
@@ -211,8 +209,9 @@ impl<'texture> Shader<'texture> {
     pub fn from_memory(contents: &str, type_: ShaderType) -> SfResult<FBox<Self>> {
         let contents = CString::new(contents)?;
         let mut new = Self::new()?;
-        unsafe { ffi::sfShader_loadFromMemory_1(new.raw_mut(), contents.as_ptr(), type_) }
-            .into_sf_result()?;
+        sf_call(|| unsafe {
+            ffi::sfShader_loadFromMemory_1(new.raw_mut(), contents.as_ptr(), type_)
+        })?;
         Ok(new)
     }
 
@@ -227,10 +226,9 @@ impl<'texture> Shader<'texture> {
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
 
-        unsafe {
+        sf_call(|| unsafe {
             ffi::sfShader_loadFromMemory_vert_frag(new.raw_mut(), vert.as_ptr(), frag.as_ptr())
-        }
-        .into_sf_result()?;
+        })?;
         Ok(new)
     }
 
@@ -245,15 +243,14 @@ impl<'texture> Shader<'texture> {
         let geom = CString::new(geom)?;
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
-        unsafe {
+        sf_call(|| unsafe {
             ffi::sfShader_loadFromMemory_all(
                 new.raw_mut(),
                 vert.as_ptr(),
                 geom.as_ptr(),
                 frag.as_ptr(),
             )
-        }
-        .into_sf_result()?;
+        })?;
         Ok(new)
     }
 
@@ -293,6 +290,28 @@ impl<'texture> Shader<'texture> {
         unsafe { ffi::sfShader_isGeometryAvailable() }
     }
 
+    /// Specify the value of a uniform of any supported type.
+    ///
+    /// The GLSL type of the uniform is determined by the type of `value`,
+    /// see [`IntoUniform`] for the supported types.
+    ///
+    /// ```no_run
+    /// # use sf2g::{graphics::*, system::*};
+    /// # let mut shader: &mut Shader = unimplemented!();
+    /// # let texture: &Texture = unimplemented!();
+    /// shader.set_uniform("offset", 2.)?;
+    /// shader.set_uniform("point", Vector2f::new(0.5, 0.8))?;
+    /// shader.set_uniform("color", Color::RED)?;
+    /// shader.set_uniform("matrix", Transform::IDENTITY)?;
+    /// shader.set_uniform("overlay", texture)?;
+    /// shader.set_uniform("current", CurrentTexture)?;
+    /// shader.set_uniform("weights", &[0.25, 0.5, 0.25])?;
+    /// # Ok::<(), sf2g::SfError>(())
+    /// ```
+    pub fn set_uniform<V: IntoUniform<'texture>>(&mut self, name: &str, value: V) -> SfResult<()> {
+        value.set_on(self, name)
+    }
+
     /// Specify value for `float` uniform.
     pub fn set_uniform_float(&mut self, name: &str, value: f32) -> SfResult<()> {
         let cstring = CString::new(name)?;
@@ -582,6 +601,25 @@ impl<'texture> Shader<'texture> {
     pub fn native_handle(&self) -> u32 {
         unsafe { ffi::sfShader_getNativeHandle(self.raw()) }
     }
+    /// Tell whether the compiled program has an active uniform named `name`.
+    ///
+    /// Uniforms that are declared but never used are usually optimized away by the driver,
+    /// so they are reported as missing too.
+    ///
+    /// Fails if the OpenGL implementation can't look up uniforms.
+    pub fn has_uniform(&self, name: &str) -> SfResult<bool> {
+        let cstring = CString::new(name)?;
+        match unsafe { ffi::sfShader_getUniformLocation(self.raw(), cstring.as_ptr()) } {
+            -2 => Err(SfError::CallFailed),
+            location => Ok(location >= 0),
+        }
+    }
+    /// Set all uniforms of `uniforms` at once.
+    ///
+    /// See [`Uniforms`].
+    pub fn set_uniforms<U: Uniforms<'texture> + ?Sized>(&mut self, uniforms: &U) -> SfResult<()> {
+        uniforms.apply_uniforms(self)
+    }
     pub(super) fn raw(&self) -> *const ffi::sfShader {
         let ptr: *const Self = self;
         ptr.cast()
@@ -597,3 +635,24 @@ impl Drop for Shader<'_> {
         unsafe { ffi::sfShader_del(self.raw_mut()) }
     }
 }
+
+#[cfg_attr(not(feature = "ci-headless"), test)]
+fn test_has_uniform() {
+    #![expect(clippy::unwrap_used)]
+    use crate::graphics::RenderWindow;
+
+    const FRAG: &str = "uniform float used;
+void main() { gl_FragColor = vec4(used); }";
+    let check = |shader: &Shader| {
+        assert!(shader.has_uniform("used").unwrap());
+        assert!(!shader.has_uniform("missing").unwrap());
+    };
+    let window =
+        RenderWindow::new((32, 32), "test", Default::default(), &Default::default()).unwrap();
+    let shader = Shader::from_memory(FRAG, ShaderType::Fragment).unwrap();
+    // With the context of the window active
+    check(&shader);
+    // And without any active context
+    drop(window);
+    check(&shader);
+}
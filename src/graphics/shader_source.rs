@@ -0,0 +1,488 @@
+use {
+    super::ShaderType,
+    crate::{SfError, cpp::FBox, graphics::Shader},
+    std::{
+        error::Error,
+        fmt::{Display, Write as _},
+        path::{Path, PathBuf},
+    },
+};
+
+/// GLSL source preprocessor, resolving `#include` directives and injecting `#define`s.
+///
+/// [`Shader::from_memory`] and friends pass the source to the driver verbatim, and GLSL has no
+/// standard way of sharing code between shaders. `ShaderSource` fills that gap:
+///
+/// - `#include "file"` is resolved relative to the including file first, then relative to the
+///   search paths. `#include <file>` is only resolved relative to the search paths.
+///   Including a file that is already being included is an error.
+/// - `#define`s added with [`define`] are injected right after the `#version` directive
+///   (or at the start, if there is none), which makes it easy to compile permutations of
+///   the same shader.
+/// - `#line` directives are emitted around each included file, so line numbers in compile errors
+///   refer to the original files. The source string number in the error is an index into
+///   [`PreprocessedSource::files`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::graphics::*;
+/// let mut source = ShaderSource::new();
+/// source.add_search_path("shaders/lib").define("MAX_LIGHTS", "8").define("USE_FOG", "");
+/// let shader = source.load_vert_frag("shaders/lit.vert", "shaders/lit.frag").unwrap();
+/// ```
+///
+/// [`define`]: ShaderSource::define
+#[derive(Debug, Clone, Default)]
+pub struct ShaderSource {
+    search_paths: Vec<PathBuf>,
+    defines: Vec<(String, String)>,
+}
+
+/// The result of preprocessing a shader with [`ShaderSource`].
+#[derive(Debug, Clone)]
+pub struct PreprocessedSource {
+    /// The preprocessed GLSL source
+    pub source: String,
+    /// Every file that contributed to the source, indexed by their `#line` source string number.
+    ///
+    /// The first entry is the main file, or an empty path if the source was preprocessed
+    /// from memory.
+    pub files: Vec<PathBuf>,
+}
+
+/// Error when preprocessing or loading a shader with [`ShaderSource`].
+#[derive(Debug)]
+pub enum ShaderSourceError {
+    /// A source file couldn't be read
+    Io {
+        /// The file that couldn't be read
+        path: PathBuf,
+        /// The underlying error
+        error: std::io::Error,
+    },
+    /// An `#include` couldn't be resolved
+    IncludeNotFound {
+        /// The name given to `#include`
+        name: String,
+        /// The file containing the `#include`
+        includer: PathBuf,
+        /// Line of the `#include` in `includer`
+        line: usize,
+    },
+    /// A file includes itself, directly or indirectly
+    IncludeCycle(
+        /// The chain of includes, starting and ending with the same file
+        Vec<PathBuf>,
+    ),
+    /// An `#include` directive is malformed
+    MalformedInclude {
+        /// The file containing the `#include`
+        includer: PathBuf,
+        /// Line of the `#include` in `includer`
+        line: usize,
+    },
+    /// SFML failed to load the preprocessed shader
+    Sfml(SfError),
+}
+
+impl Display for ShaderSourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "Failed to read {}: {error}", path.display()),
+            Self::IncludeNotFound {
+                name,
+                includer,
+                line,
+            } => write!(
+                f,
+                "{}:{line}: Can't find included file \"{name}\"",
+                includer.display()
+            ),
+            Self::IncludeCycle(chain) => {
+                write!(f, "Include cycle:")?;
+                for path in chain {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::MalformedInclude { includer, line } => {
+                write!(f, "{}:{line}: Malformed #include", includer.display())
+            }
+            Self::Sfml(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ShaderSourceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Sfml(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SfError> for ShaderSourceError {
+    fn from(e: SfError) -> Self {
+        Self::Sfml(e)
+    }
+}
+
+/// State of a single preprocessing run
+struct Run<'a> {
+    src: &'a ShaderSource,
+    out: String,
+    files: Vec<PathBuf>,
+    /// Canonical paths of the files currently being included
+    stack: Vec<PathBuf>,
+    /// GLSL before 3.30 and GLSL ES 1.00 set the line number of the line *after* `#line` to
+    /// `line + 1`
+    line_offset: usize,
+}
+
+impl Run<'_> {
+    fn line_directive(&mut self, line: usize, file: usize) {
+        let line = line - self.line_offset;
+        let _ = writeln!(self.out, "#line {line} {file}");
+    }
+    fn defines(&mut self) {
+        for (name, value) in &self.src.defines {
+            if value.is_empty() {
+                let _ = writeln!(self.out, "#define {name}");
+            } else {
+                let _ = writeln!(self.out, "#define {name} {value}");
+            }
+        }
+    }
+    fn process(
+        &mut self,
+        source: &str,
+        file: usize,
+        dir: Option<&Path>,
+    ) -> Result<(), ShaderSourceError> {
+        for (i, line) in source.lines().enumerate() {
+            let lineno = i + 1;
+            let Some(directive) = line.trim_start().strip_prefix('#') else {
+                self.out.push_str(line);
+                self.out.push('\n');
+                continue;
+            };
+            let directive = directive.trim_start();
+            if directive.starts_with("version") {
+                if file == 0 {
+                    self.out.push_str(line);
+                    self.out.push('\n');
+                    if !self.src.defines.is_empty() {
+                        self.defines();
+                        self.line_directive(lineno + 1, file);
+                    }
+                } else {
+                    // Only the main file gets to decide the version
+                    self.out.push('\n');
+                }
+                continue;
+            }
+            if let Some(rest) = directive.strip_prefix("include") {
+                self.include(rest.trim(), file, dir, lineno)?;
+                self.line_directive(lineno + 1, file);
+                continue;
+            }
+            self.out.push_str(line);
+            self.out.push('\n');
+        }
+        Ok(())
+    }
+    fn include(
+        &mut self,
+        spec: &str,
+        includer: usize,
+        dir: Option<&Path>,
+        line: usize,
+    ) -> Result<(), ShaderSourceError> {
+        let (name, relative) =
+            if let Some(name) = spec.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                (name, true)
+            } else if let Some(name) = spec.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+                (name, false)
+            } else {
+                return Err(ShaderSourceError::MalformedInclude {
+                    includer: self.files[includer].clone(),
+                    line,
+                });
+            };
+        let path = relative
+            .then_some(dir)
+            .flatten()
+            .into_iter()
+            .chain(self.src.search_paths.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| ShaderSourceError::IncludeNotFound {
+                name: name.to_owned(),
+                includer: self.files[includer].clone(),
+                line,
+            })?;
+        let canonical = path.canonicalize().map_err(|error| ShaderSourceError::Io {
+            path: path.clone(),
+            error,
+        })?;
+        if let Some(pos) = self.stack.iter().position(|p| *p == canonical) {
+            let mut chain = self.stack[pos..].to_vec();
+            chain.push(canonical);
+            return Err(ShaderSourceError::IncludeCycle(chain));
+        }
+        let source = read(&path)?;
+        let file = self.files.len();
+        self.files.push(path.clone());
+        self.stack.push(canonical);
+        self.line_directive(1, file);
+        self.process(&source, file, path.parent())?;
+        let _ = self.stack.pop();
+        Ok(())
+    }
+}
+
+fn read(path: &Path) -> Result<String, ShaderSourceError> {
+    std::fs::read_to_string(path).map_err(|error| ShaderSourceError::Io {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+/// Returns the `#version` number declared in `source`, and whether it is for the `es`
+/// profile, if any
+fn glsl_version(source: &str) -> Option<(u32, bool)> {
+    source.lines().find_map(|line| {
+        let rest = line.trim_start().strip_prefix('#')?.trim_start();
+        let mut words = rest.strip_prefix("version")?.split_whitespace();
+        let number = words.next()?.parse().ok()?;
+        Some((number, words.next() == Some("es")))
+    })
+}
+
+/// Whether `#line` sets the number of the line after it to `line + 1`, as in GLSL before
+/// 3.30 and GLSL ES 1.00, rather than to `line`
+fn line_offset(version: Option<(u32, bool)>) -> usize {
+    match version {
+        // GLSL ES 1.00 doesn't need the `es` profile
+        Some((number, true)) | Some((number @ 100, false)) => usize::from(number < 300),
+        Some((number, false)) => usize::from(number < 330),
+        None => 1,
+    }
+}
+
+impl ShaderSource {
+    /// Create a preprocessor with no search paths and no defines.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add a directory to look for included files in.
+    ///
+    /// Search paths are tried in the order they were added.
+    pub fn add_search_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.search_paths.push(path.into());
+        self
+    }
+    /// Inject `#define name value` into every preprocessed shader.
+    ///
+    /// `value` can be empty, to define a flag that can be tested with `#ifdef`.
+    /// Defining the same name again replaces its value.
+    pub fn define(&mut self, name: &str, value: &str) -> &mut Self {
+        match self.defines.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => value.clone_into(v),
+            None => self.defines.push((name.to_owned(), value.to_owned())),
+        }
+        self
+    }
+    /// Remove a define added with [`ShaderSource::define`].
+    pub fn undefine(&mut self, name: &str) -> &mut Self {
+        self.defines.retain(|(n, _)| n != name);
+        self
+    }
+    /// Preprocess the shader source file at `path`.
+    pub fn preprocess_file(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<PreprocessedSource, ShaderSourceError> {
+        let path = path.as_ref();
+        let source = read(path)?;
+        let canonical = path.canonicalize().map_err(|error| ShaderSourceError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+        self.run(&source, path.to_owned(), Some(canonical), path.parent())
+    }
+    /// Preprocess shader source code in memory.
+    ///
+    /// Included files are only looked up in the search paths.
+    pub fn preprocess(&self, source: &str) -> Result<PreprocessedSource, ShaderSourceError> {
+        self.run(source, PathBuf::new(), None, None)
+    }
+    fn run(
+        &self,
+        source: &str,
+        path: PathBuf,
+        canonical: Option<PathBuf>,
+        dir: Option<&Path>,
+    ) -> Result<PreprocessedSource, ShaderSourceError> {
+        let version = glsl_version(source);
+        let mut run = Run {
+            src: self,
+            out: String::with_capacity(source.len()),
+            files: vec![path],
+            stack: canonical.into_iter().collect(),
+            line_offset: line_offset(version),
+        };
+        // Without a `#version` directive, the defines can simply go first
+        if version.is_none() && !self.defines.is_empty() {
+            run.defines();
+            run.line_directive(1, 0);
+        }
+        run.process(source, 0, dir)?;
+        Ok(PreprocessedSource {
+            source: run.out,
+            files: run.files,
+        })
+    }
+    /// Preprocess and load the vertex, geometry or fragment shader from a file.
+    ///
+    /// See [`Shader::from_file`].
+    pub fn load<'texture>(
+        &self,
+        path: impl AsRef<Path>,
+        type_: ShaderType,
+    ) -> Result<FBox<Shader<'texture>>, ShaderSourceError> {
+        let src = self.preprocess_file(path)?;
+        Ok(Shader::from_memory(&src.source, type_)?)
+    }
+    /// Preprocess and load both the vertex and fragment shaders from files.
+    ///
+    /// See [`Shader::from_file_vert_frag`].
+    pub fn load_vert_frag<'texture>(
+        &self,
+        vert: impl AsRef<Path>,
+        frag: impl AsRef<Path>,
+    ) -> Result<FBox<Shader<'texture>>, ShaderSourceError> {
+        let vert = self.preprocess_file(vert)?;
+        let frag = self.preprocess_file(frag)?;
+        Ok(Shader::from_memory_vert_frag(&vert.source, &frag.source)?)
+    }
+    /// Preprocess and load the vertex, geometry and fragment shaders from files.
+    ///
+    /// See [`Shader::from_file_all`].
+    pub fn load_all<'texture>(
+        &self,
+        vert: impl AsRef<Path>,
+        geom: impl AsRef<Path>,
+        frag: impl AsRef<Path>,
+    ) -> Result<FBox<Shader<'texture>>, ShaderSourceError> {
+        let vert = self.preprocess_file(vert)?;
+        let geom = self.preprocess_file(geom)?;
+        let frag = self.preprocess_file(frag)?;
+        Ok(Shader::from_memory_all(
+            &vert.source,
+            &geom.source,
+            &frag.source,
+        )?)
+    }
+}
+
+/// Write `files` in a new temporary directory, and return its path
+#[cfg(test)]
+fn temp_files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    #![expect(clippy::unwrap_used)]
+    let dir = std::env::temp_dir().join(format!("sf2g-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for (path, text) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, text).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_nested_include() {
+    #![expect(clippy::unwrap_used)]
+    let dir = temp_files(
+        "nested-include",
+        &[
+            (
+                "main.frag",
+                "#version 330\n#include \"common.glsl\"\nvoid main() {}\n",
+            ),
+            ("common.glsl", "#include <math.glsl>\nfloat a;\n"),
+            ("lib/math.glsl", "float pi;\n"),
+        ],
+    );
+    let mut source = ShaderSource::new();
+    let _ = source.add_search_path(dir.join("lib"));
+    let out = source.preprocess_file(dir.join("main.frag")).unwrap();
+    assert_eq!(
+        out.source,
+        "#version 330\n#line 1 1\n#line 1 2\nfloat pi;\n#line 2 1\nfloat a;\n#line 3 0\n\
+         void main() {}\n"
+    );
+    assert_eq!(
+        out.files,
+        [
+            dir.join("main.frag"),
+            dir.join("common.glsl"),
+            dir.join("lib").join("math.glsl")
+        ]
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_include_cycle() {
+    #![expect(clippy::unwrap_used)]
+    let dir = temp_files(
+        "include-cycle",
+        &[
+            ("a.glsl", "#include \"b.glsl\"\n"),
+            ("b.glsl", "float b;\n#include \"a.glsl\"\n"),
+        ],
+    );
+    let result = ShaderSource::new().preprocess_file(dir.join("a.glsl"));
+    let Err(ShaderSourceError::IncludeCycle(chain)) = result else {
+        panic!("{result:?}");
+    };
+    let (a, b) = (
+        dir.join("a.glsl").canonicalize().unwrap(),
+        dir.join("b.glsl").canonicalize().unwrap(),
+    );
+    assert_eq!(chain, [a.clone(), b, a]);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_defines() {
+    #![expect(clippy::unwrap_used)]
+    let mut source = ShaderSource::new();
+    let _ = source.define("N", "4").define("FLAG", "");
+    let defines = "#define N 4\n#define FLAG\n";
+    for (version, line) in [
+        ("#version 120", 1),
+        ("#version 100", 1),
+        ("  # version 330 core", 2),
+        ("#version 300 es", 2),
+    ] {
+        let out = source
+            .preprocess(&format!("{version}\nuniform float x;\n"))
+            .unwrap();
+        assert_eq!(
+            out.source,
+            format!("{version}\n{defines}#line {line} 0\nuniform float x;\n")
+        );
+    }
+    // Without a version, the defines go first
+    let out = source.preprocess("uniform float x;\n").unwrap();
+    assert_eq!(
+        out.source,
+        format!("{defines}#line 0 0\nuniform float x;\n")
+    );
+}
@@ -0,0 +1,375 @@
+use crate::{
+    graphics::{FloatRect, RenderTarget, View},
+    system::{Vector2f, Vector2i, Vector2u},
+    window::Event,
+};
+
+/// How a [`SplitScreen`] divides the render target into regions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SplitLayout {
+    /// A single region covering the whole target
+    Single,
+    /// Regions side by side, from left to right
+    Columns(usize),
+    /// Regions stacked on top of each other, from top to bottom
+    Rows(usize),
+    /// A grid of regions, in row-major order
+    Grid {
+        /// Number of regions on each row
+        columns: usize,
+        /// Number of rows
+        rows: usize,
+    },
+    /// A main region covering the whole target, with a second region drawn on top of it
+    PictureInPicture {
+        /// Area of the inset region, expressed as a factor of the target size
+        inset: FloatRect,
+    },
+    /// Arbitrary regions, expressed as factors of the target size.
+    ///
+    /// Later regions are on top of earlier ones.
+    Custom(Vec<FloatRect>),
+}
+
+impl SplitLayout {
+    /// Number of regions in the layout
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Single => 1,
+            Self::Columns(n) | Self::Rows(n) => *n,
+            Self::Grid { columns, rows } => columns * rows,
+            Self::PictureInPicture { .. } => 2,
+            Self::Custom(regions) => regions.len(),
+        }
+    }
+    /// Whether the layout has no regions
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The regions of the layout, expressed as factors of the target size.
+    #[must_use]
+    pub fn regions(&self) -> Vec<FloatRect> {
+        match self {
+            Self::Single => vec![FloatRect::new(0., 0., 1., 1.)],
+            Self::Columns(n) => Self::grid(*n, 1),
+            Self::Rows(n) => Self::grid(1, *n),
+            Self::Grid { columns, rows } => Self::grid(*columns, *rows),
+            Self::PictureInPicture { inset } => vec![FloatRect::new(0., 0., 1., 1.), *inset],
+            Self::Custom(regions) => regions.clone(),
+        }
+    }
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Layouts don't have millions of regions"
+    )]
+    fn grid(columns: usize, rows: usize) -> Vec<FloatRect> {
+        let (w, h) = (1. / columns as f32, 1. / rows as f32);
+        (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |col| FloatRect::new(col as f32 * w, row as f32 * h, w, h))
+            })
+            .collect()
+    }
+    /// Whether the regions tile the target, and should be separated by the gap
+    fn is_tiled(&self) -> bool {
+        matches!(self, Self::Columns(_) | Self::Rows(_) | Self::Grid { .. })
+    }
+}
+
+/// Divides a render target into several regions, each shown through its own [`View`].
+///
+/// Meant for split-screen multiplayer, minimaps and the like.
+///
+/// Each region has a view, whose viewport is kept in sync with the region. When the target
+/// is resized, the width of each view is adapted to the aspect ratio of its region, so the
+/// world isn't distorted. Use [`View::zoom`] rather than [`View::set_size`] to change
+/// how much of the world a view shows, so the aspect ratio is kept.
+///
+/// Pointer positions (mouse or touch) can be routed to the region under them with
+/// [`pixel_to_world`](SplitScreen::pixel_to_world) or
+/// [`handle_event`](SplitScreen::handle_event).
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let players: [Vector2f; 2] = unimplemented!();
+/// let mut split = SplitScreen::new(SplitLayout::Columns(2), window.size());
+/// split.set_gap(4.);
+/// loop {
+///     while let Some(event) = window.poll_event() {
+///         if let Some((player, pos)) = split.handle_event(&event) {
+///             println!("Player {player} clicked at {pos:?}");
+///         }
+///     }
+///     window.clear(Color::BLACK);
+///     for (i, player) in players.iter().enumerate() {
+///         split.view_mut(i).set_center(*player);
+///         window.set_view(split.view(i));
+///         // Draw the world...
+///     }
+///     window.display();
+///     # break;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitScreen {
+    layout: SplitLayout,
+    gap: f32,
+    target_size: Vector2u,
+    /// Regions in target pixels
+    regions: Vec<FloatRect>,
+    views: Vec<View>,
+}
+
+impl SplitScreen {
+    /// Create a split screen for a target of size `target_size`.
+    ///
+    /// The view of each region initially shows the world at one unit per pixel.
+    #[must_use]
+    pub fn new(layout: SplitLayout, target_size: Vector2u) -> Self {
+        let mut new = Self {
+            layout,
+            gap: 0.,
+            target_size,
+            regions: Vec::new(),
+            views: Vec::new(),
+        };
+        new.fill_views();
+        new
+    }
+    /// Add views for regions that don't have one, and remove views for regions that
+    /// don't exist anymore, then update all views.
+    fn fill_views(&mut self) {
+        self.regions = self
+            .layout
+            .regions()
+            .into_iter()
+            .map(|region| self.to_pixels(region))
+            .collect();
+        let len = self.regions.len();
+        self.views.truncate(len);
+        for i in self.views.len()..len {
+            let size = self.region_pixels(i).size();
+            self.views.push(View::new(size / 2., size));
+        }
+        for i in 0..len {
+            self.fit_index(i);
+        }
+    }
+    fn fit_index(&mut self, index: usize) {
+        let mut view = self.views[index];
+        self.fit_view(index, &mut view);
+        self.views[index] = view;
+    }
+    /// The current layout
+    #[must_use]
+    pub fn layout(&self) -> &SplitLayout {
+        &self.layout
+    }
+    /// Change the layout.
+    ///
+    /// The views of regions that exist in both layouts are kept.
+    pub fn set_layout(&mut self, layout: SplitLayout) {
+        self.layout = layout;
+        self.fill_views();
+    }
+    /// The gap between regions of tiled layouts, in pixels
+    #[must_use]
+    pub const fn gap(&self) -> f32 {
+        self.gap
+    }
+    /// Set the gap between regions of tiled layouts, in pixels.
+    ///
+    /// The gap shows whatever the target was cleared with.
+    pub fn set_gap(&mut self, gap: f32) {
+        self.gap = gap;
+        self.fill_views();
+    }
+    /// The size of the target the regions are computed for
+    #[must_use]
+    pub const fn target_size(&self) -> Vector2u {
+        self.target_size
+    }
+    /// Update the regions for a target of size `target_size`.
+    ///
+    /// If the target is empty, like when the window is minimized, the views keep their
+    /// viewports until the next resize.
+    pub fn resize(&mut self, target_size: Vector2u) {
+        self.target_size = target_size;
+        self.fill_views();
+    }
+    /// Number of regions
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+    /// Whether there are no regions
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+    /// The view of the region `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn view(&self, index: usize) -> &View {
+        &self.views[index]
+    }
+    /// Mutable access to the view of the region `index`.
+    ///
+    /// The viewport of the view is managed by the split screen, and is overwritten on resize.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn view_mut(&mut self, index: usize) -> &mut View {
+        &mut self.views[index]
+    }
+    /// The views of all regions
+    #[must_use]
+    pub fn views(&self) -> &[View] {
+        &self.views
+    }
+    /// The area of the region `index`, in target pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn region_pixels(&self, index: usize) -> FloatRect {
+        self.regions[index]
+    }
+    fn to_pixels(&self, region: FloatRect) -> FloatRect {
+        let target = self.target_size.as_other::<f32>();
+        let mut left = region.left * target.x;
+        let mut top = region.top * target.y;
+        let mut right = (region.left + region.width) * target.x;
+        let mut bottom = (region.top + region.height) * target.y;
+        if self.layout.is_tiled() {
+            // Only shrink the edges shared with other regions
+            let half = self.gap / 2.;
+            if left > 0. {
+                left += half;
+            }
+            if top > 0. {
+                top += half;
+            }
+            if right < target.x {
+                right -= half;
+            }
+            if bottom < target.y {
+                bottom -= half;
+            }
+        }
+        FloatRect::new(left, top, (right - left).max(0.), (bottom - top).max(0.))
+    }
+    /// Set the viewport of `view` to the region `index`, and adapt its width to the aspect
+    /// ratio of the region.
+    ///
+    /// Useful for views managed elsewhere, like the one of a [`Camera`].
+    ///
+    /// `view` is left unchanged if the target is empty, like when the window is minimized.
+    ///
+    /// [`Camera`]: crate::graphics::Camera
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn fit_view(&self, index: usize, view: &mut View) {
+        let px = self.region_pixels(index);
+        if self.target_size.x == 0 || self.target_size.y == 0 {
+            return;
+        }
+        let target = self.target_size.as_other::<f32>();
+        view.set_viewport(FloatRect::new(
+            px.left / target.x,
+            px.top / target.y,
+            px.width / target.x,
+            px.height / target.y,
+        ));
+        if px.width > 0. && px.height > 0. {
+            let height = view.size().y;
+            view.set_size(Vector2f::new(height * px.width / px.height, height));
+        }
+    }
+    /// The region under `pixel`, if any.
+    ///
+    /// If regions overlap, the one on top is returned.
+    #[must_use]
+    pub fn region_at(&self, pixel: Vector2i) -> Option<usize> {
+        let pixel = pixel.as_other::<f32>();
+        (0..self.len()).rev().find(|&i| {
+            let r = self.region_pixels(i);
+            pixel.x >= r.left
+                && pixel.y >= r.top
+                && pixel.x < r.left + r.width
+                && pixel.y < r.top + r.height
+        })
+    }
+    /// The region under `pixel`, and the world position under it through the view of
+    /// that region.
+    #[must_use]
+    pub fn pixel_to_world(&self, pixel: Vector2i) -> Option<(usize, Vector2f)> {
+        let index = self.region_at(pixel)?;
+        Some((
+            index,
+            self.views[index].pixel_to_world(pixel, self.target_size),
+        ))
+    }
+    /// Update the regions on [`Event::Resized`], and route pointer events.
+    ///
+    /// For mouse button, mouse move, mouse wheel and touch events, returns the region under
+    /// the pointer and the world position under it, like
+    /// [`pixel_to_world`](SplitScreen::pixel_to_world).
+    pub fn handle_event(&mut self, event: &Event) -> Option<(usize, Vector2f)> {
+        match *event {
+            Event::Resized { width, height } => {
+                self.resize(Vector2u::new(width, height));
+                None
+            }
+            Event::MouseButtonPressed { x, y, .. }
+            | Event::MouseButtonReleased { x, y, .. }
+            | Event::MouseMoved { x, y }
+            | Event::MouseWheelScrolled { x, y, .. }
+            | Event::TouchBegan { x, y, .. }
+            | Event::TouchMoved { x, y, .. }
+            | Event::TouchEnded { x, y, .. } => self.pixel_to_world(Vector2i::new(x, y)),
+            _ => None,
+        }
+    }
+    /// Draw each region in turn: set the view of the region on `target`, and call `draw`
+    /// with the index of the region.
+    ///
+    /// The view of `target` is restored afterwards.
+    pub fn draw_each<T, F>(&self, target: &mut T, mut draw: F)
+    where
+        T: RenderTarget + ?Sized,
+        F: FnMut(&mut T, usize),
+    {
+        let old_view = target.view();
+        for (i, view) in self.views.iter().enumerate() {
+            target.set_view(view);
+            draw(target, i);
+        }
+        target.set_view(&old_view);
+    }
+}
+
+#[test]
+fn test_resize_empty() {
+    let mut split = SplitScreen::new(SplitLayout::Columns(2), Vector2u::new(800, 600));
+    let before = split.views().to_vec();
+    split.resize(Vector2u::new(0, 0));
+    assert_eq!(split.views(), &before[..]);
+    assert_eq!(split.pixel_to_world(Vector2i::new(0, 0)), None);
+    split.resize(Vector2u::new(800, 0));
+    assert_eq!(split.views(), &before[..]);
+    split.resize(Vector2u::new(800, 600));
+    assert_eq!(split.views(), &before[..]);
+}
@@ -0,0 +1,722 @@
+use {
+    crate::{
+        graphics::{
+            AnimationClip, FramePlacement, IntRect, PlayMode, Sprite, SpriteSheet, Transformable,
+        },
+        system::{Time, Vector2f, Vector2i, Vector2u},
+    },
+    serde::{Deserialize, Deserializer, de},
+    std::{collections::HashMap, fmt},
+};
+
+/// Sprite atlas metadata exported by Aseprite or TexturePacker.
+///
+/// Deserializes the JSON exports of both tools, in their "hash" and "array" flavors:
+///
+/// - frames, with their rotation and trimming, and their pivots (TexturePacker) or
+///   durations (Aseprite),
+/// - frame tags (Aseprite),
+/// - animations, as lists of frame names (TexturePacker),
+/// - slices, with their 9-patch centers and pivots (Aseprite).
+///
+/// Use [`to_sprite_sheet`](Self::to_sprite_sheet) to play the tags and animations with an
+/// [`AnimationPlayer`](crate::graphics::AnimationPlayer), and [`AtlasFrame::apply`] to show a
+/// single frame with a [`Sprite`], with its trimming and pivot taken into account.
+///
+/// Requires the `serde` feature. The atlas is loaded through [`Deserialize`], so the JSON
+/// parser is up to you: the example uses the [`serde_json`](https://docs.rs/serde_json)
+/// crate, which has to be added to your dependencies.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::graphics::*;
+/// let json = std::fs::read_to_string("hero.json").unwrap();
+/// let atlas: SpriteAtlas = serde_json::from_str(&json).unwrap();
+/// let texture = Texture::from_file(atlas.image.as_deref().unwrap()).unwrap();
+/// let mut sprite = Sprite::with_texture(&texture);
+/// atlas.frame("hero 0.aseprite").unwrap().apply(&mut sprite);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "raw::Atlas")]
+pub struct SpriteAtlas {
+    /// Path of the texture image, relative to the metadata file
+    pub image: Option<String>,
+    /// Size of the texture image
+    pub size: Vector2u,
+    /// The frames, in the order of the export
+    pub frames: Vec<AtlasFrame>,
+    /// Frame tags, as ranges of frames
+    pub tags: Vec<AtlasTag>,
+    /// Animations, as indices in [`frames`](Self::frames), by name
+    pub animations: HashMap<String, Vec<usize>>,
+    /// Named regions of the frames
+    pub slices: Vec<AtlasSlice>,
+}
+
+impl SpriteAtlas {
+    /// The index of the frame named `name`, if any
+    #[must_use]
+    pub fn frame_index(&self, name: &str) -> Option<usize> {
+        self.frames.iter().position(|frame| frame.name == name)
+    }
+    /// The frame named `name`, if any
+    #[must_use]
+    pub fn frame(&self, name: &str) -> Option<&AtlasFrame> {
+        self.frames.iter().find(|frame| frame.name == name)
+    }
+    /// The slice named `name`, if any
+    #[must_use]
+    pub fn slice(&self, name: &str) -> Option<&AtlasSlice> {
+        self.slices.iter().find(|slice| slice.name == name)
+    }
+    /// Build a [`SpriteSheet`] with the frames of the atlas, and a clip for each tag and
+    /// animation.
+    ///
+    /// Frames without a duration are shown for `default_duration`. Tags repeated once play
+    /// with [`PlayMode::Once`], and other tags loop.
+    ///
+    /// If some frames are trimmed, rotated or have a pivot, the sheet gets
+    /// [placements](SpriteSheet::placements) from their [`origin`](AtlasFrame::origin) and
+    /// [`rotation`](AtlasFrame::rotation), so [`AnimationPlayer::apply`] shows them like
+    /// [`AtlasFrame::apply`] does.
+    ///
+    /// [`AnimationPlayer::apply`]: crate::graphics::AnimationPlayer::apply
+    #[must_use]
+    pub fn to_sprite_sheet(&self, default_duration: Time) -> SpriteSheet {
+        let mut sheet = SpriteSheet::from_rects(self.frames.iter().map(|f| f.rect).collect());
+        if self
+            .frames
+            .iter()
+            .any(|f| f.rotated || f.origin() != Vector2f::default())
+        {
+            sheet.placements = self
+                .frames
+                .iter()
+                .map(|f| FramePlacement {
+                    origin: f.origin(),
+                    rotation: f.rotation(),
+                })
+                .collect();
+        }
+        let clip = |frames: &mut dyn Iterator<Item = usize>, mode| AnimationClip {
+            frames: frames
+                .map(|i| {
+                    let duration = self.frames.get(i).and_then(|f| f.duration);
+                    (i, duration.unwrap_or(default_duration))
+                })
+                .collect(),
+            mode,
+            events: Vec::new(),
+        };
+        for (name, frames) in &self.animations {
+            let clip = clip(&mut frames.iter().copied(), PlayMode::Loop);
+            let _ = sheet.clips.insert(name.clone(), clip);
+        }
+        for tag in &self.tags {
+            let mode = match tag.direction {
+                TagDirection::PingPong | TagDirection::PingPongReverse => PlayMode::PingPong,
+                _ if tag.repeat == Some(1) => PlayMode::Once,
+                _ => PlayMode::Loop,
+            };
+            let clip = match tag.direction {
+                TagDirection::Forward | TagDirection::PingPong => {
+                    clip(&mut (tag.from..=tag.to), mode)
+                }
+                TagDirection::Reverse | TagDirection::PingPongReverse => {
+                    clip(&mut (tag.from..=tag.to).rev(), mode)
+                }
+            };
+            let _ = sheet.clips.insert(tag.name.clone(), clip);
+        }
+        sheet
+    }
+}
+
+/// A frame of a [`SpriteAtlas`]
+///
+/// Packers usually trim the transparent borders of frames, and may rotate them by 90 degrees
+/// to pack them tighter. The frame remembers how, so it can be shown as if it was the
+/// original image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasFrame {
+    /// Name of the frame, usually the name of the original image
+    pub name: String,
+    /// Area of the texture holding the frame, as stored.
+    ///
+    /// Its width and height are swapped if the frame is rotated.
+    pub rect: IntRect,
+    /// Whether the frame is stored rotated 90 degrees clockwise in the texture
+    pub rotated: bool,
+    /// Position of the trimmed frame in the original image
+    pub offset: Vector2i,
+    /// Size of the original image, before trimming
+    pub source_size: Vector2u,
+    /// Pivot point of the frame, as a fraction of [`source_size`](Self::source_size)
+    pub pivot: Vector2f,
+    /// How long the frame is shown in animations, if the exporter says so
+    pub duration: Option<Time>,
+}
+
+impl AtlasFrame {
+    /// Whether transparent borders were trimmed from the original image
+    #[must_use]
+    #[expect(
+        clippy::cast_sign_loss,
+        reason = "Frame sizes are positive in valid metadata"
+    )]
+    pub fn is_trimmed(&self) -> bool {
+        let size = self.size();
+        self.offset != Vector2i::default()
+            || size.x as u32 != self.source_size.x
+            || size.y as u32 != self.source_size.y
+    }
+    /// Size of the trimmed frame, upright
+    #[must_use]
+    pub fn size(&self) -> Vector2i {
+        if self.rotated {
+            Vector2i::new(self.rect.height, self.rect.width)
+        } else {
+            self.rect.size()
+        }
+    }
+    /// Angle to rotate a sprite showing [`rect`](Self::rect) by, to show the frame upright
+    #[must_use]
+    pub fn rotation(&self) -> f32 {
+        if self.rotated { -90. } else { 0. }
+    }
+    /// The origin to give a sprite showing [`rect`](Self::rect), so that the pivot of the
+    /// original image ends up at the position of the sprite.
+    ///
+    /// It is in the coordinates of the stored frame, so it accounts for trimming and rotation.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Frame sizes are small enough to be exact"
+    )]
+    pub fn origin(&self) -> Vector2f {
+        let source = self.source_size.as_other::<f32>();
+        let pivot = Vector2f::new(self.pivot.x * source.x, self.pivot.y * source.y)
+            - self.offset.as_other::<f32>();
+        if self.rotated {
+            Vector2f::new(self.size().y as f32 - pivot.y, pivot.x)
+        } else {
+            pivot
+        }
+    }
+    /// Make `sprite` show the frame upright, with the pivot of the original image at the
+    /// position of the sprite.
+    ///
+    /// Sets the texture rect, origin and rotation of the sprite. To rotate the sprite further,
+    /// call [`rotate`](Transformable::rotate) afterwards.
+    pub fn apply(&self, sprite: &mut Sprite) {
+        sprite.set_texture_rect(self.rect);
+        sprite.set_origin(self.origin());
+        sprite.set_rotation(self.rotation());
+    }
+    /// Map `rect`, in the coordinates of the original image, to the texture.
+    ///
+    /// This is useful to find the [slices](AtlasSlice) of a frame in the texture. Parts of
+    /// `rect` outside of the trimmed frame are not clipped. For rotated frames, the result is
+    /// rotated like the frame.
+    #[must_use]
+    pub fn source_to_texture(&self, rect: IntRect) -> IntRect {
+        let (x, y) = (rect.left - self.offset.x, rect.top - self.offset.y);
+        if self.rotated {
+            IntRect::new(
+                self.rect.left + self.size().y - (y + rect.height),
+                self.rect.top + x,
+                rect.height,
+                rect.width,
+            )
+        } else {
+            IntRect::new(
+                self.rect.left + x,
+                self.rect.top + y,
+                rect.width,
+                rect.height,
+            )
+        }
+    }
+}
+
+/// The order in which an [`AtlasTag`] plays its frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum TagDirection {
+    /// From first to last
+    #[default]
+    Forward,
+    /// From last to first
+    Reverse,
+    /// From first to last, then back to the first
+    PingPong,
+    /// From last to first, then back to the last
+    PingPongReverse,
+}
+
+/// A named range of frames of a [`SpriteAtlas`], usually an animation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AtlasTag {
+    /// Name of the tag
+    pub name: String,
+    /// Index of the first frame
+    pub from: usize,
+    /// Index of the last frame, included
+    pub to: usize,
+    /// The order in which the frames are played
+    pub direction: TagDirection,
+    /// How many times the animation plays, or `None` to loop forever
+    pub repeat: Option<u32>,
+}
+
+/// A named region of the frames of a [`SpriteAtlas`], like a hitbox or a 9-patch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasSlice {
+    /// Name of the slice
+    pub name: String,
+    /// Where the slice is, starting from some frame
+    pub keys: Vec<SliceKey>,
+}
+
+impl AtlasSlice {
+    /// The key in effect on frame `frame`: the last key starting at or before it.
+    #[must_use]
+    pub fn key(&self, frame: usize) -> Option<&SliceKey> {
+        self.keys.iter().rev().find(|key| key.frame <= frame)
+    }
+}
+
+/// Where an [`AtlasSlice`] is, from frame [`frame`](Self::frame) onwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SliceKey {
+    /// Index of the first frame the key applies to
+    pub frame: usize,
+    /// Area of the slice, in the coordinates of the original image
+    pub bounds: IntRect,
+    /// Stretchable center of a 9-patch slice, relative to [`bounds`](Self::bounds)
+    pub center: Option<IntRect>,
+    /// Pivot point of the slice, relative to [`bounds`](Self::bounds)
+    pub pivot: Option<Vector2i>,
+}
+
+impl SliceKey {
+    /// The 9 patches of a 9-patch slice, in the coordinates of the original image, or `None`
+    /// if the slice has no center.
+    ///
+    /// Patches are in row-major order, starting at the top left corner. Corners keep their
+    /// size when the slice is stretched, edges stretch along one axis, and the center along
+    /// both. Some patches are empty if the center touches the bounds.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::*;
+    /// let key = SliceKey {
+    ///     frame: 0,
+    ///     bounds: IntRect::new(10, 10, 30, 20),
+    ///     center: Some(IntRect::new(4, 4, 22, 12)),
+    ///     pivot: None,
+    /// };
+    /// let patches = key.nine_patch().unwrap();
+    /// assert_eq!(patches[0], IntRect::new(10, 10, 4, 4));
+    /// assert_eq!(patches[4], IntRect::new(14, 14, 22, 12));
+    /// assert_eq!(patches[8], IntRect::new(36, 26, 4, 4));
+    /// ```
+    #[must_use]
+    pub fn nine_patch(&self) -> Option<[IntRect; 9]> {
+        let center = self.center?;
+        let b = self.bounds;
+        let xs = [0, center.left, center.left + center.width, b.width];
+        let ys = [0, center.top, center.top + center.height, b.height];
+        Some(std::array::from_fn(|i| {
+            let (col, row) = (i % 3, i / 3);
+            IntRect::new(
+                b.left + xs[col],
+                b.top + ys[row],
+                xs[col + 1] - xs[col],
+                ys[row + 1] - ys[row],
+            )
+        }))
+    }
+}
+
+/// The JSON layout shared by Aseprite and TexturePacker
+mod raw {
+    use super::{Deserialize, Deserializer, HashMap, de, fmt};
+
+    #[derive(Deserialize)]
+    pub(super) struct Atlas {
+        pub(super) frames: Frames,
+        #[serde(default)]
+        pub(super) meta: Meta,
+        #[serde(default)]
+        pub(super) animations: HashMap<String, Vec<String>>,
+    }
+
+    /// Frames by name in the "hash" flavor, or a list in the "array" flavor
+    pub(super) enum Frames {
+        Hash(Vec<(String, Frame)>),
+        Array(Vec<Frame>),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Frame {
+        #[serde(default)]
+        pub(super) filename: String,
+        pub(super) frame: Rect,
+        #[serde(default)]
+        pub(super) rotated: bool,
+        pub(super) sprite_source_size: Option<Rect>,
+        pub(super) source_size: Option<Size>,
+        pub(super) pivot: Option<Point<f32>>,
+        pub(super) duration: Option<i32>,
+    }
+
+    #[derive(Deserialize, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Meta {
+        pub(super) image: Option<String>,
+        pub(super) size: Option<Size>,
+        #[serde(default)]
+        pub(super) frame_tags: Vec<Tag>,
+        #[serde(default)]
+        pub(super) slices: Vec<Slice>,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct Tag {
+        pub(super) name: String,
+        pub(super) from: usize,
+        pub(super) to: usize,
+        #[serde(default)]
+        pub(super) direction: String,
+        pub(super) repeat: Option<Repeat>,
+    }
+
+    /// Aseprite writes the repeat count as a string
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum Repeat {
+        Number(u32),
+        String(String),
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct Slice {
+        pub(super) name: String,
+        #[serde(default)]
+        pub(super) keys: Vec<SliceKey>,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct SliceKey {
+        pub(super) frame: usize,
+        pub(super) bounds: Rect,
+        pub(super) center: Option<Rect>,
+        pub(super) pivot: Option<Point<i32>>,
+    }
+
+    #[derive(Deserialize, Clone, Copy)]
+    pub(super) struct Rect {
+        pub(super) x: i32,
+        pub(super) y: i32,
+        pub(super) w: i32,
+        pub(super) h: i32,
+    }
+
+    #[derive(Deserialize, Clone, Copy)]
+    pub(super) struct Size {
+        pub(super) w: u32,
+        pub(super) h: u32,
+    }
+
+    #[derive(Deserialize, Clone, Copy)]
+    pub(super) struct Point<T> {
+        pub(super) x: T,
+        pub(super) y: T,
+    }
+
+    impl<'de> Deserialize<'de> for Frames {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(FramesVisitor)
+        }
+    }
+
+    /// Keeps the frames of the "hash" flavor in order, since tags refer to them by index
+    struct FramesVisitor;
+
+    impl<'de> de::Visitor<'de> for FramesVisitor {
+        type Value = Frames;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map or a list of frames")
+        }
+        fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Frames, A::Error> {
+            let mut frames = Vec::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some(entry) = map.next_entry()? {
+                frames.push(entry);
+            }
+            Ok(Frames::Hash(frames))
+        }
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Frames, A::Error> {
+            let mut frames = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(frame) = seq.next_element()? {
+                frames.push(frame);
+            }
+            Ok(Frames::Array(frames))
+        }
+    }
+}
+
+impl From<raw::Rect> for IntRect {
+    fn from(raw::Rect { x, y, w, h }: raw::Rect) -> Self {
+        IntRect::new(x, y, w, h)
+    }
+}
+
+impl TryFrom<raw::Atlas> for SpriteAtlas {
+    type Error = String;
+
+    fn try_from(raw: raw::Atlas) -> Result<Self, String> {
+        let frames: Vec<AtlasFrame> = match raw.frames {
+            raw::Frames::Hash(frames) => frames,
+            raw::Frames::Array(frames) => frames
+                .into_iter()
+                .map(|f| (f.filename.clone(), f))
+                .collect(),
+        }
+        .into_iter()
+        .map(|(name, f)| {
+            // TexturePacker gives the upright size of rotated frames, Aseprite never rotates
+            #[expect(clippy::cast_sign_loss, reason = "Frame sizes are positive")]
+            let upright = Vector2u::new(f.frame.w as u32, f.frame.h as u32);
+            let rect = if f.rotated {
+                IntRect::new(f.frame.x, f.frame.y, f.frame.h, f.frame.w)
+            } else {
+                f.frame.into()
+            };
+            AtlasFrame {
+                name,
+                rect,
+                rotated: f.rotated,
+                offset: f
+                    .sprite_source_size
+                    .map_or_else(Vector2i::default, |r| Vector2i::new(r.x, r.y)),
+                source_size: f.source_size.map_or(upright, |s| Vector2u::new(s.w, s.h)),
+                pivot: f
+                    .pivot
+                    .map_or_else(Vector2f::default, |p| Vector2f::new(p.x, p.y)),
+                duration: f.duration.map(Time::milliseconds),
+            }
+        })
+        .collect();
+        let index = |name: &str| {
+            frames
+                .iter()
+                .position(|f| f.name == name)
+                .ok_or_else(|| format!("unknown frame `{name}` in animation"))
+        };
+        let animations = raw
+            .animations
+            .iter()
+            .map(|(name, names)| {
+                let indices = names.iter().map(|n| index(n)).collect::<Result<_, _>>()?;
+                Ok((name.clone(), indices))
+            })
+            .collect::<Result<_, String>>()?;
+        let tags = raw
+            .meta
+            .frame_tags
+            .into_iter()
+            .map(|tag| {
+                let direction = match tag.direction.as_str() {
+                    "" | "forward" => TagDirection::Forward,
+                    "reverse" => TagDirection::Reverse,
+                    "pingpong" => TagDirection::PingPong,
+                    "pingpong_reverse" => TagDirection::PingPongReverse,
+                    other => return Err(format!("unknown tag direction `{other}`")),
+                };
+                let repeat = match tag.repeat {
+                    None => None,
+                    Some(raw::Repeat::Number(n)) => Some(n),
+                    Some(raw::Repeat::String(s)) => Some(
+                        s.parse()
+                            .map_err(|_| format!("invalid tag repeat count `{s}`"))?,
+                    ),
+                };
+                Ok(AtlasTag {
+                    name: tag.name,
+                    from: tag.from,
+                    to: tag.to,
+                    direction,
+                    // Aseprite writes 0 for tags repeating forever
+                    repeat: repeat.filter(|&n| n > 0),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        let slices = raw
+            .meta
+            .slices
+            .into_iter()
+            .map(|slice| AtlasSlice {
+                name: slice.name,
+                keys: slice
+                    .keys
+                    .into_iter()
+                    .map(|key| SliceKey {
+                        frame: key.frame,
+                        bounds: key.bounds.into(),
+                        center: key.center.map(Into::into),
+                        pivot: key.pivot.map(|p| Vector2i::new(p.x, p.y)),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Ok(Self {
+            image: raw.meta.image,
+            size: raw
+                .meta
+                .size
+                .map_or_else(Vector2u::default, |s| Vector2u::new(s.w, s.h)),
+            frames,
+            tags,
+            animations,
+            slices,
+        })
+    }
+}
+
+#[test]
+fn test_texture_packer_hash() {
+    #![expect(clippy::unwrap_used)]
+    let json = r#"{
+        "frames": {
+            "walk_1.png": {
+                "frame": {"x": 2, "y": 2, "w": 20, "h": 30},
+                "rotated": false,
+                "trimmed": true,
+                "spriteSourceSize": {"x": 4, "y": 1, "w": 20, "h": 30},
+                "sourceSize": {"w": 32, "h": 32},
+                "pivot": {"x": 0.5, "y": 1}
+            },
+            "walk_0.png": {
+                "frame": {"x": 30, "y": 2, "w": 20, "h": 30},
+                "rotated": true,
+                "trimmed": true,
+                "spriteSourceSize": {"x": 6, "y": 2, "w": 20, "h": 30},
+                "sourceSize": {"w": 32, "h": 32},
+                "pivot": {"x": 0.5, "y": 1}
+            }
+        },
+        "animations": {"walk": ["walk_0.png", "walk_1.png"]},
+        "meta": {"app": "https://www.codeandweb.com/texturepacker", "image": "hero.png",
+            "size": {"w": 64, "h": 64}, "scale": "1"}
+    }"#;
+    let atlas: SpriteAtlas = serde_json::from_str(json).unwrap();
+    assert_eq!(atlas.image.as_deref(), Some("hero.png"));
+    assert_eq!(atlas.size, Vector2u::new(64, 64));
+    // The order of the file is kept
+    assert_eq!(atlas.frame_index("walk_1.png"), Some(0));
+    assert_eq!(atlas.frame_index("walk_0.png"), Some(1));
+
+    let upright = &atlas.frames[0];
+    assert_eq!(upright.rect, IntRect::new(2, 2, 20, 30));
+    assert!(upright.is_trimmed());
+    assert_eq!(upright.rotation(), 0.);
+    // The pivot is at (16, 32) in the original image, and the frame starts at (4, 1)
+    assert_eq!(upright.origin(), Vector2f::new(12., 31.));
+    assert_eq!(
+        upright.source_to_texture(IntRect::new(4, 1, 5, 6)),
+        IntRect::new(2, 2, 5, 6)
+    );
+
+    let rotated = &atlas.frames[1];
+    assert_eq!(rotated.rect, IntRect::new(30, 2, 30, 20));
+    assert_eq!(rotated.size(), Vector2i::new(20, 30));
+    assert_eq!(rotated.rotation(), -90.);
+    // The pivot is at (10, 30) in the upright trimmed frame, which is stored turned
+    // clockwise, so it ends up on the left edge of the stored frame
+    assert_eq!(rotated.origin(), Vector2f::new(0., 10.));
+    assert_eq!(
+        rotated.source_to_texture(IntRect::new(6, 2, 4, 6)),
+        IntRect::new(54, 2, 6, 4)
+    );
+
+    assert_eq!(atlas.animations["walk"], [1, 0]);
+    let sheet = atlas.to_sprite_sheet(Time::milliseconds(80));
+    assert_eq!(sheet.frames, [upright.rect, rotated.rect]);
+    assert_eq!(sheet.placements.len(), 2);
+    assert_eq!(sheet.placements[1].origin, Vector2f::new(0., 10.));
+    assert_eq!(sheet.placements[1].rotation, -90.);
+    let walk = &sheet.clips["walk"];
+    assert_eq!(walk.mode, PlayMode::Loop);
+    assert_eq!(
+        walk.frames,
+        [(1, Time::milliseconds(80)), (0, Time::milliseconds(80))]
+    );
+}
+
+#[test]
+fn test_aseprite_array() {
+    #![expect(clippy::unwrap_used)]
+    let json = r##"{
+        "frames": [
+            {"filename": "hero 0.aseprite", "frame": {"x": 0, "y": 0, "w": 16, "h": 16},
+                "rotated": false, "trimmed": false,
+                "spriteSourceSize": {"x": 0, "y": 0, "w": 16, "h": 16},
+                "sourceSize": {"w": 16, "h": 16}, "duration": 100},
+            {"filename": "hero 1.aseprite", "frame": {"x": 16, "y": 0, "w": 16, "h": 16},
+                "rotated": false, "trimmed": false,
+                "spriteSourceSize": {"x": 0, "y": 0, "w": 16, "h": 16},
+                "sourceSize": {"w": 16, "h": 16}, "duration": 150},
+            {"filename": "hero 2.aseprite", "frame": {"x": 32, "y": 0, "w": 16, "h": 16},
+                "rotated": false, "trimmed": false,
+                "spriteSourceSize": {"x": 0, "y": 0, "w": 16, "h": 16},
+                "sourceSize": {"w": 16, "h": 16}, "duration": 100}
+        ],
+        "meta": {
+            "app": "https://www.aseprite.org/", "image": "hero.png",
+            "size": {"w": 48, "h": 16},
+            "frameTags": [
+                {"name": "idle", "from": 0, "to": 1, "direction": "forward", "repeat": "1"},
+                {"name": "run", "from": 0, "to": 2, "direction": "pingpong"},
+                {"name": "back", "from": 1, "to": 2, "direction": "reverse", "repeat": "0"}
+            ],
+            "slices": [{"name": "panel", "color": "#0000ffff", "keys": [{
+                "frame": 0,
+                "bounds": {"x": 2, "y": 2, "w": 12, "h": 12},
+                "center": {"x": 3, "y": 3, "w": 6, "h": 6},
+                "pivot": {"x": 6, "y": 12}
+            }]}]
+        }
+    }"##;
+    let atlas: SpriteAtlas = serde_json::from_str(json).unwrap();
+    assert_eq!(atlas.frames.len(), 3);
+    let second = &atlas.frames[1];
+    assert_eq!(second.name, "hero 1.aseprite");
+    assert_eq!(second.rect, IntRect::new(16, 0, 16, 16));
+    assert!(!second.is_trimmed());
+    assert_eq!(second.origin(), Vector2f::default());
+    assert_eq!(second.duration, Some(Time::milliseconds(150)));
+
+    let tag = |name: &str| atlas.tags.iter().find(|tag| tag.name == name).unwrap();
+    assert_eq!(tag("idle").repeat, Some(1));
+    assert_eq!(tag("run").direction, TagDirection::PingPong);
+    assert_eq!(tag("back").direction, TagDirection::Reverse);
+    assert_eq!(tag("back").repeat, None);
+
+    let key = atlas.slice("panel").unwrap().key(2).unwrap();
+    assert_eq!(key.bounds, IntRect::new(2, 2, 12, 12));
+    assert_eq!(key.center, Some(IntRect::new(3, 3, 6, 6)));
+    assert_eq!(key.pivot, Some(Vector2i::new(6, 12)));
+
+    let sheet = atlas.to_sprite_sheet(Time::milliseconds(80));
+    assert!(sheet.placements.is_empty());
+    let ms = Time::milliseconds;
+    assert_eq!(sheet.clips["idle"].mode, PlayMode::Once);
+    assert_eq!(sheet.clips["idle"].frames, [(0, ms(100)), (1, ms(150))]);
+    assert_eq!(sheet.clips["run"].mode, PlayMode::PingPong);
+    assert_eq!(sheet.clips["back"].mode, PlayMode::Loop);
+    assert_eq!(sheet.clips["back"].frames, [(2, ms(100)), (1, ms(150))]);
+}
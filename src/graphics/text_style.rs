@@ -4,6 +4,7 @@ bitflags::bitflags! {
     /// Available text styles.
     #[repr(C)]
     #[derive(Debug, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct TextStyle: u32 {
         /// Regular characters, no style.
         const REGULAR = 0;
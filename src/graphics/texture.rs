@@ -1,8 +1,9 @@
 use crate::{
-    IntoSfResult, SfError, SfResult,
+    SfError, SfResult,
     cpp::FBox,
     ffi::graphics::{self as ffi, sfTexture_create},
     graphics::RenderWindow,
+    sf_call,
     system::Vector2u,
     window::Window,
 };
@@ -37,7 +38,7 @@ decl_opaque! {
 /// And they cannot be accessed individually.
 /// Therefore, if you need to read the texture's pixels (like for pixel-perfect collisions),
 /// it is recommended to store the collision information separately,
-/// for example in an array of booleans.
+/// for example in a [`CollisionMask`].
 ///
 /// Like [`Image`], `Texture` can handle a unique internal representation of pixels,
 /// which is RGBA 32 bits.
@@ -45,6 +46,7 @@ decl_opaque! {
 /// 8 bits red, green, blue and alpha channels – just like a [`Color`].
 ///
 /// [`Color`]: crate::graphics::Color
+/// [`CollisionMask`]: crate::graphics::CollisionMask
 pub Texture;
 }
 
@@ -57,11 +59,11 @@ impl Texture {
     /// Returns whether creation was successful.
     #[must_use = "Check if texture was created successfully"]
     pub fn create(&mut self, width: u32, height: u32) -> SfResult<()> {
-        unsafe { sfTexture_create(self, width, height) }.into_sf_result()
+        sf_call(|| unsafe { sfTexture_create(self, width, height) })
     }
     /// Creates a new `Texture`
     pub fn new() -> SfResult<FBox<Texture>> {
-        FBox::new(unsafe { ffi::sfTexture_new() }).into_sf_result()
+        sf_call(|| FBox::new(unsafe { ffi::sfTexture_new() }))
     }
 
     /// Load texture from memory
@@ -293,7 +295,7 @@ impl Texture {
     /// level image is modified, at which point this function will have to be called again to
     /// regenerate it.
     pub fn generate_mipmap(&mut self) -> SfResult<()> {
-        unsafe { ffi::sfTexture_generateMipmap(self) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfTexture_generateMipmap(self) })
     }
 }
 
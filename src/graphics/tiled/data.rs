@@ -0,0 +1,284 @@
+//! Decoding helpers shared by the TMX and JSON parsers
+
+use {
+    super::{Error, Layer, Tile, TileLayer, Tileset},
+    crate::{
+        graphics::Color,
+        system::{Vector2f, Vector2i, Vector2u},
+    },
+    std::path::Path,
+};
+
+/// Parse a Tiled color, `#AARRGGBB` or `#RRGGBB`, with the `#` being optional.
+pub(super) fn parse_color(s: &str) -> Result<Color, Error> {
+    let hex = s.trim().trim_start_matches('#');
+    let value = u32::from_str_radix(hex, 16)
+        .ok()
+        .filter(|_| hex.is_ascii())
+        .ok_or_else(|| Error::Invalid(format!("invalid color \"{s}\"")))?;
+    let [a, r, g, b] = value.to_be_bytes();
+    match hex.len() {
+        6 => Ok(Color::rgb(r, g, b)),
+        8 => Ok(Color::rgba(r, g, b, a)),
+        _ => Err(Error::Invalid(format!("invalid color \"{s}\""))),
+    }
+}
+
+/// Parse an optional color, where an empty string means unset.
+pub(super) fn parse_optional_color(s: &str) -> Result<Option<Color>, Error> {
+    if s.is_empty() {
+        Ok(None)
+    } else {
+        parse_color(s).map(Some)
+    }
+}
+
+/// Decode tile layer data, encoded as `"csv"` or `"base64"`, with an optional compression.
+pub(super) fn decode_tiles(
+    text: &str,
+    encoding: &str,
+    compression: &str,
+) -> Result<Vec<u32>, Error> {
+    match encoding {
+        "csv" => text
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse()
+                    .map_err(|_| Error::Invalid(format!("invalid tile id \"{s}\"")))
+            })
+            .collect(),
+        "base64" => {
+            let bytes = decode_base64(text)?;
+            let bytes = match compression {
+                "" => bytes,
+                "zlib" => miniz_oxide::inflate::decompress_to_vec_zlib(&bytes)
+                    .map_err(|err| Error::Invalid(format!("invalid zlib data: {err}")))?,
+                "gzip" => miniz_oxide::inflate::decompress_to_vec(gzip_body(&bytes)?)
+                    .map_err(|err| Error::Invalid(format!("invalid gzip data: {err}")))?,
+                other => {
+                    return Err(Error::Invalid(format!(
+                        "unsupported compression \"{other}\""
+                    )));
+                }
+            };
+            if bytes.len() % 4 != 0 {
+                return Err(Error::Invalid("truncated tile data".into()));
+            }
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect())
+        }
+        other => Err(Error::Invalid(format!("unsupported encoding \"{other}\""))),
+    }
+}
+
+/// Decode standard base64, ignoring whitespace.
+fn decode_base64(text: &str) -> Result<Vec<u8>, Error> {
+    let sextet = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        if c == b'=' {
+            break;
+        }
+        let value = sextet(c).ok_or_else(|| Error::Invalid("invalid base64 data".into()))?;
+        acc = (acc << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            #[expect(clippy::cast_possible_truncation, reason = "Keeping the low byte")]
+            out.push((acc >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
+
+/// The deflate stream of a gzip member, without its header.
+fn gzip_body(bytes: &[u8]) -> Result<&[u8], Error> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+    let invalid = || Error::Invalid("invalid gzip header".into());
+    if bytes.len() < 10 || bytes[..3] != [0x1f, 0x8b, 8] {
+        return Err(invalid());
+    }
+    let flags = bytes[3];
+    let mut rest = &bytes[10..];
+    if flags & FEXTRA != 0 {
+        let len = usize::from(u16::from_le_bytes([
+            *rest.first().ok_or_else(invalid)?,
+            *rest.get(1).ok_or_else(invalid)?,
+        ]));
+        rest = rest.get(2 + len..).ok_or_else(invalid)?;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = rest.iter().position(|&b| b == 0).ok_or_else(invalid)?;
+            rest = &rest[end + 1..];
+        }
+    }
+    if flags & FHCRC != 0 {
+        rest = rest.get(2..).ok_or_else(invalid)?;
+    }
+    Ok(rest)
+}
+
+/// Build a tile layer from decoded cells.
+pub(super) fn tile_layer(
+    origin: Vector2i,
+    size: Vector2u,
+    raw: &[u32],
+) -> Result<TileLayer, Error> {
+    if raw.len() != size.x as usize * size.y as usize {
+        return Err(Error::Invalid(format!(
+            "expected {} tiles, found {}",
+            size.x as usize * size.y as usize,
+            raw.len()
+        )));
+    }
+    Ok(TileLayer {
+        origin,
+        size,
+        tiles: raw.iter().map(|&raw| Tile::from_raw(raw)).collect(),
+    })
+}
+
+/// Build a chunk of a layer of an infinite map from decoded cells.
+pub(super) fn chunk(origin: Vector2i, size: Vector2u, raw: &[u32]) -> Result<TileLayer, Error> {
+    if size.x == 0 || size.y == 0 {
+        return Err(Error::Invalid(format!(
+            "empty chunk at {}, {}",
+            origin.x, origin.y
+        )));
+    }
+    tile_layer(origin, size, raw)
+}
+
+/// The most tiles the merged chunks of a layer can cover, so that chunks far apart from each
+/// other are rejected instead of filling the memory
+const MAX_MERGED_TILES: i64 = 1 << 24;
+
+/// Merge the chunks of a layer of an infinite map into a single grid covering all of them.
+#[expect(
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation,
+    reason = "Chunk positions are offset to be positive, and the size is limited"
+)]
+pub(super) fn merge_chunks(chunks: &[TileLayer]) -> Result<TileLayer, Error> {
+    let Some(first) = chunks.first() else {
+        return Ok(TileLayer::default());
+    };
+    // In i64, so that no chunk position or size can overflow
+    let (mut min, mut max) = (
+        (i64::from(first.origin.x), i64::from(first.origin.y)),
+        (i64::from(first.origin.x), i64::from(first.origin.y)),
+    );
+    for chunk in chunks {
+        let (x, y) = (i64::from(chunk.origin.x), i64::from(chunk.origin.y));
+        min = (min.0.min(x), min.1.min(y));
+        max = (
+            max.0.max(x + i64::from(chunk.size.x)),
+            max.1.max(y + i64::from(chunk.size.y)),
+        );
+    }
+    let (width, height) = (max.0 - min.0, max.1 - min.1);
+    if width
+        .checked_mul(height)
+        .is_none_or(|tiles| tiles > MAX_MERGED_TILES)
+    {
+        return Err(Error::Invalid(format!(
+            "chunks spread over {width} by {height} tiles"
+        )));
+    }
+    let size = Vector2u::new(width as u32, height as u32);
+    let mut tiles = vec![Tile::default(); size.x as usize * size.y as usize];
+    for chunk in chunks {
+        let (ox, oy) = (
+            (i64::from(chunk.origin.x) - min.0) as usize,
+            (i64::from(chunk.origin.y) - min.1) as usize,
+        );
+        for (i, row) in chunk.tiles.chunks(chunk.size.x as usize).enumerate() {
+            let start = (oy + i) * size.x as usize + ox;
+            tiles[start..start + row.len()].copy_from_slice(row);
+        }
+    }
+    Ok(TileLayer {
+        // The minimum is the origin of one of the chunks
+        origin: Vector2i::new(min.0 as i32, min.1 as i32),
+        size,
+        tiles,
+    })
+}
+
+/// The offset, opacity and visibility of the group layers containing a layer
+#[derive(Clone, Copy)]
+pub(super) struct Group {
+    pub(super) offset: Vector2f,
+    pub(super) opacity: f32,
+    pub(super) visible: bool,
+}
+
+impl Group {
+    pub(super) const ROOT: Self = Self {
+        offset: Vector2f::new(0., 0.),
+        opacity: 1.,
+        visible: true,
+    };
+    /// Apply the group to `layer`, and return the group for the children of `layer`.
+    pub(super) fn apply(self, layer: &mut Layer) -> Self {
+        layer.offset += self.offset;
+        layer.opacity *= self.opacity;
+        layer.visible &= self.visible;
+        Self {
+            offset: layer.offset,
+            opacity: layer.opacity,
+            visible: layer.visible,
+        }
+    }
+}
+
+/// Load an external tileset, in TSX format if its extension is `.tsx`, and in JSON format
+/// otherwise.
+pub(super) fn load_tileset(first_gid: u32, dir: &Path, source: &str) -> Result<Tileset, Error> {
+    let path = dir.join(source);
+    let text = std::fs::read_to_string(&path)?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut tileset = if path.extension().is_some_and(|ext| ext == "tsx") {
+        super::tmx::parse_tileset_file(&text, dir)?
+    } else {
+        super::json::parse_tileset_file(&text, dir)?
+    };
+    tileset.first_gid = first_gid;
+    Ok(tileset)
+}
+
+#[test]
+fn test_merge_far_chunks() {
+    #![expect(clippy::unwrap_used)]
+    let chunk = |x: i32, y: i32| chunk(Vector2i::new(x, y), Vector2u::new(2, 1), &[1, 2]).unwrap();
+    let merged = merge_chunks(&[chunk(-2, 0), chunk(2, 1)]).unwrap();
+    assert_eq!(merged.origin, Vector2i::new(-2, 0));
+    assert_eq!(merged.size, Vector2u::new(6, 2));
+    let gids: Vec<u32> = merged.tiles.iter().map(|tile| tile.gid).collect();
+    assert_eq!(gids, [1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+    assert!(matches!(
+        merge_chunks(&[chunk(-100_000, 0), chunk(100_000, 100_000)]),
+        Err(Error::Invalid(_))
+    ));
+    assert!(matches!(
+        merge_chunks(&[chunk(i32::MIN, i32::MIN), chunk(i32::MAX, i32::MAX)]),
+        Err(Error::Invalid(_))
+    ));
+}
@@ -0,0 +1,305 @@
+//! The JSON format
+
+use {
+    super::{
+        Error, ImageLayer, Layer, LayerKind, Map, Object, ObjectLayer, ObjectShape, Orientation,
+        Properties, PropertyValue, Tile, TileData, TileLayer, Tileset,
+        data::{self, Group},
+    },
+    crate::system::{Time, Vector2f, Vector2i, Vector2u},
+    serde_json::Value,
+    std::path::Path,
+};
+
+pub(super) fn parse_map(json: &str, dir: &Path) -> Result<Map, Error> {
+    let root: Value = serde_json::from_str(json).map_err(|err| Error::Syntax(err.to_string()))?;
+    if root["type"] != "map" && !root["layers"].is_array() {
+        return Err(Error::Invalid("not a map".into()));
+    }
+    let mut map = Map {
+        orientation: match str(&root, "orientation").unwrap_or("orthogonal") {
+            "orthogonal" => Orientation::Orthogonal,
+            "isometric" => Orientation::Isometric,
+            other => {
+                return Err(Error::Invalid(format!(
+                    "unsupported orientation \"{other}\""
+                )));
+            }
+        },
+        size: Vector2u::new(uint(&root, "width", 0)?, uint(&root, "height", 0)?),
+        tile_size: Vector2u::new(uint(&root, "tilewidth", 0)?, uint(&root, "tileheight", 0)?),
+        background_color: str(&root, "backgroundcolor")
+            .map(data::parse_color)
+            .transpose()?,
+        properties: properties(&root)?,
+        ..Map::default()
+    };
+    for tileset in array(&root, "tilesets") {
+        let first_gid = uint(tileset, "firstgid", 1)?;
+        map.tilesets.push(match str(tileset, "source") {
+            Some(source) => data::load_tileset(first_gid, dir, source)?,
+            None => parse_tileset(tileset, first_gid, dir)?,
+        });
+    }
+    map.tilesets.sort_by_key(|tileset| tileset.first_gid);
+    for layer in array(&root, "layers") {
+        layers(layer, dir, Group::ROOT, &mut map.layers)?;
+    }
+    Ok(map)
+}
+
+pub(super) fn parse_tileset_file(json: &str, dir: &Path) -> Result<Tileset, Error> {
+    let root: Value = serde_json::from_str(json).map_err(|err| Error::Syntax(err.to_string()))?;
+    parse_tileset(&root, 0, dir)
+}
+
+fn parse_tileset(value: &Value, first_gid: u32, dir: &Path) -> Result<Tileset, Error> {
+    let mut tileset = Tileset {
+        first_gid,
+        name: str(value, "name").unwrap_or_default().to_owned(),
+        tile_size: Vector2u::new(uint(value, "tilewidth", 0)?, uint(value, "tileheight", 0)?),
+        spacing: uint(value, "spacing", 0)?,
+        margin: uint(value, "margin", 0)?,
+        tile_count: uint(value, "tilecount", 0)?,
+        columns: uint(value, "columns", 0)?,
+        image: str(value, "image").map(|image| dir.join(image)),
+        image_size: Vector2u::new(
+            uint(value, "imagewidth", 0)?,
+            uint(value, "imageheight", 0)?,
+        ),
+        offset: Vector2i::new(
+            int(&value["tileoffset"], "x", 0)?,
+            int(&value["tileoffset"], "y", 0)?,
+        ),
+        properties: properties(value)?,
+        ..Tileset::default()
+    };
+    for tile in array(value, "tiles") {
+        let data = TileData {
+            class: class(tile),
+            image: str(tile, "image").map(|image| dir.join(image)),
+            image_size: Vector2u::new(uint(tile, "imagewidth", 0)?, uint(tile, "imageheight", 0)?),
+            animation: array(tile, "animation")
+                .map(|frame| {
+                    Ok((
+                        uint(frame, "tileid", 0)?,
+                        Time::milliseconds(int(frame, "duration", 0)?),
+                    ))
+                })
+                .collect::<Result<_, Error>>()?,
+            properties: properties(tile)?,
+        };
+        let _ = tileset.tiles.insert(uint(tile, "id", 0)?, data);
+    }
+    Ok(tileset)
+}
+
+/// Parse the layer `value`, and push it to `out`. Group layers push their children instead.
+fn layers(value: &Value, dir: &Path, group: Group, out: &mut Vec<Layer>) -> Result<(), Error> {
+    let kind = match str(value, "type").unwrap_or_default() {
+        "tilelayer" => LayerKind::Tiles(tile_layer(value)?),
+        "objectgroup" => LayerKind::Objects(object_layer(value)?),
+        "imagelayer" => LayerKind::Image(ImageLayer {
+            image: str(value, "image")
+                .filter(|image| !image.is_empty())
+                .map(|image| dir.join(image)),
+            repeat_x: bool(value, "repeatx", false),
+            repeat_y: bool(value, "repeaty", false),
+        }),
+        // Used as a placeholder until the children are flattened
+        "group" => LayerKind::Image(ImageLayer::default()),
+        _ => return Ok(()),
+    };
+    let mut layer = Layer {
+        id: uint(value, "id", 0)?,
+        name: str(value, "name").unwrap_or_default().to_owned(),
+        visible: bool(value, "visible", true),
+        opacity: float(value, "opacity", 1.),
+        offset: Vector2f::new(float(value, "offsetx", 0.), float(value, "offsety", 0.)),
+        properties: properties(value)?,
+        kind,
+    };
+    let children = group.apply(&mut layer);
+    if value["type"] == "group" {
+        for child in array(value, "layers") {
+            layers(child, dir, children, out)?;
+        }
+    } else {
+        out.push(layer);
+    }
+    Ok(())
+}
+
+fn tile_layer(value: &Value) -> Result<TileLayer, Error> {
+    let encoding = str(value, "encoding").unwrap_or("csv");
+    let compression = str(value, "compression").unwrap_or_default();
+    let decode = |data: &Value| -> Result<Vec<u32>, Error> {
+        match data {
+            Value::Null => Ok(Vec::new()),
+            Value::String(text) => data::decode_tiles(text, encoding, compression),
+            Value::Array(ids) => ids
+                .iter()
+                .map(|id| {
+                    id.as_u64()
+                        .and_then(|id| u32::try_from(id).ok())
+                        .ok_or_else(|| Error::Invalid(format!("invalid tile id {id}")))
+                })
+                .collect(),
+            other => Err(Error::Invalid(format!("invalid tile data {other}"))),
+        }
+    };
+    let origin = |value: &Value| -> Result<Vector2i, Error> {
+        Ok(Vector2i::new(int(value, "x", 0)?, int(value, "y", 0)?))
+    };
+    let size = |value: &Value| -> Result<Vector2u, Error> {
+        Ok(Vector2u::new(
+            uint(value, "width", 0)?,
+            uint(value, "height", 0)?,
+        ))
+    };
+    if value["chunks"].is_array() {
+        let chunks = array(value, "chunks")
+            .map(|chunk| data::chunk(origin(chunk)?, size(chunk)?, &decode(&chunk["data"])?))
+            .collect::<Result<Vec<_>, _>>()?;
+        data::merge_chunks(&chunks)
+    } else {
+        data::tile_layer(origin(value)?, size(value)?, &decode(&value["data"])?)
+    }
+}
+
+fn object_layer(value: &Value) -> Result<ObjectLayer, Error> {
+    Ok(ObjectLayer {
+        color: str(value, "color").map(data::parse_color).transpose()?,
+        objects: array(value, "objects")
+            .map(object)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn object(value: &Value) -> Result<Object, Error> {
+    let shape = if bool(value, "ellipse", false) {
+        ObjectShape::Ellipse
+    } else if bool(value, "point", false) {
+        ObjectShape::Point
+    } else if value["polygon"].is_array() {
+        ObjectShape::Polygon(points(&value["polygon"]))
+    } else if value["polyline"].is_array() {
+        ObjectShape::Polyline(points(&value["polyline"]))
+    } else if let Some(text) = str(&value["text"], "text") {
+        ObjectShape::Text(text.to_owned())
+    } else {
+        ObjectShape::Rect
+    };
+    Ok(Object {
+        id: uint(value, "id", 0)?,
+        name: str(value, "name").unwrap_or_default().to_owned(),
+        class: class(value),
+        position: Vector2f::new(float(value, "x", 0.), float(value, "y", 0.)),
+        size: Vector2f::new(float(value, "width", 0.), float(value, "height", 0.)),
+        rotation: float(value, "rotation", 0.),
+        visible: bool(value, "visible", true),
+        tile: value["gid"]
+            .is_u64()
+            .then(|| uint(value, "gid", 0).map(Tile::from_raw))
+            .transpose()?,
+        shape,
+        properties: properties(value)?,
+    })
+}
+
+fn points(value: &Value) -> Vec<Vector2f> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|point| Vector2f::new(float(point, "x", 0.), float(point, "y", 0.)))
+        .collect()
+}
+
+/// The `properties` array of `value`
+fn properties(value: &Value) -> Result<Properties, Error> {
+    array(value, "properties")
+        .map(|prop| {
+            let name = str(prop, "name").unwrap_or_default().to_owned();
+            let raw = &prop["value"];
+            let value = match str(prop, "type").unwrap_or("string") {
+                "color" => PropertyValue::Color(data::parse_optional_color(
+                    raw.as_str().unwrap_or_default(),
+                )?),
+                "file" => PropertyValue::File(raw.as_str().unwrap_or_default().to_owned()),
+                "object" => PropertyValue::Object(uint(prop, "value", 0)?),
+                "float" => PropertyValue::Float(raw.as_f64().unwrap_or_default()),
+                _ => untyped(raw),
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Convert a property value from its JSON type. Members of class properties are stored
+/// without their Tiled type, so colors, files and objects are read as strings and integers.
+fn untyped(value: &Value) -> PropertyValue {
+    match value {
+        Value::Bool(b) => PropertyValue::Bool(*b),
+        Value::Number(n) => n.as_i64().map_or_else(
+            || PropertyValue::Float(n.as_f64().unwrap_or_default()),
+            PropertyValue::Int,
+        ),
+        Value::Object(members) => PropertyValue::Class(
+            members
+                .iter()
+                .map(|(name, value)| (name.clone(), untyped(value)))
+                .collect(),
+        ),
+        Value::String(s) => PropertyValue::String(s.clone()),
+        Value::Null | Value::Array(_) => PropertyValue::String(String::new()),
+    }
+}
+
+/// The class of a tile or object, named `type` before Tiled 1.9
+fn class(value: &Value) -> String {
+    str(value, "class")
+        .or_else(|| str(value, "type"))
+        .unwrap_or_default()
+        .to_owned()
+}
+
+fn array<'v>(value: &'v Value, key: &str) -> impl Iterator<Item = &'v Value> {
+    value[key].as_array().into_iter().flatten()
+}
+
+fn str<'v>(value: &'v Value, key: &str) -> Option<&'v str> {
+    value[key].as_str()
+}
+
+fn bool(value: &Value, key: &str, default: bool) -> bool {
+    value[key].as_bool().unwrap_or(default)
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "Tiled stores single precision values"
+)]
+fn float(value: &Value, key: &str, default: f32) -> f32 {
+    value[key].as_f64().map_or(default, |v| v as f32)
+}
+
+fn uint(value: &Value, key: &str, default: u32) -> Result<u32, Error> {
+    match &value[key] {
+        Value::Null => Ok(default),
+        v => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| Error::Invalid(format!("invalid {key} {v}"))),
+    }
+}
+
+fn int(value: &Value, key: &str, default: i32) -> Result<i32, Error> {
+    match &value[key] {
+        Value::Null => Ok(default),
+        v => v
+            .as_i64()
+            .and_then(|v| i32::try_from(v).ok())
+            .ok_or_else(|| Error::Invalid(format!("invalid {key} {v}"))),
+    }
+}
@@ -0,0 +1,494 @@
+//! Loading and drawing maps made with the [Tiled](https://www.mapeditor.org) editor.
+//!
+//! Requires the `tiled` feature.
+//!
+//! [`Map::load`] reads maps in the TMX (XML) and JSON formats, with their external tilesets
+//! (TSX or JSON). Supported features:
+//!
+//! - Orthogonal and isometric orientations, fixed size and infinite maps
+//! - Tile layers, with flipped tiles, in CSV or base64 encoding, uncompressed or
+//!   compressed with zlib or gzip
+//! - Object layers, with rectangles, ellipses, points, polygons, polylines and tile objects
+//! - Image layers
+//! - Group layers, which are flattened: their children are added to [`Map::layers`] with the
+//!   offset, opacity and visibility of the group applied
+//! - Tileset tile animations
+//! - Custom properties, on the map, tilesets, tiles, layers and objects
+//!
+//! [`MapRenderer`] draws the tile and image layers, with the tileset and image textures
+//! loaded by [`MapTextures`].
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! # use sf2g::{graphics::{*, tiled::*}, system::*};
+//! # let mut window: RenderWindow = unimplemented!();
+//! # let mut clock = Clock::start().unwrap();
+//! let map = Map::load("level1.tmx").unwrap();
+//! let textures = MapTextures::load(&map).unwrap();
+//! let mut renderer = MapRenderer::new(&map).unwrap();
+//! for layer in &map.layers {
+//!     if let LayerKind::Objects(objects) = &layer.kind {
+//!         for object in &objects.objects {
+//!             if object.class == "spawn" {
+//!                 // Spawn something at object.position...
+//!             }
+//!         }
+//!     }
+//! }
+//! loop {
+//!     renderer.update(clock.restart()).unwrap();
+//!     renderer.draw(&mut window, &textures, &RenderStates::DEFAULT);
+//!     # break;
+//! }
+//! ```
+
+pub use self::render::{MapRenderer, MapTextures};
+use {
+    crate::{
+        SfError,
+        graphics::{Color, IntRect},
+        system::{Time, Vector2f, Vector2i, Vector2u},
+    },
+    std::{
+        collections::HashMap,
+        fmt, io,
+        path::{Path, PathBuf},
+    },
+};
+
+mod data;
+mod json;
+mod render;
+mod tmx;
+
+/// The projection of a [`Map`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    /// Tiles are laid out on a regular grid
+    #[default]
+    Orthogonal,
+    /// Tiles are diamonds, with the X axis of the map going down-right, and the Y axis
+    /// going down-left
+    Isometric,
+}
+
+/// A map made with Tiled
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Map {
+    /// The projection of the map
+    pub orientation: Orientation,
+    /// Size of the map, in tiles. Infinite maps report the size of their initial area.
+    pub size: Vector2u,
+    /// Size of a tile of the grid, in pixels
+    pub tile_size: Vector2u,
+    /// Background color, if set
+    pub background_color: Option<Color>,
+    /// Tilesets, sorted by [`first_gid`](Tileset::first_gid)
+    pub tilesets: Vec<Tileset>,
+    /// Layers, from bottom to top
+    pub layers: Vec<Layer>,
+    /// Custom properties
+    pub properties: Properties,
+}
+
+impl Map {
+    /// Load a map from a file, in TMX format if its extension is `.tmx`, and in JSON format
+    /// otherwise.
+    ///
+    /// External tilesets and images are looked up relative to the map file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        if path.extension().is_some_and(|ext| ext == "tmx") {
+            Self::from_tmx(&text, dir)
+        } else {
+            Self::from_json(&text, dir)
+        }
+    }
+    /// Parse a map in TMX format.
+    ///
+    /// External tilesets and images are looked up relative to `dir`.
+    pub fn from_tmx(xml: &str, dir: &Path) -> Result<Self, Error> {
+        tmx::parse_map(xml, dir)
+    }
+    /// Parse a map in JSON format.
+    ///
+    /// External tilesets and images are looked up relative to `dir`.
+    pub fn from_json(json: &str, dir: &Path) -> Result<Self, Error> {
+        json::parse_map(json, dir)
+    }
+    /// The tileset containing the global tile id `gid`, and the id of the tile in it
+    #[must_use]
+    pub fn tileset_of(&self, gid: u32) -> Option<(usize, u32)> {
+        let index = self
+            .tilesets
+            .iter()
+            .rposition(|tileset| tileset.first_gid <= gid)?;
+        let tileset = &self.tilesets[index];
+        let id = gid - tileset.first_gid;
+        // Tile ids of image collections can have gaps, and go past the tile count
+        (id < tileset.tile_count || tileset.tiles.contains_key(&id)).then_some((index, id))
+    }
+    /// The position of the top corner of the tile cell at `tile`, in world coordinates.
+    ///
+    /// Fractional tile coordinates are allowed.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::{graphics::tiled::*, system::*};
+    /// let map = Map {
+    ///     orientation: Orientation::Isometric,
+    ///     size: Vector2u::new(10, 10),
+    ///     tile_size: Vector2u::new(64, 32),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(map.tile_to_world(Vector2f::new(0., 0.)), Vector2f::new(320., 0.));
+    /// assert_eq!(map.tile_to_world(Vector2f::new(1., 0.)), Vector2f::new(352., 16.));
+    /// ```
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Map sizes are small enough to be exact"
+    )]
+    pub fn tile_to_world(&self, tile: Vector2f) -> Vector2f {
+        let (w, h) = (self.tile_size.x as f32, self.tile_size.y as f32);
+        match self.orientation {
+            Orientation::Orthogonal => Vector2f::new(tile.x * w, tile.y * h),
+            Orientation::Isometric => Vector2f::new(
+                (tile.x - tile.y + self.size.y as f32) * w / 2.,
+                (tile.x + tile.y) * h / 2.,
+            ),
+        }
+    }
+    /// The position of an object, in world coordinates.
+    ///
+    /// In isometric maps, Tiled stores object positions along the axes of the map, in units
+    /// of the tile height. They are projected like tiles.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Tile sizes are small enough to be exact"
+    )]
+    pub fn object_to_world(&self, position: Vector2f) -> Vector2f {
+        match self.orientation {
+            Orientation::Orthogonal => position,
+            Orientation::Isometric => self.tile_to_world(position / self.tile_size.y as f32),
+        }
+    }
+}
+
+/// A set of tiles, usually cut from a single image
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tileset {
+    /// Global id of the first tile of the set
+    pub first_gid: u32,
+    /// Name of the tileset
+    pub name: String,
+    /// Size of the tiles, in pixels
+    pub tile_size: Vector2u,
+    /// Space between the tiles in the image, in pixels
+    pub spacing: u32,
+    /// Space around the tiles in the image, in pixels
+    pub margin: u32,
+    /// Number of tiles
+    pub tile_count: u32,
+    /// Number of tile columns in the image
+    pub columns: u32,
+    /// Path of the image, or `None` for collections of images
+    pub image: Option<PathBuf>,
+    /// Size of the image, in pixels
+    pub image_size: Vector2u,
+    /// Offset applied when drawing the tiles, in pixels
+    pub offset: Vector2i,
+    /// Additional data of some tiles, by local id
+    pub tiles: HashMap<u32, TileData>,
+    /// Custom properties
+    pub properties: Properties,
+}
+
+impl Tileset {
+    /// The area of the tile with local id `id` in the image
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::{graphics::{IntRect, tiled::*}, system::*};
+    /// let tileset = Tileset {
+    ///     tile_size: Vector2u::new(16, 16),
+    ///     spacing: 2,
+    ///     margin: 1,
+    ///     columns: 4,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(tileset.tile_rect(5), IntRect::new(19, 19, 16, 16));
+    /// ```
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_wrap,
+        reason = "Tileset images are far smaller than i32::MAX"
+    )]
+    pub fn tile_rect(&self, id: u32) -> IntRect {
+        let columns = self.columns.max(1);
+        let (col, row) = (id % columns, id / columns);
+        IntRect::new(
+            (self.margin + col * (self.tile_size.x + self.spacing)) as i32,
+            (self.margin + row * (self.tile_size.y + self.spacing)) as i32,
+            self.tile_size.x as i32,
+            self.tile_size.y as i32,
+        )
+    }
+}
+
+/// Additional data of a tile of a [`Tileset`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TileData {
+    /// Class of the tile, empty if unset
+    pub class: String,
+    /// Image of the tile, in collections of images
+    pub image: Option<PathBuf>,
+    /// Size of the image of the tile, in pixels
+    pub image_size: Vector2u,
+    /// Animation frames, as local tile ids and durations. Empty if the tile isn't animated.
+    pub animation: Vec<(u32, Time)>,
+    /// Custom properties
+    pub properties: Properties,
+}
+
+/// A cell of a tile layer: a global tile id, with flip flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Tile {
+    /// Global tile id, 0 for empty cells
+    pub gid: u32,
+    /// Whether the tile is mirrored horizontally
+    pub flip_horizontal: bool,
+    /// Whether the tile is mirrored vertically
+    pub flip_vertical: bool,
+    /// Whether the X and Y axes of the tile are swapped. This is applied before the other
+    /// flips.
+    pub flip_diagonal: bool,
+}
+
+impl Tile {
+    /// Decode a global tile id as stored by Tiled, with the flip flags in its highest bits.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::tiled::Tile;
+    /// let tile = Tile::from_raw(0x8000_0005);
+    /// assert_eq!(tile.gid, 5);
+    /// assert!(tile.flip_horizontal && !tile.flip_vertical);
+    /// ```
+    #[must_use]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self {
+            // The 4th bit is the rotation of hexagonal maps, which aren't supported
+            gid: raw & 0x0fff_ffff,
+            flip_horizontal: raw & 0x8000_0000 != 0,
+            flip_vertical: raw & 0x4000_0000 != 0,
+            flip_diagonal: raw & 0x2000_0000 != 0,
+        }
+    }
+    /// Whether the cell is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.gid == 0
+    }
+}
+
+/// A layer of a [`Map`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    /// Unique id of the layer
+    pub id: u32,
+    /// Name of the layer
+    pub name: String,
+    /// Whether the layer is shown
+    pub visible: bool,
+    /// Opacity of the layer, from 0 to 1
+    pub opacity: f32,
+    /// Offset applied when drawing the layer, in pixels
+    pub offset: Vector2f,
+    /// Custom properties
+    pub properties: Properties,
+    /// The contents of the layer
+    pub kind: LayerKind,
+}
+
+/// The contents of a [`Layer`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerKind {
+    /// A grid of tiles
+    Tiles(TileLayer),
+    /// Free-form objects
+    Objects(ObjectLayer),
+    /// A single image
+    Image(ImageLayer),
+}
+
+/// A grid of tiles
+///
+/// The chunks of infinite maps are merged into a single grid. Maps whose chunks are spread
+/// over more than 2<sup>24</sup> tiles are rejected with [`Error::Invalid`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TileLayer {
+    /// Tile coordinates of the top left cell. Only infinite maps have tiles at negative
+    /// coordinates.
+    pub origin: Vector2i,
+    /// Size of the grid, in tiles
+    pub size: Vector2u,
+    /// The cells, in row-major order
+    pub tiles: Vec<Tile>,
+}
+
+impl TileLayer {
+    /// The cell at tile coordinates `(x, y)`, or `None` outside of the layer
+    #[must_use]
+    #[expect(
+        clippy::cast_sign_loss,
+        reason = "Coordinates are checked to be positive"
+    )]
+    pub fn tile(&self, x: i32, y: i32) -> Option<Tile> {
+        let (x, y) = (x - self.origin.x, y - self.origin.y);
+        if x < 0 || y < 0 || x as u32 >= self.size.x || y as u32 >= self.size.y {
+            return None;
+        }
+        self.tiles
+            .get(y as usize * self.size.x as usize + x as usize)
+            .copied()
+    }
+}
+
+/// Free-form objects, like spawn points or collision shapes
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ObjectLayer {
+    /// Color the objects are shown with in the editor
+    pub color: Option<Color>,
+    /// The objects
+    pub objects: Vec<Object>,
+}
+
+/// An object of an [`ObjectLayer`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Object {
+    /// Unique id of the object
+    pub id: u32,
+    /// Name of the object
+    pub name: String,
+    /// Class of the object, empty if unset
+    pub class: String,
+    /// Position of the object, in pixels. See [`Map::object_to_world`].
+    ///
+    /// For tile objects, this is the bottom left corner of the tile.
+    pub position: Vector2f,
+    /// Size of the object, in pixels
+    pub size: Vector2f,
+    /// Rotation around the position, in degrees, clockwise
+    pub rotation: f32,
+    /// Whether the object is shown
+    pub visible: bool,
+    /// The tile shown by tile objects
+    pub tile: Option<Tile>,
+    /// The shape of the object
+    pub shape: ObjectShape,
+    /// Custom properties
+    pub properties: Properties,
+}
+
+/// The shape of an [`Object`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ObjectShape {
+    /// A rectangle of the size of the object
+    #[default]
+    Rect,
+    /// An ellipse fitting in the size of the object
+    Ellipse,
+    /// A point, without size
+    Point,
+    /// A closed polygon, with points relative to the position of the object
+    Polygon(Vec<Vector2f>),
+    /// An open polyline, with points relative to the position of the object
+    Polyline(Vec<Vector2f>),
+    /// A text box of the size of the object
+    Text(String),
+}
+
+/// A layer showing a single image
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImageLayer {
+    /// Path of the image, or `None` if the layer is empty
+    pub image: Option<PathBuf>,
+    /// Whether the image is repeated horizontally over the whole map
+    pub repeat_x: bool,
+    /// Whether the image is repeated vertically over the whole map
+    pub repeat_y: bool,
+}
+
+/// Custom properties, by name
+pub type Properties = HashMap<String, PropertyValue>;
+
+/// The value of a custom property
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    /// A string
+    String(String),
+    /// An integer
+    Int(i64),
+    /// A floating point number
+    Float(f64),
+    /// A boolean
+    Bool(bool),
+    /// A color, or `None` if unset
+    Color(Option<Color>),
+    /// A path, relative to the file defining the property
+    File(String),
+    /// The id of an object, 0 if unset
+    Object(u32),
+    /// A custom class, with its members
+    Class(Properties),
+}
+
+/// Error loading a Tiled map
+#[derive(Debug)]
+pub enum Error {
+    /// A file couldn't be read
+    Io(io::Error),
+    /// A file isn't valid XML or JSON
+    Syntax(String),
+    /// A file is valid XML or JSON, but not a valid or supported Tiled file
+    Invalid(String),
+    /// An image couldn't be loaded as a texture
+    Texture(PathBuf, SfError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read Tiled file: {err}"),
+            Self::Syntax(msg) => write!(f, "failed to parse Tiled file: {msg}"),
+            Self::Invalid(msg) => write!(f, "invalid Tiled file: {msg}"),
+            Self::Texture(path, err) => {
+                write!(f, "failed to load texture {}: {err}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Texture(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
@@ -0,0 +1,525 @@
+//! Drawing maps with vertex buffers
+
+use {
+    super::{Error, LayerKind, Map, Orientation, Tile, TileLayer},
+    crate::{
+        SfResult,
+        cpp::FBox,
+        graphics::{
+            Color, FloatRect, IntRect, PrimitiveType, RenderStates, RenderTarget, Texture, Vertex,
+            VertexBuffer, VertexBufferUsage,
+        },
+        system::{Time, Vector2f},
+    },
+    std::{collections::HashMap, path::Path},
+};
+
+/// Width and height of the chunks layers are split into, in tiles
+const CHUNK_SIZE: u32 = 16;
+
+/// The texture a batch of tiles is drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TextureKey {
+    /// The image of a tileset
+    Tileset(usize),
+    /// The image of a tile of a collection of images
+    Tile(usize, u32),
+}
+
+/// The textures of the tilesets and image layers of a [`Map`].
+///
+/// See the [module documentation](super) for an example.
+#[derive(Debug, Default)]
+pub struct MapTextures {
+    tiles: HashMap<TextureKey, FBox<Texture>>,
+    layers: HashMap<usize, FBox<Texture>>,
+}
+
+impl MapTextures {
+    /// Load the images of the tilesets and image layers of `map`.
+    ///
+    /// The textures of repeated image layers are made [repeated](Texture::set_repeated).
+    pub fn load(map: &Map) -> Result<Self, Error> {
+        let mut textures = Self::default();
+        for (i, tileset) in map.tilesets.iter().enumerate() {
+            if let Some(image) = &tileset.image {
+                let _ = textures
+                    .tiles
+                    .insert(TextureKey::Tileset(i), load_texture(image)?);
+            }
+            for (&id, tile) in &tileset.tiles {
+                if let Some(image) = &tile.image {
+                    let _ = textures
+                        .tiles
+                        .insert(TextureKey::Tile(i, id), load_texture(image)?);
+                }
+            }
+        }
+        for (i, layer) in map.layers.iter().enumerate() {
+            let LayerKind::Image(image) = &layer.kind else {
+                continue;
+            };
+            if let Some(path) = &image.image {
+                let mut texture = load_texture(path)?;
+                texture.set_repeated(image.repeat_x || image.repeat_y);
+                let _ = textures.layers.insert(i, texture);
+            }
+        }
+        Ok(textures)
+    }
+    /// The texture of the tileset at `index` in [`Map::tilesets`], unless it is a collection
+    /// of images
+    #[must_use]
+    pub fn tileset(&self, index: usize) -> Option<&Texture> {
+        self.tiles.get(&TextureKey::Tileset(index)).map(|t| &**t)
+    }
+    /// The texture of the image layer at `index` in [`Map::layers`]
+    #[must_use]
+    pub fn layer_image(&self, index: usize) -> Option<&Texture> {
+        self.layers.get(&index).map(|t| &**t)
+    }
+}
+
+fn load_texture(path: &Path) -> Result<FBox<Texture>, Error> {
+    let name = path
+        .to_str()
+        .ok_or_else(|| Error::Invalid(format!("non UTF-8 path {}", path.display())))?;
+    Texture::from_file(name).map_err(|err| Error::Texture(path.to_owned(), err))
+}
+
+/// Draws the tile and image layers of a [`Map`].
+///
+/// Tile layers are split into chunks of 16×16 tiles, and the tiles of each chunk using the
+/// same texture are uploaded once into a [`VertexBuffer`]. Only the chunks overlapping the
+/// current [`View`](crate::graphics::View) of the target are drawn. Animated tiles are kept
+/// in [`VertexBufferUsage::DYNAMIC`] buffers, which are only updated when a frame changes.
+///
+/// Object layers aren't drawn, as their meaning depends on the game.
+///
+/// Within a chunk, tiles from different tilesets are drawn in separate batches, so tiles
+/// larger than the grid of the map can overlap in a different order than in Tiled.
+///
+/// See the [module documentation](super) for an example.
+#[derive(Debug)]
+pub struct MapRenderer {
+    layers: Vec<LayerBatches>,
+    animations: Vec<Animation>,
+    elapsed: Time,
+}
+
+#[derive(Debug)]
+struct LayerBatches {
+    visible: bool,
+    content: Content,
+}
+
+#[derive(Debug)]
+enum Content {
+    Tiles(Vec<Chunk>),
+    Image {
+        offset: Vector2f,
+        color: Color,
+        repeat_x: bool,
+        repeat_y: bool,
+    },
+    None,
+}
+
+/// The tiles of a chunk using the same texture
+#[derive(Debug)]
+struct Chunk {
+    texture: TextureKey,
+    bounds: FloatRect,
+    vertices: Vec<Vertex>,
+    buffer: FBox<VertexBuffer>,
+    /// The animated tiles, as the index of their first vertex, their animation, and their
+    /// flip flags
+    animated: Vec<(usize, usize, Tile)>,
+}
+
+/// The animation of a tile of a tileset
+#[derive(Debug)]
+struct Animation {
+    frames: Vec<(IntRect, Time)>,
+    duration: Time,
+    current: usize,
+    changed: bool,
+}
+
+impl Animation {
+    fn frame_at(&self, elapsed: Time) -> usize {
+        let duration = self.duration.as_microseconds();
+        if duration <= 0 {
+            return 0;
+        }
+        let mut t = elapsed.as_microseconds() % duration;
+        for (i, &(_, frame)) in self.frames.iter().enumerate() {
+            t -= frame.as_microseconds();
+            if t < 0 {
+                return i;
+            }
+        }
+        self.frames.len() - 1
+    }
+}
+
+impl MapRenderer {
+    /// Build the vertex buffers of the layers of `map`.
+    ///
+    /// The renderer doesn't keep a reference to `map`, so later changes to `map` aren't
+    /// shown until a new renderer is built.
+    pub fn new(map: &Map) -> SfResult<Self> {
+        let mut builder = Builder {
+            map,
+            animation_ids: HashMap::new(),
+            animations: Vec::new(),
+        };
+        for (i, tileset) in map.tilesets.iter().enumerate() {
+            // Animations can only change the texture rect, not the texture
+            if tileset.image.is_none() {
+                continue;
+            }
+            for (&id, tile) in &tileset.tiles {
+                if tile.animation.is_empty() {
+                    continue;
+                }
+                let frames: Vec<_> = tile
+                    .animation
+                    .iter()
+                    .map(|&(id, duration)| (tileset.tile_rect(id), duration))
+                    .collect();
+                let _ = builder
+                    .animation_ids
+                    .insert((i, id), builder.animations.len());
+                builder.animations.push(Animation {
+                    duration: frames.iter().fold(Time::ZERO, |acc, &(_, d)| acc + d),
+                    frames,
+                    current: 0,
+                    changed: false,
+                });
+            }
+        }
+        let mut layers = Vec::with_capacity(map.layers.len());
+        for layer in &map.layers {
+            let color = opacity_color(layer.opacity);
+            let content = match &layer.kind {
+                LayerKind::Tiles(tiles) => {
+                    Content::Tiles(builder.chunks(tiles, layer.offset, color)?)
+                }
+                LayerKind::Image(image) if image.image.is_some() => Content::Image {
+                    offset: layer.offset,
+                    color,
+                    repeat_x: image.repeat_x,
+                    repeat_y: image.repeat_y,
+                },
+                LayerKind::Image(_) | LayerKind::Objects(_) => Content::None,
+            };
+            layers.push(LayerBatches {
+                visible: layer.visible,
+                content,
+            });
+        }
+        Ok(Self {
+            layers,
+            animations: builder.animations,
+            elapsed: Time::ZERO,
+        })
+    }
+    /// Whether the layer at `index` in [`Map::layers`] is drawn
+    #[must_use]
+    pub fn is_layer_visible(&self, index: usize) -> bool {
+        self.layers.get(index).is_some_and(|layer| layer.visible)
+    }
+    /// Show or hide the layer at `index` in [`Map::layers`].
+    ///
+    /// Layers start with the visibility they have in the map.
+    pub fn set_layer_visible(&mut self, index: usize, visible: bool) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.visible = visible;
+        }
+    }
+    /// Advance the tile animations by `dt`, and update the vertex buffers of the chunks
+    /// whose animated tiles changed frame.
+    pub fn update(&mut self, dt: Time) -> SfResult<()> {
+        self.elapsed += dt;
+        let mut any_changed = false;
+        for animation in &mut self.animations {
+            let frame = animation.frame_at(self.elapsed);
+            animation.changed = frame != animation.current;
+            animation.current = frame;
+            any_changed |= animation.changed;
+        }
+        if !any_changed {
+            return Ok(());
+        }
+        for layer in &mut self.layers {
+            let Content::Tiles(chunks) = &mut layer.content else {
+                continue;
+            };
+            for chunk in chunks {
+                let mut changed = false;
+                for &(vertex, animation, tile) in &chunk.animated {
+                    let animation = &self.animations[animation];
+                    if animation.changed {
+                        changed = true;
+                        let coords = tex_coords(animation.frames[animation.current].0, tile);
+                        for (v, i) in chunk.vertices[vertex..vertex + 6].iter_mut().zip(QUAD) {
+                            v.tex_coords = coords[i];
+                        }
+                    }
+                }
+                if changed {
+                    chunk.buffer.update(&chunk.vertices, 0)?;
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Draw the visible layers, with the tiles outside of the view of `target` left out.
+    ///
+    /// The transform of `states` is applied to the map, and its texture is replaced by
+    /// those of `textures`.
+    pub fn draw<RT: RenderTarget + ?Sized>(
+        &self,
+        target: &mut RT,
+        textures: &MapTextures,
+        states: &RenderStates,
+    ) {
+        let visible = visible_area(target, states);
+        for (i, layer) in self.layers.iter().enumerate() {
+            if !layer.visible {
+                continue;
+            }
+            match &layer.content {
+                Content::Tiles(chunks) => {
+                    for chunk in chunks {
+                        if !chunk.bounds.intersects(&visible) {
+                            continue;
+                        }
+                        let Some(texture) = textures.tiles.get(&chunk.texture) else {
+                            continue;
+                        };
+                        let states = RenderStates {
+                            texture: Some(texture),
+                            ..*states
+                        };
+                        target.draw_vertex_buffer(&chunk.buffer, &states);
+                    }
+                }
+                &Content::Image {
+                    offset,
+                    color,
+                    repeat_x,
+                    repeat_y,
+                } => {
+                    let Some(texture) = textures.layer_image(i) else {
+                        continue;
+                    };
+                    let size = texture.size().as_other::<f32>();
+                    let mut area = FloatRect::from_vecs(offset, size);
+                    if repeat_x {
+                        area.left = visible.left;
+                        area.width = visible.width;
+                    }
+                    if repeat_y {
+                        area.top = visible.top;
+                        area.height = visible.height;
+                    }
+                    let positions = corners(area);
+                    let coords =
+                        corners(FloatRect::from_vecs(area.position() - offset, area.size()));
+                    let vertices = QUAD.map(|i| Vertex::new(positions[i], color, coords[i]));
+                    let states = RenderStates {
+                        texture: Some(texture),
+                        ..*states
+                    };
+                    target.draw_primitives(&vertices, PrimitiveType::TRIANGLES, &states);
+                }
+                Content::None => {}
+            }
+        }
+    }
+}
+
+/// The indices of the corners `[top left, top right, bottom right, bottom left]` making up
+/// the two triangles of a quad
+const QUAD: [usize; 6] = [0, 1, 2, 0, 2, 3];
+
+/// The tiles of a chunk drawn with the same texture, before they are uploaded
+struct Batch {
+    texture: TextureKey,
+    vertices: Vec<Vertex>,
+    animated: Vec<(usize, usize, Tile)>,
+}
+
+impl Batch {
+    fn upload(self) -> SfResult<Chunk> {
+        let usage = if self.animated.is_empty() {
+            VertexBufferUsage::STATIC
+        } else {
+            VertexBufferUsage::DYNAMIC
+        };
+        let mut buffer = VertexBuffer::new(PrimitiveType::TRIANGLES, self.vertices.len(), usage)?;
+        buffer.update(&self.vertices, 0)?;
+        Ok(Chunk {
+            texture: self.texture,
+            bounds: FloatRect::from_points(self.vertices.iter().map(|v| v.position))
+                .unwrap_or_default(),
+            vertices: self.vertices,
+            buffer,
+            animated: self.animated,
+        })
+    }
+}
+
+/// The state shared while building the chunks of the layers of a map
+struct Builder<'m> {
+    map: &'m Map,
+    /// Indices in `animations`, by tileset index and local tile id
+    animation_ids: HashMap<(usize, u32), usize>,
+    animations: Vec<Animation>,
+}
+
+impl Builder<'_> {
+    /// Split `tiles` into chunks, with a batch per texture in each chunk.
+    #[expect(
+        clippy::cast_possible_wrap,
+        reason = "Map sizes are far below i32::MAX"
+    )]
+    fn chunks(&self, tiles: &TileLayer, offset: Vector2f, color: Color) -> SfResult<Vec<Chunk>> {
+        let mut chunks = Vec::new();
+        for cy in (0..tiles.size.y).step_by(CHUNK_SIZE as usize) {
+            for cx in (0..tiles.size.x).step_by(CHUNK_SIZE as usize) {
+                let mut batches = Vec::new();
+                for y in cy..(cy + CHUNK_SIZE).min(tiles.size.y) {
+                    for x in cx..(cx + CHUNK_SIZE).min(tiles.size.x) {
+                        let tile = tiles.tiles[(y * tiles.size.x + x) as usize];
+                        let cell = (tiles.origin.x + x as i32, tiles.origin.y + y as i32);
+                        self.add_tile(&mut batches, tile, cell, offset, color);
+                    }
+                }
+                for batch in batches {
+                    chunks.push(batch.upload()?);
+                }
+            }
+        }
+        Ok(chunks)
+    }
+    /// Add the quad of `tile`, in the cell at tile coordinates `cell`, to the batch of its
+    /// texture.
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Coordinates are small enough to be exact"
+    )]
+    fn add_tile(
+        &self,
+        batches: &mut Vec<Batch>,
+        tile: Tile,
+        cell: (i32, i32),
+        offset: Vector2f,
+        color: Color,
+    ) {
+        let map = self.map;
+        let Some((ts, id)) = map.tileset_of(tile.gid) else {
+            return;
+        };
+        let tileset = &map.tilesets[ts];
+        let animation = self.animation_ids.get(&(ts, id)).copied();
+        let (texture, rect) = if tileset.image.is_some() {
+            let rect = match animation {
+                Some(animation) => self.animations[animation].frames[0].0,
+                None => tileset.tile_rect(id),
+            };
+            (TextureKey::Tileset(ts), rect)
+        } else {
+            let Some(data) = tileset.tiles.get(&id).filter(|data| data.image.is_some()) else {
+                return;
+            };
+            let size = data.image_size.as_other::<i32>();
+            (TextureKey::Tile(ts, id), IntRect::new(0, 0, size.x, size.y))
+        };
+        let tw = map.tile_size.x as f32;
+        let (x, y) = (cell.0 as f32, cell.1 as f32);
+        // Tiles are aligned on the bottom left corner of their cell
+        let bottom_left = match map.orientation {
+            Orientation::Orthogonal => map.tile_to_world(Vector2f::new(x, y + 1.)),
+            Orientation::Isometric => {
+                map.tile_to_world(Vector2f::new(x + 1., y + 1.)) - Vector2f::new(tw / 2., 0.)
+            }
+        };
+        let (w, h) = (rect.width as f32, rect.height as f32);
+        let size = if tile.flip_diagonal {
+            Vector2f::new(h, w)
+        } else {
+            Vector2f::new(w, h)
+        };
+        let top_left =
+            bottom_left - Vector2f::new(0., size.y) + tileset.offset.as_other::<f32>() + offset;
+        let positions = corners(FloatRect::from_vecs(top_left, size));
+        let coords = tex_coords(rect, tile);
+        let batch = match batches.iter().position(|b| b.texture == texture) {
+            Some(i) => &mut batches[i],
+            None => {
+                batches.push(Batch {
+                    texture,
+                    vertices: Vec::new(),
+                    animated: Vec::new(),
+                });
+                batches.last_mut().expect("A batch was just pushed")
+            }
+        };
+        if let Some(animation) = animation {
+            batch.animated.push((batch.vertices.len(), animation, tile));
+        }
+        batch
+            .vertices
+            .extend(QUAD.map(|i| Vertex::new(positions[i], color, coords[i])));
+    }
+}
+
+/// The corners of `rect`: top left, top right, bottom right, bottom left
+fn corners(rect: FloatRect) -> [Vector2f; 4] {
+    let (l, t) = (rect.left, rect.top);
+    let (r, b) = (rect.left + rect.width, rect.top + rect.height);
+    [
+        Vector2f::new(l, t),
+        Vector2f::new(r, t),
+        Vector2f::new(r, b),
+        Vector2f::new(l, b),
+    ]
+}
+
+/// The texture coordinates of the corners of a tile, with its flips applied
+fn tex_coords(rect: IntRect, tile: Tile) -> [Vector2f; 4] {
+    let [tl, tr, br, bl] = corners(rect.as_other());
+    let mut c = [tl, tr, br, bl];
+    if tile.flip_diagonal {
+        c = [tl, bl, br, tr];
+    }
+    if tile.flip_horizontal {
+        c = [c[1], c[0], c[3], c[2]];
+    }
+    if tile.flip_vertical {
+        c = [c[3], c[2], c[1], c[0]];
+    }
+    c
+}
+
+/// The vertex color applying a layer opacity
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "The alpha is clamped to [0, 255]"
+)]
+fn opacity_color(opacity: f32) -> Color {
+    Color::rgba(255, 255, 255, (opacity.clamp(0., 1.) * 255.).round() as u8)
+}
+
+/// The area of the map seen by the view of `target`, in map coordinates
+fn visible_area<RT: RenderTarget + ?Sized>(target: &RT, states: &RenderStates) -> FloatRect {
+    let world = target
+        .view()
+        .inverse_transform()
+        .transform_rect(FloatRect::new(-1., -1., 2., 2.));
+    states.transform.inverse().transform_rect(world)
+}
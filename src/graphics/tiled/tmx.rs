@@ -0,0 +1,309 @@
+//! The TMX (XML) format
+
+use {
+    super::{
+        Error, ImageLayer, Layer, LayerKind, Map, Object, ObjectLayer, ObjectShape, Orientation,
+        Properties, PropertyValue, Tile, TileData, TileLayer, Tileset,
+        data::{self, Group},
+    },
+    crate::system::{Time, Vector2f, Vector2i, Vector2u},
+    roxmltree::{Document, Node},
+    std::{path::Path, str::FromStr},
+};
+
+pub(super) fn parse_map(xml: &str, dir: &Path) -> Result<Map, Error> {
+    let doc = Document::parse(xml).map_err(|err| Error::Syntax(err.to_string()))?;
+    let root = doc.root_element();
+    if !root.has_tag_name("map") {
+        return Err(Error::Invalid("the root element isn't <map>".into()));
+    }
+    let mut map = Map {
+        orientation: match root.attribute("orientation").unwrap_or("orthogonal") {
+            "orthogonal" => Orientation::Orthogonal,
+            "isometric" => Orientation::Isometric,
+            other => {
+                return Err(Error::Invalid(format!(
+                    "unsupported orientation \"{other}\""
+                )));
+            }
+        },
+        size: Vector2u::new(attr(root, "width", 0)?, attr(root, "height", 0)?),
+        tile_size: Vector2u::new(attr(root, "tilewidth", 0)?, attr(root, "tileheight", 0)?),
+        background_color: root
+            .attribute("backgroundcolor")
+            .map(data::parse_color)
+            .transpose()?,
+        ..Map::default()
+    };
+    for node in root.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "properties" => map.properties = properties(node)?,
+            "tileset" => {
+                let first_gid = attr(node, "firstgid", 1)?;
+                map.tilesets.push(match node.attribute("source") {
+                    Some(source) => data::load_tileset(first_gid, dir, source)?,
+                    None => parse_tileset(node, first_gid, dir)?,
+                });
+            }
+            _ => layers(node, dir, Group::ROOT, &mut map.layers)?,
+        }
+    }
+    map.tilesets.sort_by_key(|tileset| tileset.first_gid);
+    Ok(map)
+}
+
+pub(super) fn parse_tileset_file(xml: &str, dir: &Path) -> Result<Tileset, Error> {
+    let doc = Document::parse(xml).map_err(|err| Error::Syntax(err.to_string()))?;
+    let root = doc.root_element();
+    if !root.has_tag_name("tileset") {
+        return Err(Error::Invalid("the root element isn't <tileset>".into()));
+    }
+    parse_tileset(root, 0, dir)
+}
+
+fn parse_tileset(node: Node, first_gid: u32, dir: &Path) -> Result<Tileset, Error> {
+    let mut tileset = Tileset {
+        first_gid,
+        name: node.attribute("name").unwrap_or_default().to_owned(),
+        tile_size: Vector2u::new(attr(node, "tilewidth", 0)?, attr(node, "tileheight", 0)?),
+        spacing: attr(node, "spacing", 0)?,
+        margin: attr(node, "margin", 0)?,
+        tile_count: attr(node, "tilecount", 0)?,
+        columns: attr(node, "columns", 0)?,
+        ..Tileset::default()
+    };
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "properties" => tileset.properties = properties(child)?,
+            "tileoffset" => {
+                tileset.offset = Vector2i::new(attr(child, "x", 0)?, attr(child, "y", 0)?)
+            }
+            "image" => {
+                tileset.image = child.attribute("source").map(|source| dir.join(source));
+                tileset.image_size =
+                    Vector2u::new(attr(child, "width", 0)?, attr(child, "height", 0)?);
+            }
+            "tile" => {
+                let id = attr(child, "id", 0)?;
+                let _ = tileset.tiles.insert(id, tile_data(child, dir)?);
+            }
+            _ => {}
+        }
+    }
+    Ok(tileset)
+}
+
+fn tile_data(node: Node, dir: &Path) -> Result<TileData, Error> {
+    let mut tile = TileData {
+        class: class(node),
+        ..TileData::default()
+    };
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "properties" => tile.properties = properties(child)?,
+            "image" => {
+                tile.image = child.attribute("source").map(|source| dir.join(source));
+                tile.image_size =
+                    Vector2u::new(attr(child, "width", 0)?, attr(child, "height", 0)?);
+            }
+            "animation" => {
+                for frame in child.children().filter(|n| n.has_tag_name("frame")) {
+                    tile.animation.push((
+                        attr(frame, "tileid", 0)?,
+                        Time::milliseconds(attr(frame, "duration", 0)?),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(tile)
+}
+
+/// Parse the layer `node`, and push it to `out`. Group layers push their children instead.
+fn layers(node: Node, dir: &Path, group: Group, out: &mut Vec<Layer>) -> Result<(), Error> {
+    let kind = match node.tag_name().name() {
+        "layer" => LayerKind::Tiles(tile_layer(node)?),
+        "objectgroup" => LayerKind::Objects(object_layer(node)?),
+        "imagelayer" => LayerKind::Image(ImageLayer {
+            image: node
+                .children()
+                .find(|n| n.has_tag_name("image"))
+                .and_then(|image| image.attribute("source"))
+                .map(|source| dir.join(source)),
+            repeat_x: attr(node, "repeatx", 0u8)? != 0,
+            repeat_y: attr(node, "repeaty", 0u8)? != 0,
+        }),
+        // Used as a placeholder until the children are flattened
+        "group" => LayerKind::Image(ImageLayer::default()),
+        _ => return Ok(()),
+    };
+    let mut layer = Layer {
+        id: attr(node, "id", 0)?,
+        name: node.attribute("name").unwrap_or_default().to_owned(),
+        visible: attr(node, "visible", 1u8)? != 0,
+        opacity: attr(node, "opacity", 1.)?,
+        offset: Vector2f::new(attr(node, "offsetx", 0.)?, attr(node, "offsety", 0.)?),
+        properties: match node.children().find(|n| n.has_tag_name("properties")) {
+            Some(props) => properties(props)?,
+            None => Properties::new(),
+        },
+        kind,
+    };
+    let children = group.apply(&mut layer);
+    if node.has_tag_name("group") {
+        for child in node.children().filter(Node::is_element) {
+            layers(child, dir, children, out)?;
+        }
+    } else {
+        out.push(layer);
+    }
+    Ok(())
+}
+
+fn tile_layer(node: Node) -> Result<TileLayer, Error> {
+    let size = Vector2u::new(attr(node, "width", 0)?, attr(node, "height", 0)?);
+    let Some(data) = node.children().find(|n| n.has_tag_name("data")) else {
+        return data::tile_layer(
+            Vector2i::default(),
+            size,
+            &vec![0; size.x as usize * size.y as usize],
+        );
+    };
+    let encoding = data.attribute("encoding").unwrap_or_default();
+    let compression = data.attribute("compression").unwrap_or_default();
+    let decode = |node: Node| -> Result<Vec<u32>, Error> {
+        if encoding.is_empty() {
+            // The deprecated XML encoding, with a <tile> element per cell
+            node.children()
+                .filter(|n| n.has_tag_name("tile"))
+                .map(|tile| attr(tile, "gid", 0))
+                .collect()
+        } else {
+            data::decode_tiles(node.text().unwrap_or_default(), encoding, compression)
+        }
+    };
+    let chunks = data
+        .children()
+        .filter(|n| n.has_tag_name("chunk"))
+        .map(|chunk| {
+            data::chunk(
+                Vector2i::new(attr(chunk, "x", 0)?, attr(chunk, "y", 0)?),
+                Vector2u::new(attr(chunk, "width", 0)?, attr(chunk, "height", 0)?),
+                &decode(chunk)?,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if chunks.is_empty() {
+        data::tile_layer(Vector2i::default(), size, &decode(data)?)
+    } else {
+        data::merge_chunks(&chunks)
+    }
+}
+
+fn object_layer(node: Node) -> Result<ObjectLayer, Error> {
+    Ok(ObjectLayer {
+        color: node.attribute("color").map(data::parse_color).transpose()?,
+        objects: node
+            .children()
+            .filter(|n| n.has_tag_name("object"))
+            .map(object)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn object(node: Node) -> Result<Object, Error> {
+    let mut object = Object {
+        id: attr(node, "id", 0)?,
+        name: node.attribute("name").unwrap_or_default().to_owned(),
+        class: class(node),
+        position: Vector2f::new(attr(node, "x", 0.)?, attr(node, "y", 0.)?),
+        size: Vector2f::new(attr(node, "width", 0.)?, attr(node, "height", 0.)?),
+        rotation: attr(node, "rotation", 0.)?,
+        visible: attr(node, "visible", 1u8)? != 0,
+        tile: node
+            .attribute("gid")
+            .map(|_| attr(node, "gid", 0).map(Tile::from_raw))
+            .transpose()?,
+        ..Object::default()
+    };
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "properties" => object.properties = properties(child)?,
+            "ellipse" => object.shape = ObjectShape::Ellipse,
+            "point" => object.shape = ObjectShape::Point,
+            "polygon" => object.shape = ObjectShape::Polygon(points(child)?),
+            "polyline" => object.shape = ObjectShape::Polyline(points(child)?),
+            "text" => {
+                object.shape = ObjectShape::Text(child.text().unwrap_or_default().to_owned());
+            }
+            _ => {}
+        }
+    }
+    Ok(object)
+}
+
+/// Parse the `points` attribute of a polygon or polyline, like `"0,0 32,0 32,16"`.
+fn points(node: Node) -> Result<Vec<Vector2f>, Error> {
+    let points = node.attribute("points").unwrap_or_default();
+    points
+        .split_whitespace()
+        .map(|point| {
+            point
+                .split_once(',')
+                .and_then(|(x, y)| Some(Vector2f::new(x.parse().ok()?, y.parse().ok()?)))
+                .ok_or_else(|| Error::Invalid(format!("invalid point \"{point}\"")))
+        })
+        .collect()
+}
+
+fn properties(node: Node) -> Result<Properties, Error> {
+    node.children()
+        .filter(|n| n.has_tag_name("property"))
+        .map(|prop| {
+            let name = prop.attribute("name").unwrap_or_default().to_owned();
+            // Multiline strings are stored as text instead of in the attribute
+            let value = prop
+                .attribute("value")
+                .or_else(|| prop.text())
+                .unwrap_or_default();
+            let value = match prop.attribute("type").unwrap_or("string") {
+                "bool" => PropertyValue::Bool(value == "true"),
+                "int" => PropertyValue::Int(parse(value, "int")?),
+                "float" => PropertyValue::Float(parse(value, "float")?),
+                "color" => PropertyValue::Color(data::parse_optional_color(value)?),
+                "file" => PropertyValue::File(value.to_owned()),
+                "object" => PropertyValue::Object(parse(value, "object")?),
+                "class" => PropertyValue::Class(
+                    match prop.children().find(|n| n.has_tag_name("properties")) {
+                        Some(members) => properties(members)?,
+                        None => Properties::new(),
+                    },
+                ),
+                _ => PropertyValue::String(value.to_owned()),
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// The class of a tile or object, named `type` before Tiled 1.9
+fn class(node: Node) -> String {
+    node.attribute("class")
+        .or_else(|| node.attribute("type"))
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// Parse the attribute `name` of `node`, or return `default` if it is missing.
+fn attr<T: FromStr>(node: Node, name: &str, default: T) -> Result<T, Error> {
+    node.attribute(name)
+        .map_or(Ok(default), |value| parse(value, name))
+}
+
+fn parse<T: FromStr>(value: &str, name: &str) -> Result<T, Error> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| Error::Invalid(format!("invalid {name} \"{value}\"")))
+}
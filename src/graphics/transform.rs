@@ -1,9 +1,18 @@
-use crate::{ffi::graphics as ffi, graphics::FloatRect, system::Vector2f};
+use {
+    crate::{
+        graphics::{FloatRect, glsl},
+        system::Vector2f,
+    },
+    std::ops::{Mul, MulAssign},
+};
 
 /// Define a 3x3 transform matrix.
 ///
 /// A `Transform` specifies how to translate,
 /// rotate, scale, shear, project, whatever things.
+///
+/// With the `serde` feature, transforms are serialized as their 3x3 matrix, as an array
+/// of 3 rows.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Transform {
@@ -92,6 +101,78 @@ impl Transform {
         }
     }
 
+    /// Create a translation transform
+    #[must_use]
+    pub const fn from_translation(offset: Vector2f) -> Self {
+        Self::new(1., 0., offset.x, 0., 1., offset.y, 0., 0., 1.)
+    }
+
+    /// Create a rotation transform
+    ///
+    /// # Arguments
+    /// * angle - Rotation angle, in degrees
+    #[must_use]
+    pub fn from_rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Self::new(cos, -sin, 0., sin, cos, 0., 0., 0., 1.)
+    }
+
+    /// Create a scaling transform
+    #[must_use]
+    pub const fn from_scale(factors: Vector2f) -> Self {
+        Self::new(factors.x, 0., 0., 0., factors.y, 0., 0., 0., 1.)
+    }
+
+    /// Create a shearing transform
+    ///
+    /// Points are moved horizontally by `factors.x` times their Y coordinate,
+    /// and vertically by `factors.y` times their X coordinate.
+    #[must_use]
+    pub const fn from_shear(factors: Vector2f) -> Self {
+        Self::new(1., factors.x, 0., factors.y, 1., 0., 0., 0., 1.)
+    }
+
+    /// Return the 3x3 matrix, in column-major order
+    ///
+    /// This is the layout of a GLSL `mat3`.
+    #[must_use]
+    pub const fn matrix3(&self) -> [f32; 9] {
+        let m = &self.matrix;
+        [m[0], m[1], m[3], m[4], m[5], m[7], m[12], m[13], m[15]]
+    }
+
+    /// Return the determinant of the 3x3 matrix
+    ///
+    /// A negative determinant means that the transform flips things, and a determinant of
+    /// 0 means that it can't be inverted.
+    #[must_use]
+    pub const fn determinant(&self) -> f32 {
+        let m = &self.matrix;
+        m[0] * (m[15] * m[5] - m[7] * m[13]) - m[1] * (m[15] * m[4] - m[7] * m[12])
+            + m[3] * (m[13] * m[4] - m[5] * m[12])
+    }
+
+    /// Return the combination of `self` followed by `other`
+    ///
+    /// Same as [`combine`](Transform::combine), but returns a new transform.
+    /// Also available as `self * other`.
+    #[must_use]
+    pub const fn combined(&self, other: &Transform) -> Self {
+        let a = &self.matrix;
+        let b = &other.matrix;
+        Self::new(
+            a[0] * b[0] + a[4] * b[1] + a[12] * b[3],
+            a[0] * b[4] + a[4] * b[5] + a[12] * b[7],
+            a[0] * b[12] + a[4] * b[13] + a[12] * b[15],
+            a[1] * b[0] + a[5] * b[1] + a[13] * b[3],
+            a[1] * b[4] + a[5] * b[5] + a[13] * b[7],
+            a[1] * b[12] + a[5] * b[13] + a[13] * b[15],
+            a[3] * b[0] + a[7] * b[1] + a[15] * b[3],
+            a[3] * b[4] + a[7] * b[5] + a[15] * b[7],
+            a[3] * b[12] + a[7] * b[13] + a[15] * b[15],
+        )
+    }
+
     /// Combine two transforms
     ///
     /// The result is a transform that is equivalent to applying
@@ -101,7 +182,7 @@ impl Transform {
     /// # Arguments
     /// * other - Transform to combine to transform
     pub fn combine(&mut self, other: &Transform) {
-        unsafe { ffi::sfTransform_combine(self, other) }
+        *self = self.combined(other);
     }
 
     /// Combine a transform with a translation
@@ -110,7 +191,7 @@ impl Transform {
     /// * x - Offset to apply on X axis
     /// * y - Offset to apply on Y axis
     pub fn translate(&mut self, x: f32, y: f32) {
-        unsafe { ffi::sfTransform_translate(self, x, y) }
+        self.combine(&Self::from_translation(Vector2f::new(x, y)));
     }
 
     /// Combine the current transform with a rotation
@@ -118,7 +199,7 @@ impl Transform {
     /// # Arguments
     /// * angle - Rotation angle, in degrees
     pub fn rotate(&mut self, angle: f32) {
-        unsafe { ffi::sfTransform_rotate(self, angle) }
+        self.combine(&Self::from_rotation(angle));
     }
 
     /// Combine the current transform with a rotation
@@ -133,7 +214,18 @@ impl Transform {
     /// * `center_x` - X coordinate of the center of rotation
     /// * `center_y` - Y coordinate of the center of rotation
     pub fn rotate_with_center(&mut self, angle: f32, center_x: f32, center_y: f32) {
-        unsafe { ffi::sfTransform_rotateWithCenter(self, angle, center_x, center_y) }
+        let (sin, cos) = angle.to_radians().sin_cos();
+        self.combine(&Self::new(
+            cos,
+            -sin,
+            center_x * (1. - cos) + center_y * sin,
+            sin,
+            cos,
+            center_y * (1. - cos) - center_x * sin,
+            0.,
+            0.,
+            1.,
+        ));
     }
 
     /// Combine the current transform with a scaling
@@ -142,7 +234,7 @@ impl Transform {
     /// * `scale_x` - Scaling factor on the X axis
     /// * `scale_y` - Scaling factor on the Y axis
     pub fn scale(&mut self, scale_x: f32, scale_y: f32) {
-        unsafe { ffi::sfTransform_scale(self, scale_x, scale_y) }
+        self.combine(&Self::from_scale(Vector2f::new(scale_x, scale_y)));
     }
 
     /// Combine the current transform with a scaling
@@ -158,7 +250,28 @@ impl Transform {
     /// * `center_x` - X coordinate of the center of scaling
     /// * `center_y` - Y coordinate of the center of scaling
     pub fn scale_with_center(&mut self, scale_x: f32, scale_y: f32, center_x: f32, center_y: f32) {
-        unsafe { ffi::sfTransform_scaleWithCenter(self, scale_x, scale_y, center_x, center_y) }
+        self.combine(&Self::new(
+            scale_x,
+            0.,
+            center_x * (1. - scale_x),
+            0.,
+            scale_y,
+            center_y * (1. - scale_y),
+            0.,
+            0.,
+            1.,
+        ));
+    }
+
+    /// Combine the current transform with a shearing
+    ///
+    /// See [`Transform::from_shear`].
+    ///
+    /// # Arguments
+    /// * `shear_x` - Shearing factor on the X axis
+    /// * `shear_y` - Shearing factor on the Y axis
+    pub fn shear(&mut self, shear_x: f32, shear_y: f32) {
+        self.combine(&Self::from_shear(Vector2f::new(shear_x, shear_y)));
     }
 
     /// Apply a transform to a 2D point
@@ -168,8 +281,24 @@ impl Transform {
     ///
     /// Return a transformed point
     #[must_use]
-    pub fn transform_point(&self, point: Vector2f) -> Vector2f {
-        unsafe { ffi::sfTransform_transformPoint(self, point) }
+    pub const fn transform_point(&self, point: Vector2f) -> Vector2f {
+        let m = &self.matrix;
+        Vector2f::new(
+            m[0] * point.x + m[4] * point.y + m[12],
+            m[1] * point.x + m[5] * point.y + m[13],
+        )
+    }
+
+    /// Apply a transform to a 2D vector, ignoring the translation
+    ///
+    /// This is useful for directions and offsets rather than positions.
+    #[must_use]
+    pub const fn transform_vector(&self, vector: Vector2f) -> Vector2f {
+        let m = &self.matrix;
+        Vector2f::new(
+            m[0] * vector.x + m[4] * vector.y,
+            m[1] * vector.x + m[5] * vector.y,
+        )
     }
 
     /// Apply a transform to a rectangle
@@ -186,7 +315,184 @@ impl Transform {
     /// Return the transformed rectangle
     #[must_use]
     pub fn transform_rect(&self, rectangle: FloatRect) -> FloatRect {
-        unsafe { ffi::sfTransform_transformRect(self, rectangle) }
+        let FloatRect {
+            left,
+            top,
+            width,
+            height,
+        } = rectangle;
+        let points = [
+            self.transform_point(Vector2f::new(left, top)),
+            self.transform_point(Vector2f::new(left, top + height)),
+            self.transform_point(Vector2f::new(left + width, top)),
+            self.transform_point(Vector2f::new(left + width, top + height)),
+        ];
+        let mut min = points[0];
+        let mut max = points[0];
+        for p in &points[1..] {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+        FloatRect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// Decompose the transform into translation, rotation, shear and scale
+    ///
+    /// This only accounts for the affine part of the transform.
+    /// See [`Decomposition`] for the meaning of the parts.
+    #[must_use]
+    pub fn decompose(&self) -> Decomposition {
+        let m = &self.matrix;
+        let (a, b, c, d) = (m[0], m[1], m[4], m[5]);
+        let scale_x = a.hypot(b);
+        if scale_x == 0. {
+            // Degenerate X axis: attribute everything to the Y axis
+            let scale_y = c.hypot(d);
+            return Decomposition {
+                translation: Vector2f::new(m[12], m[13]),
+                rotation: if scale_y == 0. {
+                    0.
+                } else {
+                    (-c).atan2(d).to_degrees()
+                },
+                shear: 0.,
+                scale: Vector2f::new(0., scale_y),
+            };
+        }
+        let det = a * d - b * c;
+        let scale_y = det / scale_x;
+        Decomposition {
+            translation: Vector2f::new(m[12], m[13]),
+            rotation: b.atan2(a).to_degrees(),
+            shear: if det == 0. { 0. } else { (a * c + b * d) / det },
+            scale: Vector2f::new(scale_x, scale_y),
+        }
+    }
+
+    /// Interpolate between `self` and `other`
+    ///
+    /// Both transforms are [decomposed](Transform::decompose), and their parts
+    /// interpolated with [`Decomposition::lerp`], so the rotation follows the shortest arc
+    /// instead of shrinking the transform halfway through.
+    #[must_use]
+    pub fn lerp(&self, other: &Transform, t: f32) -> Self {
+        self.decompose().lerp(&other.decompose(), t).to_transform()
+    }
+
+    /// Tell whether each element of the matrix is within `epsilon` of the one of `other`
+    #[must_use]
+    pub fn approx_eq(&self, other: &Transform, epsilon: f32) -> bool {
+        self.matrix
+            .iter()
+            .zip(&other.matrix)
+            .all(|(a, b)| (a - b).abs() <= epsilon)
+    }
+}
+
+/// The parts of an affine [`Transform`], as returned by [`Transform::decompose`].
+///
+/// The transform is equivalent to scaling, then shearing along the X axis,
+/// then rotating, then translating:
+/// `translation * rotation * shear * scale`.
+///
+/// Mirroring is represented by a negative `scale.y`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Decomposition {
+    /// Translation
+    pub translation: Vector2f,
+    /// Rotation, in degrees
+    pub rotation: f32,
+    /// Shearing factor along the X axis
+    pub shear: f32,
+    /// Scaling factors
+    pub scale: Vector2f,
+}
+
+impl Decomposition {
+    /// Build the transform from its parts
+    #[must_use]
+    pub fn to_transform(&self) -> Transform {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let Vector2f { x: sx, y: sy } = self.scale;
+        let k = self.shear;
+        Transform::new(
+            sx * cos,
+            sy * (k * cos - sin),
+            self.translation.x,
+            sx * sin,
+            sy * (k * sin + cos),
+            self.translation.y,
+            0.,
+            0.,
+            1.,
+        )
+    }
+
+    /// Interpolate between `self` and `other`
+    ///
+    /// Translation, shear and scale are interpolated linearly, and the rotation
+    /// along the shortest arc.
+    #[must_use]
+    pub fn lerp(&self, other: &Decomposition, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let mut delta = (other.rotation - self.rotation) % 360.;
+        if delta > 180. {
+            delta -= 360.;
+        } else if delta < -180. {
+            delta += 360.;
+        }
+        Self {
+            translation: self.translation + (other.translation - self.translation) * t,
+            rotation: self.rotation + delta * t,
+            shear: lerp(self.shear, other.shear),
+            scale: self.scale + (other.scale - self.scale) * t,
+        }
+    }
+}
+
+impl From<Decomposition> for Transform {
+    fn from(parts: Decomposition) -> Self {
+        parts.to_transform()
+    }
+}
+
+impl Mul for Transform {
+    type Output = Self;
+
+    /// Combine two transforms, see [`Transform::combine`]
+    fn mul(self, rhs: Self) -> Self {
+        self.combined(&rhs)
+    }
+}
+
+impl MulAssign for Transform {
+    /// Combine two transforms, see [`Transform::combine`]
+    fn mul_assign(&mut self, rhs: Self) {
+        self.combine(&rhs);
+    }
+}
+
+impl Mul<Vector2f> for Transform {
+    type Output = Vector2f;
+
+    /// Transform a point, see [`Transform::transform_point`]
+    fn mul(self, rhs: Vector2f) -> Vector2f {
+        self.transform_point(rhs)
+    }
+}
+
+impl From<glsl::Mat3> for Transform {
+    fn from(mat: glsl::Mat3) -> Self {
+        let m = mat.0;
+        Self::new(m[0], m[3], m[6], m[1], m[4], m[7], m[2], m[5], m[8])
+    }
+}
+
+impl From<glsl::Mat4> for Transform {
+    fn from(mat: glsl::Mat4) -> Self {
+        Self { matrix: mat.0 }
     }
 }
 
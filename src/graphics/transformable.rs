@@ -60,7 +60,6 @@ pub trait Transformable {
 }
 
 #[derive(Debug, Clone, Copy)]
-#[cfg(feature = "text")]
 pub struct TransformableData {
     pub origin: [f32; 2],
     pub position: [f32; 2],
@@ -68,7 +67,6 @@ pub struct TransformableData {
     pub scale: [f32; 2],
 }
 
-#[cfg(feature = "text")]
 impl Default for TransformableData {
     fn default() -> Self {
         Self {
@@ -80,7 +78,6 @@ impl Default for TransformableData {
     }
 }
 
-#[cfg(feature = "text")]
 impl TransformableData {
     pub fn get(&self) -> Transform {
         let angle = -self.rotation * std::f32::consts::PI / 180.0;
@@ -0,0 +1,936 @@
+use {
+    crate::{
+        graphics::{
+            CircleShape, Color, ConvexShape, RectangleShape, Shape, Sprite, Transformable, View,
+        },
+        system::{Time, Vector2f},
+    },
+    std::{f32::consts::PI, fmt},
+};
+
+/// An easing curve, from [Robert Penner's easing functions](http://robertpenner.com/easing/).
+///
+/// `In` curves start slowly, `Out` curves end slowly, and `InOut` curves do both.
+/// `Back` curves overshoot the target, `Elastic` curves oscillate around it, and `Bounce`
+/// curves bounce on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[expect(missing_docs, reason = "The names are self-explanatory")]
+pub enum Ease {
+    #[default]
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Ease {
+    /// Map the progress `t` of a tween to the progress of the value.
+    ///
+    /// `t` is clamped to `[0, 1]`. The result is 0 at 0 and 1 at 1, but can leave `[0, 1]`
+    /// in between for the `Back` and `Elastic` curves.
+    ///
+    /// # Usage example
+    ///
+    /// ```
+    /// # use sf2g::graphics::Ease;
+    /// assert_eq!(Ease::QuadIn.apply(0.5), 0.25);
+    /// assert_eq!(Ease::QuadOut.apply(0.5), 0.75);
+    /// assert!(Ease::BackOut.apply(0.8) > 1.);
+    /// ```
+    #[must_use]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        let power_in_out = |n: i32| {
+            if t < 0.5 {
+                2f32.powi(n - 1) * t.powi(n)
+            } else {
+                1. - (2. - 2. * t).powi(n) / 2.
+            }
+        };
+        const BACK: f32 = 1.701_58;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+        match self {
+            Self::Linear => t,
+            Self::SineIn => 1. - (t * PI / 2.).cos(),
+            Self::SineOut => (t * PI / 2.).sin(),
+            Self::SineInOut => (1. - (t * PI).cos()) / 2.,
+            Self::QuadIn => t * t,
+            Self::QuadOut => 1. - (1. - t).powi(2),
+            Self::QuadInOut => power_in_out(2),
+            Self::CubicIn => t.powi(3),
+            Self::CubicOut => 1. - (1. - t).powi(3),
+            Self::CubicInOut => power_in_out(3),
+            Self::QuartIn => t.powi(4),
+            Self::QuartOut => 1. - (1. - t).powi(4),
+            Self::QuartInOut => power_in_out(4),
+            Self::QuintIn => t.powi(5),
+            Self::QuintOut => 1. - (1. - t).powi(5),
+            Self::QuintInOut => power_in_out(5),
+            Self::ExpoIn if t <= 0. => 0.,
+            Self::ExpoIn => 2f32.powf(10. * t - 10.),
+            Self::ExpoOut if t >= 1. => 1.,
+            Self::ExpoOut => 1. - 2f32.powf(-10. * t),
+            Self::ExpoInOut if t <= 0. || t >= 1. => t,
+            Self::ExpoInOut if t < 0.5 => 2f32.powf(20. * t - 10.) / 2.,
+            Self::ExpoInOut => (2. - 2f32.powf(10. - 20. * t)) / 2.,
+            Self::CircIn => 1. - (1. - t * t).sqrt(),
+            Self::CircOut => (1. - (t - 1.).powi(2)).sqrt(),
+            Self::CircInOut if t < 0.5 => (1. - (1. - (2. * t).powi(2)).sqrt()) / 2.,
+            Self::CircInOut => ((1. - (2. - 2. * t).powi(2)).sqrt() + 1.) / 2.,
+            Self::BackIn => (BACK + 1.) * t.powi(3) - BACK * t * t,
+            Self::BackOut => 1. + (BACK + 1.) * (t - 1.).powi(3) + BACK * (t - 1.).powi(2),
+            Self::BackInOut if t < 0.5 => {
+                (2. * t).powi(2) * ((BACK_IN_OUT + 1.) * 2. * t - BACK_IN_OUT) / 2.
+            }
+            Self::BackInOut => {
+                ((2. * t - 2.).powi(2) * ((BACK_IN_OUT + 1.) * (2. * t - 2.) + BACK_IN_OUT) + 2.)
+                    / 2.
+            }
+            Self::ElasticIn | Self::ElasticOut | Self::ElasticInOut if t <= 0. || t >= 1. => t,
+            Self::ElasticIn => {
+                -(2f32.powf(10. * t - 10.)) * ((10. * t - 10.75) * 2. * PI / 3.).sin()
+            }
+            Self::ElasticOut => 2f32.powf(-10. * t) * ((10. * t - 0.75) * 2. * PI / 3.).sin() + 1.,
+            Self::ElasticInOut if t < 0.5 => {
+                -(2f32.powf(20. * t - 10.) * ((20. * t - 11.125) * 2. * PI / 4.5).sin()) / 2.
+            }
+            Self::ElasticInOut => {
+                2f32.powf(10. - 20. * t) * ((20. * t - 11.125) * 2. * PI / 4.5).sin() / 2. + 1.
+            }
+            Self::BounceIn => 1. - bounce_out(1. - t),
+            Self::BounceOut => bounce_out(t),
+            Self::BounceInOut if t < 0.5 => (1. - bounce_out(1. - 2. * t)) / 2.,
+            Self::BounceInOut => (1. + bounce_out(2. * t - 1.)) / 2.,
+        }
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        N * (t - 1.5 / D).powi(2) + 0.75
+    } else if t < 2.5 / D {
+        N * (t - 2.25 / D).powi(2) + 0.9375
+    } else {
+        N * (t - 2.625 / D).powi(2) + 0.984_375
+    }
+}
+
+/// A property of a [`Tweenable`] object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TweenProperty {
+    /// The position of a [`Transformable`], or the center of a [`View`]
+    Position,
+    /// The rotation, in degrees
+    Rotation,
+    /// The scale of a [`Transformable`], or the size of a [`View`]
+    Scale,
+    /// The origin of a [`Transformable`]
+    Origin,
+    /// The color of a [`Sprite`], or the fill color of a shape or text
+    Color,
+}
+
+/// The value of a [`TweenProperty`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TweenValue {
+    /// A number, like a rotation
+    Float(f32),
+    /// A vector, like a position or a scale
+    Vector(Vector2f),
+    /// A color
+    Color(Color),
+}
+
+impl TweenValue {
+    /// Interpolate towards `other`, without clamping `t`. Colors are clamped to their range.
+    fn lerp(self, other: Self, t: f32) -> Self {
+        match (self, other) {
+            (Self::Float(a), Self::Float(b)) => Self::Float(a + (b - a) * t),
+            (Self::Vector(a), Self::Vector(b)) => Self::Vector(a + (b - a) * t),
+            (Self::Color(a), Self::Color(b)) => {
+                let (a, b) = (a.to_normalized(), b.to_normalized());
+                Self::Color(Color::from_normalized(std::array::from_fn(|i| {
+                    a[i] + (b[i] - a[i]) * t
+                })))
+            }
+            _ => other,
+        }
+    }
+    /// Add `offset`, component by component.
+    fn offset(self, offset: Self) -> Self {
+        match (self, offset) {
+            (Self::Float(a), Self::Float(b)) => Self::Float(a + b),
+            (Self::Vector(a), Self::Vector(b)) => Self::Vector(a + b),
+            (Self::Color(a), Self::Color(b)) => Self::Color(a + b),
+            _ => self,
+        }
+    }
+    /// Multiply by `factor`.
+    fn scaled(self, factor: f32) -> Self {
+        match self {
+            Self::Float(a) => Self::Float(a * factor),
+            Self::Vector(a) => Self::Vector(a * factor),
+            Self::Color(_) => self,
+        }
+    }
+}
+
+impl From<f32> for TweenValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<Vector2f> for TweenValue {
+    fn from(value: Vector2f) -> Self {
+        Self::Vector(value)
+    }
+}
+
+impl From<Color> for TweenValue {
+    fn from(value: Color) -> Self {
+        Self::Color(value)
+    }
+}
+
+/// An object whose properties can be animated by a [`Tween`].
+///
+/// Implemented for sprites, shapes, texts and views. Implement it for your own types to
+/// tween them too.
+pub trait Tweenable {
+    /// The current value of `property`, or `None` if the object doesn't have it
+    fn tween_value(&self, property: TweenProperty) -> Option<TweenValue>;
+    /// Set `property` to `value`. Properties the object doesn't have are ignored.
+    fn set_tween_value(&mut self, property: TweenProperty, value: TweenValue);
+}
+
+macro_rules! impl_tweenable {
+    ($($ty:ty, $color:ident, $set_color:ident;)*) => {$(
+        impl Tweenable for $ty {
+            fn tween_value(&self, property: TweenProperty) -> Option<TweenValue> {
+                Some(match property {
+                    TweenProperty::Position => self.position().into(),
+                    TweenProperty::Rotation => self.rotation().into(),
+                    TweenProperty::Scale => self.get_scale().into(),
+                    TweenProperty::Origin => self.origin().into(),
+                    TweenProperty::Color => self.$color().into(),
+                })
+            }
+            fn set_tween_value(&mut self, property: TweenProperty, value: TweenValue) {
+                match (property, value) {
+                    (TweenProperty::Position, TweenValue::Vector(v)) => self.set_position(v),
+                    (TweenProperty::Rotation, TweenValue::Float(v)) => self.set_rotation(v),
+                    (TweenProperty::Scale, TweenValue::Vector(v)) => self.set_scale(v),
+                    (TweenProperty::Origin, TweenValue::Vector(v)) => self.set_origin(v),
+                    (TweenProperty::Color, TweenValue::Color(v)) => self.$set_color(v),
+                    _ => {}
+                }
+            }
+        }
+    )*};
+}
+
+impl_tweenable! {
+    Sprite<'_>, color, set_color;
+    RectangleShape<'_>, fill_color, set_fill_color;
+    CircleShape<'_>, fill_color, set_fill_color;
+    ConvexShape<'_>, fill_color, set_fill_color;
+}
+
+#[cfg(feature = "text")]
+impl Tweenable for crate::graphics::Text<'_> {
+    fn tween_value(&self, property: TweenProperty) -> Option<TweenValue> {
+        Some(match property {
+            TweenProperty::Position => Vector2f::from(self.tf.position).into(),
+            TweenProperty::Rotation => self.tf.rotation.into(),
+            TweenProperty::Scale => Vector2f::from(self.tf.scale).into(),
+            TweenProperty::Origin => Vector2f::from(self.tf.origin).into(),
+            TweenProperty::Color => self.fill_color().into(),
+        })
+    }
+    fn set_tween_value(&mut self, property: TweenProperty, value: TweenValue) {
+        match (property, value) {
+            (TweenProperty::Position, TweenValue::Vector(v)) => self.tf.position = v.into(),
+            (TweenProperty::Rotation, TweenValue::Float(v)) => self.tf.rotation = v,
+            (TweenProperty::Scale, TweenValue::Vector(v)) => self.tf.scale = v.into(),
+            (TweenProperty::Origin, TweenValue::Vector(v)) => self.tf.origin = v.into(),
+            (TweenProperty::Color, TweenValue::Color(v)) => self.set_fill_color(v),
+            _ => {}
+        }
+    }
+}
+
+impl Tweenable for View {
+    fn tween_value(&self, property: TweenProperty) -> Option<TweenValue> {
+        match property {
+            TweenProperty::Position => Some(self.center().into()),
+            TweenProperty::Rotation => Some(self.rotation().into()),
+            TweenProperty::Scale => Some(self.size().into()),
+            TweenProperty::Origin | TweenProperty::Color => None,
+        }
+    }
+    fn set_tween_value(&mut self, property: TweenProperty, value: TweenValue) {
+        match (property, value) {
+            (TweenProperty::Position, TweenValue::Vector(v)) => self.set_center(v),
+            (TweenProperty::Rotation, TweenValue::Float(v)) => self.set_rotation(v),
+            (TweenProperty::Scale, TweenValue::Vector(v)) => self.set_size(v),
+            _ => {}
+        }
+    }
+}
+
+/// Where a property tween ends, relative to the value it starts from
+#[derive(Debug, Clone, Copy)]
+enum End {
+    To(TweenValue),
+    By(TweenValue),
+    Times(f32),
+}
+
+enum Kind<T> {
+    Property {
+        property: TweenProperty,
+        end: End,
+        ease: Ease,
+        duration: Time,
+        /// The value of the property when the tween started
+        start: Option<TweenValue>,
+    },
+    Wait(Time),
+    Sequence(Vec<Tween<T>>),
+    Parallel(Vec<Tween<T>>),
+}
+
+type Callback<T> = Box<dyn FnMut(&mut T)>;
+
+/// An animation of the properties of a [`Tweenable`] object of type `T` over time.
+///
+/// A tween is either a single property going from its current value to a target value with
+/// an [`Ease`] curve, a [`wait`](Self::wait), or a [`sequence`](Self::sequence) or
+/// [`parallel`](Self::parallel) group of tweens. Any of them can be
+/// [delayed](Self::with_delay), [repeated](Self::repeat), played back and forth
+/// ([`yoyo`](Self::yoyo)), and call a function when it [completes](Self::on_complete).
+///
+/// Property tweens start from the value the property has when they start, so tweens in a
+/// sequence carry on from each other.
+///
+/// The tween only holds the playback state, and the target is passed to
+/// [`update`](Self::update). Properties the target doesn't have are left alone.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sf2g::{graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let mut clock = Clock::start().unwrap();
+/// let mut button = RectangleShape::with_size(Vector2f::new(200., 60.));
+/// let mut tween = Tween::move_to(Vector2f::new(300., 200.), Time::milliseconds(400), Ease::BackOut)
+///     .then(Tween::parallel([
+///         Tween::scale_to(Vector2f::new(1.1, 1.1), Time::milliseconds(150), Ease::QuadOut),
+///         Tween::color_to(Color::YELLOW, Time::milliseconds(150), Ease::Linear),
+///     ])
+///     .yoyo(true)
+///     .repeat(2))
+///     .on_complete(|button: &mut RectangleShape| button.set_outline_thickness(2.));
+/// loop {
+///     tween.update(&mut button, clock.restart());
+///     window.draw_rectangle_shape(&button, &RenderStates::DEFAULT);
+///     # break;
+/// }
+/// ```
+pub struct Tween<T> {
+    kind: Kind<T>,
+    delay: Time,
+    /// How many times the tween is played after the first time, or `None` for forever
+    repeats: Option<u32>,
+    yoyo: bool,
+    on_complete: Option<Callback<T>>,
+    completed: bool,
+    /// Time since the start, for the outermost tween
+    elapsed: Time,
+}
+
+impl<T> fmt::Debug for Tween<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("Tween");
+        let _ = match &self.kind {
+            Kind::Property {
+                property,
+                end,
+                ease,
+                duration,
+                ..
+            } => s
+                .field("property", property)
+                .field("end", end)
+                .field("ease", ease)
+                .field("duration", duration),
+            Kind::Wait(duration) => s.field("wait", duration),
+            Kind::Sequence(tweens) => s.field("sequence", tweens),
+            Kind::Parallel(tweens) => s.field("parallel", tweens),
+        };
+        s.field("delay", &self.delay)
+            .field("repeats", &self.repeats)
+            .field("yoyo", &self.yoyo)
+            .field("elapsed", &self.elapsed)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creation
+impl<T> Tween<T> {
+    fn new(kind: Kind<T>) -> Self {
+        Self {
+            kind,
+            delay: Time::ZERO,
+            repeats: Some(0),
+            yoyo: false,
+            on_complete: None,
+            completed: false,
+            elapsed: Time::ZERO,
+        }
+    }
+    fn property(property: TweenProperty, end: End, duration: Time, ease: Ease) -> Self {
+        Self::new(Kind::Property {
+            property,
+            end,
+            ease,
+            duration,
+            start: None,
+        })
+    }
+    /// Animate `property` to `value`.
+    #[must_use]
+    pub fn to(
+        property: TweenProperty,
+        value: impl Into<TweenValue>,
+        duration: Time,
+        ease: Ease,
+    ) -> Self {
+        Self::property(property, End::To(value.into()), duration, ease)
+    }
+    /// Animate `property` by adding `offset` to it.
+    #[must_use]
+    pub fn by(
+        property: TweenProperty,
+        offset: impl Into<TweenValue>,
+        duration: Time,
+        ease: Ease,
+    ) -> Self {
+        Self::property(property, End::By(offset.into()), duration, ease)
+    }
+    /// Move to `position`. For a [`View`], move its center.
+    #[must_use]
+    pub fn move_to(position: Vector2f, duration: Time, ease: Ease) -> Self {
+        Self::to(TweenProperty::Position, position, duration, ease)
+    }
+    /// Move by `offset`.
+    #[must_use]
+    pub fn move_by(offset: Vector2f, duration: Time, ease: Ease) -> Self {
+        Self::by(TweenProperty::Position, offset, duration, ease)
+    }
+    /// Rotate to `angle`, in degrees.
+    ///
+    /// Angles are interpolated as numbers, so rotating from 350 to 10 turns 340 degrees
+    /// counterclockwise. Use [`rotate_by`](Self::rotate_by) to choose the direction.
+    #[must_use]
+    pub fn rotate_to(angle: f32, duration: Time, ease: Ease) -> Self {
+        Self::to(TweenProperty::Rotation, angle, duration, ease)
+    }
+    /// Rotate by `angle`, in degrees, clockwise.
+    #[must_use]
+    pub fn rotate_by(angle: f32, duration: Time, ease: Ease) -> Self {
+        Self::by(TweenProperty::Rotation, angle, duration, ease)
+    }
+    /// Scale to `scale`.
+    #[must_use]
+    pub fn scale_to(scale: Vector2f, duration: Time, ease: Ease) -> Self {
+        Self::to(TweenProperty::Scale, scale, duration, ease)
+    }
+    /// Move the origin to `origin`.
+    #[must_use]
+    pub fn origin_to(origin: Vector2f, duration: Time, ease: Ease) -> Self {
+        Self::to(TweenProperty::Origin, origin, duration, ease)
+    }
+    /// Change the color to `color`. For shapes and texts, this is the fill color.
+    #[must_use]
+    pub fn color_to(color: Color, duration: Time, ease: Ease) -> Self {
+        Self::to(TweenProperty::Color, color, duration, ease)
+    }
+    /// Zoom a [`View`] by `factor`, like [`View::zoom`]: its size is multiplied by `factor`.
+    #[must_use]
+    pub fn zoom(factor: f32, duration: Time, ease: Ease) -> Self {
+        Self::property(TweenProperty::Scale, End::Times(factor), duration, ease)
+    }
+    /// Do nothing for `duration`.
+    #[must_use]
+    pub fn wait(duration: Time) -> Self {
+        Self::new(Kind::Wait(duration))
+    }
+    /// Play `tweens` one after the other.
+    #[must_use]
+    pub fn sequence(tweens: impl IntoIterator<Item = Self>) -> Self {
+        Self::new(Kind::Sequence(tweens.into_iter().collect()))
+    }
+    /// Play `tweens` at the same time. The group lasts as long as the longest of them.
+    #[must_use]
+    pub fn parallel(tweens: impl IntoIterator<Item = Self>) -> Self {
+        Self::new(Kind::Parallel(tweens.into_iter().collect()))
+    }
+    /// Play `next` after this tween.
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        match self.kind {
+            Kind::Sequence(mut tweens) if self.is_plain() => {
+                tweens.push(next);
+                Self::sequence(tweens)
+            }
+            _ => Self::sequence([self, next]),
+        }
+    }
+    /// Play `other` at the same time as this tween.
+    #[must_use]
+    pub fn with(self, other: Self) -> Self {
+        match self.kind {
+            Kind::Parallel(mut tweens) if self.is_plain() => {
+                tweens.push(other);
+                Self::parallel(tweens)
+            }
+            _ => Self::parallel([self, other]),
+        }
+    }
+    /// Whether the tween has none of the options that would be lost by merging it into a group
+    fn is_plain(&self) -> bool {
+        self.delay == Time::ZERO
+            && self.repeats == Some(0)
+            && !self.yoyo
+            && self.on_complete.is_none()
+    }
+    /// Wait for `delay` before starting.
+    #[must_use]
+    pub fn with_delay(mut self, delay: Time) -> Self {
+        self.delay = delay;
+        self
+    }
+    /// Play the tween `count` more times after the first one.
+    #[must_use]
+    pub fn repeat(mut self, count: u32) -> Self {
+        self.repeats = Some(count);
+        self
+    }
+    /// Play the tween over and over. It never completes.
+    #[must_use]
+    pub fn repeat_forever(mut self) -> Self {
+        self.repeats = None;
+        self
+    }
+    /// Play every other repetition backwards.
+    ///
+    /// With [`repeat(1)`](Self::repeat), the tween goes to its end and comes back.
+    #[must_use]
+    pub fn yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+    /// Call `f` with the target when the tween completes, after all its repetitions.
+    ///
+    /// If the tween is in a group that repeats or plays backwards, `f` is called again each
+    /// time the tween completes forwards. When a single [`update`](Self::update) goes over
+    /// several repetitions of the group, only the repetition it leaves and the one it ends
+    /// in are played, so `f` isn't called for the repetitions in between.
+    #[must_use]
+    pub fn on_complete(mut self, f: impl FnMut(&mut T) + 'static) -> Self {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+}
+
+/// Playback
+impl<T: Tweenable> Tween<T> {
+    /// Advance the tween by `dt`, and update the properties of `target`.
+    pub fn update(&mut self, target: &mut T, dt: Time) {
+        let prev = self.elapsed;
+        self.elapsed += dt;
+        self.seek(target, prev, self.elapsed);
+    }
+    /// Whether the tween completed, after its delay and all its repetitions
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.total().is_some_and(|total| self.elapsed >= total)
+    }
+    /// The time since the tween started, including its delay
+    #[must_use]
+    pub fn elapsed(&self) -> Time {
+        self.elapsed
+    }
+    /// The time it takes to complete the tween, including its delay and repetitions, or
+    /// `None` if it repeats forever
+    #[must_use]
+    pub fn duration(&self) -> Option<Time> {
+        self.total()
+    }
+    /// Go back to the start, so that the tween plays again from the current values of the
+    /// properties of the target.
+    pub fn restart(&mut self) {
+        self.elapsed = Time::ZERO;
+        self.reset();
+    }
+    fn reset(&mut self) {
+        self.completed = false;
+        match &mut self.kind {
+            Kind::Property { start, .. } => *start = None,
+            Kind::Wait(_) => {}
+            Kind::Sequence(tweens) | Kind::Parallel(tweens) => {
+                tweens.iter_mut().for_each(Self::reset);
+            }
+        }
+    }
+    /// The duration of one repetition
+    fn cycle(&self) -> Option<Time> {
+        match &self.kind {
+            Kind::Property { duration, .. } | Kind::Wait(duration) => Some(*duration),
+            Kind::Sequence(tweens) => tweens
+                .iter()
+                .try_fold(Time::ZERO, |acc, tween| Some(acc + tween.total()?)),
+            Kind::Parallel(tweens) => tweens
+                .iter()
+                .try_fold(Time::ZERO, |acc, tween| Some(acc.max(tween.total()?))),
+        }
+    }
+    /// The duration of the tween, with its delay and repetitions
+    fn total(&self) -> Option<Time> {
+        Some(self.delay + self.cycle()? * (i64::from(self.repeats?) + 1))
+    }
+    /// Move the tween from `prev` to `now`, in time relative to the start of the tween.
+    fn seek(&mut self, target: &mut T, prev: Time, now: Time) {
+        let total = self.total();
+        if now < prev {
+            self.completed &= total.is_some_and(|total| now >= total);
+        }
+        let (lo, hi) = (prev.min(now), prev.max(now));
+        if hi >= Time::ZERO && total.is_none_or(|total| lo <= total) {
+            let (prev, now) = (prev - self.delay, now - self.delay);
+            let (prev_cycle, prev_t) = self.local(prev);
+            let (now_cycle, now_t) = self.local(now);
+            if prev_cycle == now_cycle {
+                self.seek_cycle(target, prev_t, now_t);
+            } else {
+                // Finish the previous repetition, and play the current one from its start
+                let (end, start) = if now_cycle > prev_cycle {
+                    (self.cycle_end(prev_cycle), self.cycle_start(now_cycle))
+                } else {
+                    (self.cycle_start(prev_cycle), self.cycle_end(now_cycle))
+                };
+                self.seek_cycle(target, prev_t, end);
+                if end != start {
+                    // Without yoyo, jump back through the repetition, so that every property
+                    // in a group starts it from its start value, not only the first ones
+                    self.seek_cycle(target, end, start);
+                }
+                self.seek_cycle(target, start, now_t);
+            }
+        }
+        if total.is_some_and(|total| now >= total) && !self.completed {
+            self.completed = true;
+            if let Some(f) = &mut self.on_complete {
+                f(target);
+            }
+        }
+    }
+    /// The repetition playing at time `t` after the delay, and the time in that repetition
+    fn local(&self, t: Time) -> (u64, Time) {
+        let t = t.max(Time::ZERO);
+        // A group with a child that repeats forever plays its first repetition forever
+        let Some(cycle) = self.cycle() else {
+            return (0, t);
+        };
+        if cycle <= Time::ZERO {
+            return (0, Time::ZERO);
+        }
+        #[expect(clippy::cast_sign_loss, reason = "Both times are positive")]
+        let mut index = (t.as_microseconds() / cycle.as_microseconds()) as u64;
+        let mut local = t % cycle;
+        if let Some(repeats) = self.repeats.map(u64::from) {
+            if index > repeats {
+                index = repeats;
+                local = cycle;
+            }
+        }
+        if self.yoyo && index % 2 == 1 {
+            local = cycle - local;
+        }
+        (index, local)
+    }
+    /// The time in the repetition `index` where it starts
+    fn cycle_start(&self, index: u64) -> Time {
+        if self.yoyo && index % 2 == 1 {
+            self.cycle().unwrap_or_default()
+        } else {
+            Time::ZERO
+        }
+    }
+    /// The time in the repetition `index` where it ends
+    fn cycle_end(&self, index: u64) -> Time {
+        if self.yoyo && index % 2 == 1 {
+            Time::ZERO
+        } else {
+            self.cycle().unwrap_or_default()
+        }
+    }
+    /// Move a single repetition of the tween from `prev` to `now`.
+    fn seek_cycle(&mut self, target: &mut T, prev: Time, now: Time) {
+        match &mut self.kind {
+            Kind::Property {
+                property,
+                end,
+                ease,
+                duration,
+                start,
+            } => {
+                let from = match *start {
+                    Some(from) => from,
+                    None => {
+                        let Some(from) = target.tween_value(*property) else {
+                            return;
+                        };
+                        *start = Some(from);
+                        from
+                    }
+                };
+                let to = match *end {
+                    End::To(value) => value,
+                    End::By(offset) => from.offset(offset),
+                    End::Times(factor) => from.scaled(factor),
+                };
+                let t = if *duration > Time::ZERO {
+                    now / *duration
+                } else {
+                    1.
+                };
+                target.set_tween_value(*property, from.lerp(to, ease.apply(t)));
+            }
+            Kind::Wait(_) => {}
+            Kind::Sequence(tweens) => {
+                let mut offsets = Vec::with_capacity(tweens.len());
+                let mut offset = Time::ZERO;
+                for tween in tweens.iter() {
+                    offsets.push(offset);
+                    match tween.total() {
+                        Some(total) => offset += total,
+                        None => break,
+                    }
+                }
+                let mut seek = |(tween, offset): (&mut Self, Time)| {
+                    tween.seek(target, prev - offset, now - offset);
+                };
+                // Going backwards, the earliest tweens are the last to set their properties
+                if now < prev {
+                    tweens.iter_mut().zip(offsets).rev().for_each(&mut seek);
+                } else {
+                    tweens.iter_mut().zip(offsets).for_each(&mut seek);
+                }
+            }
+            Kind::Parallel(tweens) => {
+                for tween in tweens {
+                    tween.seek(target, prev, now);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+use std::{cell::Cell, rc::Rc};
+
+#[cfg(test)]
+#[derive(Debug, Default)]
+struct Target {
+    rotation: f32,
+    position: Vector2f,
+}
+
+#[cfg(test)]
+impl Tweenable for Target {
+    fn tween_value(&self, property: TweenProperty) -> Option<TweenValue> {
+        match property {
+            TweenProperty::Rotation => Some(self.rotation.into()),
+            TweenProperty::Position => Some(self.position.into()),
+            _ => None,
+        }
+    }
+    fn set_tween_value(&mut self, property: TweenProperty, value: TweenValue) {
+        match (property, value) {
+            (TweenProperty::Rotation, TweenValue::Float(v)) => self.rotation = v,
+            (TweenProperty::Position, TweenValue::Vector(v)) => self.position = v,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+fn ms(ms: i32) -> Time {
+    Time::milliseconds(ms)
+}
+
+#[cfg(test)]
+fn counter() -> (Rc<Cell<u32>>, impl FnMut(&mut Target) + 'static) {
+    let count = Rc::new(Cell::new(0));
+    let inc = Rc::clone(&count);
+    (count, move |_: &mut Target| inc.set(inc.get() + 1))
+}
+
+#[cfg(test)]
+#[track_caller]
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{a} != {b}");
+}
+
+#[test]
+fn test_yoyo_repeat_end() {
+    // An even number of plays ends where it started, an odd number at the end
+    for (repeats, end) in [(0, 100.), (1, 0.), (2, 100.), (3, 0.)] {
+        let mut target = Target::default();
+        let mut tween = Tween::rotate_to(100., ms(100), Ease::Linear)
+            .yoyo(true)
+            .repeat(repeats);
+        tween.update(&mut target, ms(30));
+        assert_near(target.rotation, 30.);
+        tween.update(&mut target, ms(10_000));
+        assert_near(target.rotation, end);
+        assert!(tween.is_finished());
+    }
+    // Halfway through the second play, going back
+    let mut target = Target::default();
+    let mut tween = Tween::rotate_to(100., ms(100), Ease::Linear)
+        .yoyo(true)
+        .repeat(1);
+    tween.update(&mut target, ms(150));
+    assert_near(target.rotation, 50.);
+}
+
+#[test]
+fn test_sequence_large_step() {
+    let mut target = Target::default();
+    let mut tween = Tween::rotate_to(90., ms(100), Ease::Linear)
+        .then(Tween::wait(ms(50)))
+        .then(Tween::move_to(
+            Vector2f::new(10., 20.),
+            ms(100),
+            Ease::Linear,
+        ))
+        .then(Tween::rotate_by(-30., ms(100), Ease::Linear));
+    // Over the first three children at once, into the last one
+    tween.update(&mut target, ms(300));
+    assert_near(target.rotation, 75.);
+    assert_eq!(target.position, Vector2f::new(10., 20.));
+    tween.update(&mut target, ms(1000));
+    assert_near(target.rotation, 60.);
+    assert!(tween.is_finished());
+}
+
+#[test]
+fn test_repeat_resets_siblings() {
+    let mut target = Target::default();
+    let mut tween = Tween::rotate_to(90., ms(100), Ease::Linear)
+        .then(Tween::move_to(
+            Vector2f::new(10., 0.),
+            ms(100),
+            Ease::Linear,
+        ))
+        .repeat(1);
+    tween.update(&mut target, ms(190));
+    assert_near(target.position.x, 9.);
+    // Into the second repetition, before the move starts again
+    tween.update(&mut target, ms(60));
+    assert_near(target.rotation, 45.);
+    assert_near(target.position.x, 0.);
+    tween.update(&mut target, ms(1000));
+    assert_near(target.rotation, 90.);
+    assert_near(target.position.x, 10.);
+}
+
+#[test]
+fn test_forever_child() {
+    let mut target = Target::default();
+    let mut tween = Tween::move_to(Vector2f::new(10., 0.), ms(100), Ease::Linear)
+        .then(Tween::rotate_by(90., ms(100), Ease::Linear).repeat_forever());
+    tween.update(&mut target, ms(50));
+    assert_near(target.position.x, 5.);
+    tween.update(&mut target, ms(100));
+    assert_near(target.position.x, 10.);
+    assert_near(target.rotation, 45.);
+    tween.update(&mut target, ms(1000));
+    assert_near(target.rotation, 45.);
+    assert!(!tween.is_finished());
+
+    let mut target = Target::default();
+    let mut tween = Tween::parallel([
+        Tween::rotate_by(90., ms(100), Ease::Linear).repeat_forever(),
+        Tween::move_to(Vector2f::new(10., 0.), ms(200), Ease::Linear),
+    ]);
+    tween.update(&mut target, ms(150));
+    assert_near(target.rotation, 45.);
+    assert_near(target.position.x, 7.5);
+    tween.update(&mut target, ms(10_000));
+    assert_near(target.position.x, 10.);
+    assert_eq!(tween.duration(), None);
+}
+
+#[test]
+fn test_on_complete_count() {
+    let (outer, on_outer) = counter();
+    let (inner, on_inner) = counter();
+    let mut target = Target::default();
+    let mut tween = Tween::rotate_to(90., ms(100), Ease::Linear)
+        .on_complete(on_inner)
+        .then(Tween::wait(ms(100)))
+        .repeat(2)
+        .on_complete(on_outer);
+    tween.update(&mut target, ms(50));
+    assert_eq!((inner.get(), outer.get()), (0, 0));
+    tween.update(&mut target, ms(100));
+    assert_eq!((inner.get(), outer.get()), (1, 0));
+    // The inner tween completes once per repetition, the outer one once
+    for _ in 0..100 {
+        tween.update(&mut target, ms(10));
+    }
+    assert_eq!((inner.get(), outer.get()), (3, 1));
+    tween.update(&mut target, ms(1000));
+    assert_eq!((inner.get(), outer.get()), (3, 1));
+    // Restarting plays it again. A single step over the 3 repetitions only plays the
+    // first and the last one, so the inner tween completes twice, not 3 times.
+    tween.restart();
+    tween.update(&mut target, ms(10_000));
+    assert_eq!((inner.get(), outer.get()), (3 + 2, 2));
+}
@@ -0,0 +1,315 @@
+use crate::{
+    SfResult,
+    graphics::{Color, Shader, Texture, Transform, glsl},
+    system::Time,
+};
+
+/// A set of uniforms that can be applied to a [`Shader`] at once.
+///
+/// Usually implemented with `#[derive(Uniforms)]`, which requires the `derive` feature.
+/// Each field is mapped to the uniform of the same name, and its type determines the
+/// GLSL type of the uniform, the same way as for [`IntoUniform`].
+/// Array uniforms can be `[T; N]`, `Vec<T>` or `&[T]` fields, and `Option<T>` fields are
+/// only set if they are `Some`.
+///
+/// The following attributes are supported:
+///
+/// - `#[uniforms(prefix = "u_")]` on the struct prepends a prefix to every uniform name
+/// - `#[uniform(rename = "name")]` on a field uses a different uniform name (the prefix still
+///   applies)
+/// - `#[uniform(skip)]` on a field ignores it
+///
+/// # Usage example
+///
+#[cfg_attr(feature = "derive", doc = "```no_run")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # fn main() -> sf2g::SfResult<()> {
+/// use sf2g::graphics::{glsl, Color, Shader, Texture, Uniforms};
+///
+/// #[derive(Uniforms)]
+/// #[uniforms(prefix = "u_")]
+/// struct Lighting<'t> {
+///     ambient: Color,
+///     light_pos: [glsl::Vec2; 4],
+///     intensity: f32,
+///     #[uniform(rename = "normal_map")]
+///     normals: &'t Texture,
+///     #[uniform(skip)]
+///     name: String,
+/// }
+///
+/// # let (shader, normals): (&mut Shader, &Texture) = unimplemented!();
+/// let lighting = Lighting {
+///     ambient: Color::rgb(40, 40, 60),
+///     light_pos: [glsl::Vec2::new(0., 0.); 4],
+///     intensity: 0.8,
+///     normals,
+///     name: "torches".into(),
+/// };
+/// assert!(Lighting::missing_uniforms(shader)?.is_empty());
+/// shader.set_uniforms(&lighting)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Uniforms<'texture> {
+    /// Names of the uniforms set by [`apply_uniforms`](Uniforms::apply_uniforms)
+    const NAMES: &'static [&'static str];
+
+    /// Set all the uniforms on `shader`.
+    fn apply_uniforms(&self, shader: &mut Shader<'texture>) -> SfResult<()>;
+
+    /// Check the uniform names against the compiled program of `shader`, and
+    /// return the ones it doesn't have.
+    ///
+    /// See [`Shader::has_uniform`] for the caveats.
+    fn missing_uniforms(shader: &Shader<'_>) -> SfResult<Vec<&'static str>> {
+        let mut missing = Vec::new();
+        for &name in Self::NAMES {
+            if !shader.has_uniform(name)? {
+                missing.push(name);
+            }
+        }
+        Ok(missing)
+    }
+}
+
+/// A value that can be set as a uniform with [`Shader::set_uniform`].
+///
+/// | Type                                                | GLSL type        |
+/// |-----------------------------------------------------|------------------|
+/// | `f32`, `i32`, `bool`                                | `float`, `int`, `bool` |
+/// | [`glsl::Vec2`] ([`Vector2f`]), [`glsl::Vec3`] ([`Vector3f`]), [`glsl::Vec4`] | `vec2`, `vec3`, `vec4` |
+/// | [`glsl::IVec2`] ([`Vector2i`]), [`glsl::IVec3`] ([`Vector3i`]), [`glsl::IVec4`] | `ivec2`, `ivec3`, `ivec4` |
+/// | [`glsl::BVec2`], [`glsl::BVec3`], [`glsl::BVec4`]   | `bvec2`, `bvec3`, `bvec4` |
+/// | [`glsl::Mat3`], [`glsl::Mat4`]                      | `mat3`, `mat4`   |
+/// | [`Color`]                                           | `vec4`, with components normalized to `[0 .. 1]` |
+/// | [`Transform`]                                       | `mat4` (convert with [`glsl::Mat3::from`] for `mat3`) |
+/// | [`Time`]                                            | `float`, in seconds |
+/// | `&Texture`                                          | `sampler2D`      |
+/// | [`CurrentTexture`]                                  | `sampler2D`, bound to the texture of the object being drawn |
+/// | `&[T]`, `&[T; N]`, `&Vec<T>` where `T:` [`UniformArrayElem`] | `T[]` |
+///
+/// [`Vector2f`]: crate::system::Vector2f
+/// [`Vector3f`]: crate::system::Vector3f
+/// [`Vector2i`]: crate::system::Vector2i
+/// [`Vector3i`]: crate::system::Vector3i
+pub trait IntoUniform<'texture> {
+    /// Set `self` as the value of the uniform `name` of `shader`.
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()>;
+}
+
+/// Maps a `sampler2D` uniform to the texture of the object being drawn.
+///
+/// See [`Shader::set_uniform_current_texture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CurrentTexture;
+
+/// A type that can be the element of an array uniform.
+///
+/// Implemented for `f32`, float vectors, matrices, [`Color`] (as `vec4`)
+/// and [`Transform`] (as `mat4`).
+pub trait UniformArrayElem: Sized {
+    /// Set `array` as the value of the array uniform `name` of `shader`.
+    fn set_array(array: &[Self], shader: &mut Shader<'_>, name: &str) -> SfResult<()>;
+}
+
+macro_rules! impl_into_uniform {
+    ($($t:ty => $method:ident,)*) => {
+        $(
+            impl<'texture> IntoUniform<'texture> for $t {
+                fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+                    shader.$method(name, self)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_uniform! {
+    f32 => set_uniform_float,
+    i32 => set_uniform_int,
+    bool => set_uniform_bool,
+    glsl::Vec2 => set_uniform_vec2,
+    glsl::Vec3 => set_uniform_vec3,
+    glsl::Vec4 => set_uniform_vec4,
+    glsl::IVec2 => set_uniform_ivec2,
+    glsl::IVec3 => set_uniform_ivec3,
+    glsl::IVec4 => set_uniform_ivec4,
+    glsl::BVec2 => set_uniform_bvec2,
+    glsl::BVec3 => set_uniform_bvec3,
+    glsl::BVec4 => set_uniform_bvec4,
+    glsl::Mat3 => set_uniform_mat3,
+    glsl::Mat4 => set_uniform_mat4,
+    Color => set_uniform_vec4,
+    Transform => set_uniform_mat4,
+}
+
+impl<'texture> IntoUniform<'texture> for Time {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        shader.set_uniform_float(name, self.as_seconds())
+    }
+}
+
+impl<'a: 'texture, 'texture> IntoUniform<'texture> for &'a Texture {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        shader.set_uniform_texture(name, self)
+    }
+}
+
+impl<'texture> IntoUniform<'texture> for CurrentTexture {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        shader.set_uniform_current_texture(name)
+    }
+}
+
+impl<'texture, T: UniformArrayElem> IntoUniform<'texture> for &[T] {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        T::set_array(self, shader, name)
+    }
+}
+
+impl<'texture, T: UniformArrayElem, const N: usize> IntoUniform<'texture> for &[T; N] {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        T::set_array(self, shader, name)
+    }
+}
+
+impl<'texture, T: UniformArrayElem> IntoUniform<'texture> for &Vec<T> {
+    fn set_on(self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        T::set_array(self, shader, name)
+    }
+}
+
+macro_rules! impl_uniform_array_elem {
+    ($($t:ty => $method:ident,)*) => {
+        $(
+            impl UniformArrayElem for $t {
+                fn set_array(array: &[Self], shader: &mut Shader<'_>, name: &str) -> SfResult<()> {
+                    shader.$method(name, array)
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_array_elem! {
+    f32 => set_uniform_array_float,
+    glsl::Vec2 => set_uniform_array_vec2,
+    glsl::Vec3 => set_uniform_array_vec3,
+    glsl::Vec4 => set_uniform_array_vec4,
+    glsl::Mat3 => set_uniform_array_mat3,
+    glsl::Mat4 => set_uniform_array_mat4,
+}
+
+impl UniformArrayElem for Color {
+    fn set_array(array: &[Self], shader: &mut Shader<'_>, name: &str) -> SfResult<()> {
+        let array: Vec<glsl::Vec4> = array.iter().map(|&c| c.into()).collect();
+        shader.set_uniform_array_vec4(name, &array)
+    }
+}
+
+impl UniformArrayElem for Transform {
+    fn set_array(array: &[Self], shader: &mut Shader<'_>, name: &str) -> SfResult<()> {
+        let array: Vec<glsl::Mat4> = array.iter().map(|&t| t.into()).collect();
+        shader.set_uniform_array_mat4(name, &array)
+    }
+}
+
+/// A struct field that can be set as a single uniform. Used by `#[derive(Uniforms)]`.
+#[doc(hidden)]
+pub trait UniformField<'texture> {
+    fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()>;
+}
+
+macro_rules! impl_uniform_field {
+    ($($t:ty),*) => {
+        $(
+            impl<'texture> UniformField<'texture> for $t {
+                fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+                    self.set_on(shader, name)
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_field!(
+    f32,
+    i32,
+    bool,
+    glsl::Vec2,
+    glsl::Vec3,
+    glsl::Vec4,
+    glsl::IVec2,
+    glsl::IVec3,
+    glsl::IVec4,
+    glsl::BVec2,
+    glsl::BVec3,
+    glsl::BVec4,
+    glsl::Mat3,
+    glsl::Mat4,
+    Color,
+    Transform,
+    Time,
+    CurrentTexture
+);
+
+impl<'a: 'texture, 'texture> UniformField<'texture> for &'a Texture {
+    fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        self.set_on(shader, name)
+    }
+}
+
+impl<'texture, T: UniformField<'texture>> UniformField<'texture> for Option<T> {
+    fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        match self {
+            Some(value) => value.set_field(shader, name),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'texture, T: UniformArrayElem, const N: usize> UniformField<'texture> for [T; N] {
+    fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        self.set_on(shader, name)
+    }
+}
+
+impl<'texture, T: UniformArrayElem> UniformField<'texture> for Vec<T> {
+    fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        self.set_on(shader, name)
+    }
+}
+
+impl<'texture, T: UniformArrayElem> UniformField<'texture> for &[T] {
+    fn set_field(&self, shader: &mut Shader<'texture>, name: &str) -> SfResult<()> {
+        (*self).set_on(shader, name)
+    }
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_uniforms() {
+    use crate::graphics::Uniforms;
+
+    #[derive(Uniforms)]
+    #[uniforms(prefix = "u_")]
+    struct Lighting<'t> {
+        ambient: Color,
+        #[uniform(rename = "lights")]
+        light_pos: [glsl::Vec2; 4],
+        #[uniform(skip)]
+        _name: String,
+        normals: Option<&'t Texture>,
+        r#type: i32,
+    }
+
+    #[derive(Uniforms)]
+    struct Pair<T>(#[uniform(rename = "a")] T);
+
+    assert_eq!(
+        Lighting::NAMES,
+        ["u_ambient", "u_lights", "u_normals", "u_type"]
+    );
+    assert_eq!(Pair::<f32>::NAMES, ["a"]);
+}
@@ -1,8 +1,9 @@
 use crate::{
-    IntoSfResult, SfResult,
+    SfResult,
     cpp::FBox,
     ffi::graphics as ffi,
     graphics::{PrimitiveType, Vertex},
+    sf_call,
 };
 
 /// Usage specifiers for a [`VertexBuffer`]
@@ -40,7 +41,7 @@ impl VertexBuffer {
         vertex_count: usize,
         usage: VertexBufferUsage,
     ) -> SfResult<FBox<Self>> {
-        let mut new = FBox::new(unsafe { ffi::sfVertexBuffer_new() }).into_sf_result()?;
+        let mut new = sf_call(|| FBox::new(unsafe { ffi::sfVertexBuffer_new() }))?;
         new.set_usage(usage);
         new.set_primitive_type(primitive_type);
         new.recreate(vertex_count)?;
@@ -57,7 +58,7 @@ impl VertexBuffer {
     /// Don't forget to recreate with a non-zero value when graphics memory should be
     /// allocated again.
     pub fn recreate(&mut self, vertex_count: usize) -> SfResult<()> {
-        unsafe { ffi::sfVertexBuffer_create(self, vertex_count) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfVertexBuffer_create(self, vertex_count) })
     }
 
     /// Return the vertex count of a vertex buffer
@@ -93,10 +94,9 @@ impl VertexBuffer {
     ///
     /// Return True if the update was successful
     pub fn update(&mut self, vertices: &[Vertex], offset: u32) -> SfResult<()> {
-        unsafe {
+        sf_call(|| unsafe {
             ffi::sfVertexBuffer_update(self, vertices.as_ptr().cast(), vertices.len(), offset)
-        }
-        .into_sf_result()
+        })
     }
 
     /// Copy the contents of another buffer into this buffer.
@@ -104,7 +104,7 @@ impl VertexBuffer {
     /// # Arguments
     /// * other - Vertex buffer whose contents to copy into this vertex buffer
     pub fn update_from_vertex_buffer(&mut self, other: &VertexBuffer) -> SfResult<()> {
-        unsafe { ffi::sfVertexBuffer_updateFromVertexBuffer(self, other) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfVertexBuffer_updateFromVertexBuffer(self, other) })
     }
 
     /// Swap the contents of this vertex buffer with those of another.
@@ -1,50 +1,76 @@
-use {
-    crate::{
-        IntoSfResult, SfResult,
-        cpp::{FBox, RawDefault},
-        ffi::graphics as ffi,
-        graphics::FloatRect,
-        system::Vector2f,
-    },
-    std::ptr::NonNull,
+use crate::{
+    graphics::{FloatRect, IntRect, Transform},
+    system::{Vector2f, Vector2i, Vector2u},
 };
 
-decl_opaque! {
 /// 2D camera that defines what region is shown on screen
 ///
 /// This is a very powerful concept: you can scroll,
 /// rotate or zoom the entire scene without altering
 /// the way that your drawable objects are drawn.
-pub View;
+///
+/// `View` is a plain value: it can be copied, stored in constants, and used for
+/// coordinate math without a render target, using [`pixel_to_world`] and
+/// [`world_to_pixel`]. It only gets converted to an SFML view when it is
+/// applied with [`RenderTarget::set_view`].
+///
+/// [`pixel_to_world`]: View::pixel_to_world
+/// [`world_to_pixel`]: View::world_to_pixel
+/// [`RenderTarget::set_view`]: crate::graphics::RenderTarget::set_view
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View {
+    center: Vector2f,
+    size: Vector2f,
+    rotation: f32,
+    viewport: FloatRect,
 }
 
 /// Creation
 impl View {
-    /// Creates a default `View` of (0, 0, 1000, 1000)
-    pub fn new() -> SfResult<FBox<Self>> {
-        FBox::new(unsafe { ffi::sfView_new() }).into_sf_result()
-    }
     /// Creates a view with position and size
     ///
     /// # Arguments
     /// * center - The center of the view
     /// * size - The size of the view
     #[must_use]
-    pub fn with_center_and_size(center: Vector2f, size: Vector2f) -> FBox<View> {
-        let mut view: FBox<View> = Default::default();
-        view.set_center(center);
-        view.set_size(size);
-        view
+    pub const fn new(center: Vector2f, size: Vector2f) -> Self {
+        Self {
+            center,
+            size,
+            rotation: 0.,
+            viewport: FloatRect::new(0., 0., 1., 1.),
+        }
+    }
+
+    /// Creates a view with position and size
+    ///
+    /// Same as [`View::new`].
+    #[must_use]
+    pub const fn with_center_and_size(center: Vector2f, size: Vector2f) -> Self {
+        Self::new(center, size)
     }
 
     /// Construct a view from a rectangle
     ///
     /// # Arguments
     /// * rectangle - The rectangle defining the zone to display
-    pub fn from_rect(rectangle: FloatRect) -> SfResult<FBox<View>> {
-        let mut new = Self::new()?;
-        new.reset(rectangle);
-        Ok(new)
+    #[must_use]
+    pub const fn from_rect(rectangle: FloatRect) -> Self {
+        Self::new(
+            Vector2f::new(
+                rectangle.left + rectangle.width / 2.,
+                rectangle.top + rectangle.height / 2.,
+            ),
+            Vector2f::new(rectangle.width, rectangle.height),
+        )
+    }
+}
+
+impl Default for View {
+    /// A view of (0, 0, 1000, 1000), like SFML's default view
+    fn default() -> Self {
+        Self::new(Vector2f::new(500., 500.), Vector2f::new(1000., 1000.))
     }
 }
 
@@ -52,33 +78,33 @@ impl View {
 impl View {
     /// Get the current orientation of a view
     ///
-    /// Return the rotation angle of the view, in degrees
+    /// Return the rotation angle of the view, in degrees, in the range `[0, 360)`
     #[must_use]
-    pub fn rotation(&self) -> f32 {
-        unsafe { ffi::sfView_getRotation(self) }
+    pub const fn rotation(&self) -> f32 {
+        self.rotation
     }
     /// Get the center of a view
     ///
     /// Return the center of the view
     #[must_use]
-    pub fn center(&self) -> Vector2f {
-        unsafe { ffi::sfView_getCenter(self) }
+    pub const fn center(&self) -> Vector2f {
+        self.center
     }
 
     /// Get the size of a view
     ///
     /// Return the size of the view
     #[must_use]
-    pub fn size(&self) -> Vector2f {
-        unsafe { ffi::sfView_getSize(self) }
+    pub const fn size(&self) -> Vector2f {
+        self.size
     }
 
     /// Get the target viewport rectangle of a view
     ///
     /// Return the viewport rectangle, expressed as a factor of the target size
     #[must_use]
-    pub fn viewport(&self) -> FloatRect {
-        unsafe { ffi::sfView_getViewport(self) }
+    pub const fn viewport(&self) -> FloatRect {
+        self.viewport
     }
 }
 
@@ -91,7 +117,10 @@ impl View {
     /// # Arguments
     /// * angle - New angle, in degrees
     pub fn set_rotation(&mut self, angle: f32) {
-        unsafe { ffi::sfView_setRotation(self, angle) }
+        self.rotation = angle % 360.;
+        if self.rotation < 0. {
+            self.rotation += 360.;
+        }
     }
 
     /// Rotate a view relatively to its current orientation
@@ -99,7 +128,7 @@ impl View {
     /// # Arguments
     /// * angle - Angle to rotate, in degrees
     pub fn rotate(&mut self, angle: f32) {
-        unsafe { ffi::sfView_rotate(self, angle) }
+        self.set_rotation(self.rotation + angle);
     }
 
     /// Resize a view rectangle relatively to its current size
@@ -115,7 +144,7 @@ impl View {
     /// # Arguments
     /// * factor - Zoom factor to apply
     pub fn zoom(&mut self, factor: f32) {
-        unsafe { ffi::sfView_zoom(self, factor) }
+        self.size *= factor;
     }
 
     /// Set the center of a view
@@ -123,7 +152,7 @@ impl View {
     /// # Arguments
     /// * center - New center
     pub fn set_center<C: Into<Vector2f>>(&mut self, center: C) {
-        unsafe { ffi::sfView_setCenter(self, center.into()) }
+        self.center = center.into();
     }
 
     /// Set the size of a view
@@ -131,7 +160,7 @@ impl View {
     /// # Arguments
     /// * size - New size of the view
     pub fn set_size<S: Into<Vector2f>>(&mut self, size: S) {
-        unsafe { ffi::sfView_setSize(self, size.into()) }
+        self.size = size.into();
     }
 
     /// Move a view relatively to its current position
@@ -139,7 +168,7 @@ impl View {
     /// # Arguments
     /// * offset - Offset
     pub fn move_<O: Into<Vector2f>>(&mut self, offset: O) {
-        unsafe { ffi::sfView_move(self, offset.into()) }
+        self.center += offset.into();
     }
 
     /// Set the target viewport of a view
@@ -154,7 +183,7 @@ impl View {
     /// # Arguments
     /// * viewport - New viewport rectangle
     pub fn set_viewport(&mut self, viewport: FloatRect) {
-        unsafe { ffi::sfView_setViewport(self, viewport) }
+        self.viewport = viewport;
     }
 
     /// Reset a view to the given rectangle
@@ -164,26 +193,107 @@ impl View {
     /// # Arguments
     /// * rectangle - Rectangle defining the zone to display
     pub fn reset(&mut self, rectangle: FloatRect) {
-        unsafe { ffi::sfView_reset(self, rectangle) }
+        let View { center, size, .. } = Self::from_rect(rectangle);
+        self.center = center;
+        self.size = size;
+        self.rotation = 0.;
     }
 }
 
-impl ToOwned for View {
-    type Owned = FBox<Self>;
-    fn to_owned(&self) -> Self::Owned {
-        let view = unsafe { ffi::sfView_cpy(self) };
-        FBox::new(view).expect("Failed to copy View")
+/// Coordinate mapping
+impl View {
+    /// Get the projection transform of the view
+    ///
+    /// This maps world coordinates to normalized device coordinates,
+    /// where the visible area spans `[-1, 1]` on both axes, with Y pointing up.
+    #[must_use]
+    pub fn transform(&self) -> Transform {
+        let angle = self.rotation.to_radians();
+        let (sine, cosine) = angle.sin_cos();
+        let Vector2f { x: cx, y: cy } = self.center;
+        let tx = -cx * cosine - cy * sine + cx;
+        let ty = cx * sine - cy * cosine + cy;
+        let a = 2. / self.size.x;
+        let b = -2. / self.size.y;
+        let c = -a * cx;
+        let d = -b * cy;
+        Transform::new(
+            a * cosine,
+            a * sine,
+            a * tx + c,
+            -b * sine,
+            b * cosine,
+            b * ty + d,
+            0.,
+            0.,
+            1.,
+        )
     }
-}
 
-impl RawDefault for View {
-    fn raw_default() -> NonNull<Self> {
-        NonNull::new(unsafe { ffi::sfView_new() }).expect("Failed to create view")
+    /// Get the inverse of the projection transform of the view
+    ///
+    /// This maps normalized device coordinates back to world coordinates.
+    #[must_use]
+    pub fn inverse_transform(&self) -> Transform {
+        self.transform().inverse()
+    }
+
+    /// Get the viewport of the view in pixels, for a render target of size `target_size`
+    ///
+    /// This is what [`RenderTarget::viewport`] returns.
+    ///
+    /// [`RenderTarget::viewport`]: crate::graphics::RenderTarget::viewport
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Same rounding as SFML's getViewport"
+    )]
+    pub fn viewport_pixels(&self, target_size: Vector2u) -> IntRect {
+        let width = target_size.x as f32;
+        let height = target_size.y as f32;
+        let vp = self.viewport;
+        IntRect::new(
+            (0.5 + width * vp.left) as i32,
+            (0.5 + height * vp.top) as i32,
+            (0.5 + width * vp.width) as i32,
+            (0.5 + height * vp.height) as i32,
+        )
     }
-}
 
-impl Drop for View {
-    fn drop(&mut self) {
-        unsafe { ffi::sfView_del(self) }
+    /// Convert a pixel of a render target of size `target_size` to world coordinates
+    ///
+    /// This gives the same result as [`RenderTarget::map_pixel_to_coords`] with this view,
+    /// but doesn't need the render target.
+    ///
+    /// [`RenderTarget::map_pixel_to_coords`]: crate::graphics::RenderTarget::map_pixel_to_coords
+    #[must_use]
+    pub fn pixel_to_world(&self, pixel: Vector2i, target_size: Vector2u) -> Vector2f {
+        let vp = self.viewport_pixels(target_size).as_other::<f32>();
+        let pixel = pixel.as_other::<f32>();
+        let normalized = Vector2f::new(
+            -1. + 2. * (pixel.x - vp.left) / vp.width,
+            1. - 2. * (pixel.y - vp.top) / vp.height,
+        );
+        self.inverse_transform().transform_point(normalized)
+    }
+
+    /// Convert a point in world coordinates to a pixel of a render target of size `target_size`
+    ///
+    /// This gives the same result as [`RenderTarget::map_coords_to_pixel`] with this view,
+    /// but doesn't need the render target.
+    ///
+    /// [`RenderTarget::map_coords_to_pixel`]: crate::graphics::RenderTarget::map_coords_to_pixel
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Same rounding as SFML's mapCoordsToPixel"
+    )]
+    pub fn world_to_pixel(&self, point: Vector2f, target_size: Vector2u) -> Vector2i {
+        let vp = self.viewport_pixels(target_size).as_other::<f32>();
+        let normalized = self.transform().transform_point(point);
+        Vector2i::new(
+            ((normalized.x + 1.) / 2. * vp.width + vp.left) as i32,
+            ((-normalized.y + 1.) / 2. * vp.height + vp.top) as i32,
+        )
     }
 }
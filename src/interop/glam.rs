@@ -0,0 +1,122 @@
+use crate::{
+    graphics::{Color, FloatRect, Transform, glsl},
+    system::{Vector2, Vector3},
+};
+
+macro_rules! impl_vec {
+    ($($sf:ty => $glam:ty { $($field:ident),* },)*) => {
+        $(
+            impl From<$sf> for $glam {
+                fn from(v: $sf) -> Self {
+                    Self::new($(v.$field),*)
+                }
+            }
+
+            impl From<$glam> for $sf {
+                fn from(v: $glam) -> Self {
+                    Self { $($field: v.$field),* }
+                }
+            }
+        )*
+    };
+}
+
+impl_vec! {
+    Vector2<f32> => ::glam::Vec2 { x, y },
+    Vector2<i32> => ::glam::IVec2 { x, y },
+    Vector2<u32> => ::glam::UVec2 { x, y },
+    Vector2<bool> => ::glam::BVec2 { x, y },
+    Vector3<f32> => ::glam::Vec3 { x, y, z },
+    Vector3<i32> => ::glam::IVec3 { x, y, z },
+    Vector3<u32> => ::glam::UVec3 { x, y, z },
+    Vector3<bool> => ::glam::BVec3 { x, y, z },
+    glsl::Vec4 => ::glam::Vec4 { x, y, z, w },
+    glsl::IVec4 => ::glam::IVec4 { x, y, z, w },
+    glsl::BVec4 => ::glam::BVec4 { x, y, z, w },
+}
+
+impl From<glsl::Mat3> for ::glam::Mat3 {
+    fn from(m: glsl::Mat3) -> Self {
+        Self::from_cols_array(&m.0)
+    }
+}
+
+impl From<::glam::Mat3> for glsl::Mat3 {
+    fn from(m: ::glam::Mat3) -> Self {
+        Self(m.to_cols_array())
+    }
+}
+
+impl From<glsl::Mat4> for ::glam::Mat4 {
+    fn from(m: glsl::Mat4) -> Self {
+        Self::from_cols_array(&m.0)
+    }
+}
+
+impl From<::glam::Mat4> for glsl::Mat4 {
+    fn from(m: ::glam::Mat4) -> Self {
+        Self(m.to_cols_array())
+    }
+}
+
+impl From<Transform> for ::glam::Mat3 {
+    fn from(t: Transform) -> Self {
+        Self::from_cols_array(&t.matrix3())
+    }
+}
+
+impl From<::glam::Mat3> for Transform {
+    fn from(m: ::glam::Mat3) -> Self {
+        glsl::Mat3(m.to_cols_array()).into()
+    }
+}
+
+impl From<Transform> for ::glam::Mat4 {
+    fn from(t: Transform) -> Self {
+        Self::from_cols_array(t.matrix())
+    }
+}
+
+impl From<Transform> for ::glam::Affine2 {
+    /// Convert the affine part of the transform
+    fn from(t: Transform) -> Self {
+        let m = t.matrix();
+        Self::from_cols_array(&[m[0], m[1], m[4], m[5], m[12], m[13]])
+    }
+}
+
+impl From<::glam::Affine2> for Transform {
+    fn from(a: ::glam::Affine2) -> Self {
+        let [a00, a10, a01, a11, a02, a12] = a.to_cols_array();
+        Self::new(a00, a01, a02, a10, a11, a12, 0., 0., 1.)
+    }
+}
+
+impl From<Color> for ::glam::Vec4 {
+    /// Convert to normalized components
+    fn from(c: Color) -> Self {
+        glsl::Vec4::from(c).into()
+    }
+}
+
+impl From<::glam::Vec4> for Color {
+    /// Convert from normalized components
+    fn from(v: ::glam::Vec4) -> Self {
+        Color::from_normalized(v.to_array())
+    }
+}
+
+impl From<FloatRect> for (::glam::Vec2, ::glam::Vec2) {
+    fn from(r: FloatRect) -> Self {
+        (
+            ::glam::Vec2::new(r.left, r.top),
+            ::glam::Vec2::new(r.width, r.height),
+        )
+    }
+}
+
+impl From<(::glam::Vec2, ::glam::Vec2)> for FloatRect {
+    fn from((pos, size): (::glam::Vec2, ::glam::Vec2)) -> Self {
+        Self::new(pos.x, pos.y, size.x, size.y)
+    }
+}
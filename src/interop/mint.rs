@@ -0,0 +1,193 @@
+use crate::{
+    graphics::{Color, Rect, Transform, glsl},
+    system::{Vector2, Vector3},
+};
+
+impl<T> From<Vector2<T>> for ::mint::Vector2<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl<T> From<::mint::Vector2<T>> for Vector2<T> {
+    fn from(v: ::mint::Vector2<T>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl<T> From<Vector2<T>> for ::mint::Point2<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl<T> From<::mint::Point2<T>> for Vector2<T> {
+    fn from(v: ::mint::Point2<T>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl<T> From<Vector3<T>> for ::mint::Vector3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl<T> From<::mint::Vector3<T>> for Vector3<T> {
+    fn from(v: ::mint::Vector3<T>) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl<T> From<Vector3<T>> for ::mint::Point3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl<T> From<::mint::Point3<T>> for Vector3<T> {
+    fn from(v: ::mint::Point3<T>) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+macro_rules! impl_vec4 {
+    ($($glsl:ident => $t:ty,)*) => {
+        $(
+            impl From<glsl::$glsl> for ::mint::Vector4<$t> {
+                fn from(v: glsl::$glsl) -> Self {
+                    Self {
+                        x: v.x,
+                        y: v.y,
+                        z: v.z,
+                        w: v.w,
+                    }
+                }
+            }
+
+            impl From<::mint::Vector4<$t>> for glsl::$glsl {
+                fn from(v: ::mint::Vector4<$t>) -> Self {
+                    Self {
+                        x: v.x,
+                        y: v.y,
+                        z: v.z,
+                        w: v.w,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_vec4! {
+    Vec4 => f32,
+    IVec4 => i32,
+    BVec4 => bool,
+}
+
+impl From<glsl::Mat3> for ::mint::ColumnMatrix3<f32> {
+    fn from(m: glsl::Mat3) -> Self {
+        m.0.into()
+    }
+}
+
+impl From<::mint::ColumnMatrix3<f32>> for glsl::Mat3 {
+    fn from(m: ::mint::ColumnMatrix3<f32>) -> Self {
+        Self(m.into())
+    }
+}
+
+impl From<glsl::Mat4> for ::mint::ColumnMatrix4<f32> {
+    fn from(m: glsl::Mat4) -> Self {
+        m.0.into()
+    }
+}
+
+impl From<::mint::ColumnMatrix4<f32>> for glsl::Mat4 {
+    fn from(m: ::mint::ColumnMatrix4<f32>) -> Self {
+        Self(m.into())
+    }
+}
+
+impl From<Transform> for ::mint::ColumnMatrix3<f32> {
+    fn from(t: Transform) -> Self {
+        t.matrix3().into()
+    }
+}
+
+impl From<::mint::ColumnMatrix3<f32>> for Transform {
+    fn from(m: ::mint::ColumnMatrix3<f32>) -> Self {
+        glsl::Mat3::from(m).into()
+    }
+}
+
+impl From<Transform> for ::mint::ColumnMatrix4<f32> {
+    fn from(t: Transform) -> Self {
+        (*t.matrix()).into()
+    }
+}
+
+impl From<::mint::ColumnMatrix4<f32>> for Transform {
+    fn from(m: ::mint::ColumnMatrix4<f32>) -> Self {
+        glsl::Mat4::from(m).into()
+    }
+}
+
+impl From<Color> for ::mint::Vector4<u8> {
+    fn from(c: Color) -> Self {
+        Self {
+            x: c.r,
+            y: c.g,
+            z: c.b,
+            w: c.a,
+        }
+    }
+}
+
+impl From<::mint::Vector4<u8>> for Color {
+    fn from(v: ::mint::Vector4<u8>) -> Self {
+        Self::rgba(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl<T> From<Rect<T>> for (::mint::Point2<T>, ::mint::Vector2<T>) {
+    fn from(r: Rect<T>) -> Self {
+        (
+            ::mint::Point2 {
+                x: r.left,
+                y: r.top,
+            },
+            ::mint::Vector2 {
+                x: r.width,
+                y: r.height,
+            },
+        )
+    }
+}
+
+impl<T> From<(::mint::Point2<T>, ::mint::Vector2<T>)> for Rect<T> {
+    fn from((pos, size): (::mint::Point2<T>, ::mint::Vector2<T>)) -> Self {
+        Self {
+            left: pos.x,
+            top: pos.y,
+            width: size.x,
+            height: size.y,
+        }
+    }
+}
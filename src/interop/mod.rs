@@ -0,0 +1,41 @@
+//! Conversions between sf2g's math types and other math libraries.
+//!
+//! Each library is behind a cargo feature of the same name:
+//!
+//! - `mint`: [mint](https://docs.rs/mint) types, the common bridge supported by most
+//!   math libraries
+//! - `glam`: [glam](https://docs.rs/glam) types
+//! - `nalgebra`: [nalgebra](https://docs.rs/nalgebra) types
+//!
+//! The conversions are provided as [`From`] implementations:
+//!
+//! | sf2g                               | mint                          | glam                 | nalgebra                 |
+//! |------------------------------------|-------------------------------|----------------------|--------------------------|
+//! | [`Vector2<T>`]                     | `Vector2<T>`, `Point2<T>`     | `Vec2`, `IVec2`, `UVec2`, `BVec2` | `Vector2<T>`, `Point2<T>` |
+//! | [`Vector3<T>`]                     | `Vector3<T>`, `Point3<T>`     | `Vec3`, `IVec3`, `UVec3`, `BVec3` | `Vector3<T>`, `Point3<T>` |
+//! | [`glsl::Vec4`], [`glsl::IVec4`], [`glsl::BVec4`] | `Vector4<T>`    | `Vec4`, `IVec4`, `BVec4` | `Vector4<T>`         |
+//! | [`glsl::Mat3`], [`glsl::Mat4`]     | `ColumnMatrix3`, `ColumnMatrix4` | `Mat3`, `Mat4`    | `Matrix3`, `Matrix4`     |
+//! | [`Transform`]                      | `ColumnMatrix3`, `ColumnMatrix4` | `Mat3`, `Affine2`, `Mat4` (from only) | `Matrix3`, `Affine2`, `Matrix4` (from only) |
+//! | [`Color`]                          | `Vector4<u8>`                 | `Vec4` (normalized)  | `Vector4<f32>` (normalized) |
+//! | [`Rect<T>`]                        | `(Point2<T>, Vector2<T>)` (position, size) | `(Vec2, Vec2)` | `(Point2<T>, Vector2<T>)` |
+//!
+//! Converting a normalized color back to [`Color`] clamps the components to `[0, 1]`
+//! and rounds them.
+//!
+//! [`Vector2<T>`]: crate::system::Vector2
+//! [`Vector3<T>`]: crate::system::Vector3
+//! [`glsl::Vec4`]: crate::graphics::glsl::Vec4
+//! [`glsl::IVec4`]: crate::graphics::glsl::IVec4
+//! [`glsl::BVec4`]: crate::graphics::glsl::BVec4
+//! [`glsl::Mat3`]: crate::graphics::glsl::Mat3
+//! [`glsl::Mat4`]: crate::graphics::glsl::Mat4
+//! [`Transform`]: crate::graphics::Transform
+//! [`Color`]: crate::graphics::Color
+//! [`Rect<T>`]: crate::graphics::Rect
+
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
@@ -0,0 +1,181 @@
+use {
+    crate::{
+        graphics::{Color, Rect, Transform, glsl},
+        system::{Vector2, Vector3},
+    },
+    ::nalgebra::Scalar,
+};
+
+impl<T: Scalar> From<Vector2<T>> for ::nalgebra::Vector2<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
+impl<T: Scalar> From<::nalgebra::Vector2<T>> for Vector2<T> {
+    fn from(v: ::nalgebra::Vector2<T>) -> Self {
+        let [[x, y]] = v.data.0;
+        Self { x, y }
+    }
+}
+
+impl<T: Scalar> From<Vector2<T>> for ::nalgebra::Point2<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
+impl<T: Scalar> From<::nalgebra::Point2<T>> for Vector2<T> {
+    fn from(p: ::nalgebra::Point2<T>) -> Self {
+        p.coords.into()
+    }
+}
+
+impl<T: Scalar> From<Vector3<T>> for ::nalgebra::Vector3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Scalar> From<::nalgebra::Vector3<T>> for Vector3<T> {
+    fn from(v: ::nalgebra::Vector3<T>) -> Self {
+        let [[x, y, z]] = v.data.0;
+        Self { x, y, z }
+    }
+}
+
+impl<T: Scalar> From<Vector3<T>> for ::nalgebra::Point3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: Scalar> From<::nalgebra::Point3<T>> for Vector3<T> {
+    fn from(p: ::nalgebra::Point3<T>) -> Self {
+        p.coords.into()
+    }
+}
+
+macro_rules! impl_vec4 {
+    ($($glsl:ident => $t:ty,)*) => {
+        $(
+            impl From<glsl::$glsl> for ::nalgebra::Vector4<$t> {
+                fn from(v: glsl::$glsl) -> Self {
+                    Self::new(v.x, v.y, v.z, v.w)
+                }
+            }
+
+            impl From<::nalgebra::Vector4<$t>> for glsl::$glsl {
+                fn from(v: ::nalgebra::Vector4<$t>) -> Self {
+                    let [[x, y, z, w]] = v.data.0;
+                    Self { x, y, z, w }
+                }
+            }
+        )*
+    };
+}
+
+impl_vec4! {
+    Vec4 => f32,
+    IVec4 => i32,
+    BVec4 => bool,
+}
+
+impl From<glsl::Mat3> for ::nalgebra::Matrix3<f32> {
+    fn from(m: glsl::Mat3) -> Self {
+        Self::from_column_slice(&m.0)
+    }
+}
+
+impl From<::nalgebra::Matrix3<f32>> for glsl::Mat3 {
+    fn from(m: ::nalgebra::Matrix3<f32>) -> Self {
+        let mut cols = [0.; 9];
+        cols.copy_from_slice(m.as_slice());
+        Self(cols)
+    }
+}
+
+impl From<glsl::Mat4> for ::nalgebra::Matrix4<f32> {
+    fn from(m: glsl::Mat4) -> Self {
+        Self::from_column_slice(&m.0)
+    }
+}
+
+impl From<::nalgebra::Matrix4<f32>> for glsl::Mat4 {
+    fn from(m: ::nalgebra::Matrix4<f32>) -> Self {
+        let mut cols = [0.; 16];
+        cols.copy_from_slice(m.as_slice());
+        Self(cols)
+    }
+}
+
+impl From<Transform> for ::nalgebra::Matrix3<f32> {
+    fn from(t: Transform) -> Self {
+        Self::from_column_slice(&t.matrix3())
+    }
+}
+
+impl From<::nalgebra::Matrix3<f32>> for Transform {
+    fn from(m: ::nalgebra::Matrix3<f32>) -> Self {
+        glsl::Mat3::from(m).into()
+    }
+}
+
+impl From<Transform> for ::nalgebra::Matrix4<f32> {
+    fn from(t: Transform) -> Self {
+        Self::from_column_slice(t.matrix())
+    }
+}
+
+impl From<Transform> for ::nalgebra::Affine2<f32> {
+    /// Convert the affine part of the transform
+    fn from(t: Transform) -> Self {
+        let m = t.matrix();
+        Self::from_matrix_unchecked(::nalgebra::Matrix3::new(
+            m[0], m[4], m[12], m[1], m[5], m[13], 0., 0., 1.,
+        ))
+    }
+}
+
+impl From<::nalgebra::Affine2<f32>> for Transform {
+    fn from(a: ::nalgebra::Affine2<f32>) -> Self {
+        a.into_inner().into()
+    }
+}
+
+impl From<Color> for ::nalgebra::Vector4<f32> {
+    /// Convert to normalized components
+    fn from(c: Color) -> Self {
+        glsl::Vec4::from(c).into()
+    }
+}
+
+impl From<::nalgebra::Vector4<f32>> for Color {
+    /// Convert from normalized components
+    fn from(v: ::nalgebra::Vector4<f32>) -> Self {
+        let [c] = v.data.0;
+        Color::from_normalized(c)
+    }
+}
+
+impl<T: Scalar> From<Rect<T>> for (::nalgebra::Point2<T>, ::nalgebra::Vector2<T>) {
+    fn from(r: Rect<T>) -> Self {
+        (
+            ::nalgebra::Point2::new(r.left, r.top),
+            ::nalgebra::Vector2::new(r.width, r.height),
+        )
+    }
+}
+
+impl<T: Scalar> From<(::nalgebra::Point2<T>, ::nalgebra::Vector2<T>)> for Rect<T> {
+    fn from((pos, size): (::nalgebra::Point2<T>, ::nalgebra::Vector2<T>)) -> Self {
+        let [[left, top]] = pos.coords.data.0;
+        let [[width, height]] = size.data.0;
+        Self {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+}
@@ -22,6 +22,9 @@
 )]
 
 extern crate link_cplusplus;
+// Lets the code generated by `sf2g-derive` find the crate in its own tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as sf2g;
 
 #[macro_use]
 mod ffi;
@@ -29,17 +32,48 @@ mod ffi;
 pub mod cpp;
 
 pub mod graphics;
+#[cfg(any(feature = "mint", feature = "glam", feature = "nalgebra"))]
+pub mod interop;
+#[cfg(feature = "serde")]
+mod serde_impls;
 pub mod system;
 pub mod window;
 use std::{error::Error, fmt::Display};
 
 /// An SFML operation has failed
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum SfError {
     /// An string argument passed had interior nul bytes
     NulInStr,
     /// Call to SFML function returned an error
     CallFailed,
+    /// Call to SFML function returned an error, and SFML reported why
+    ///
+    /// Contains the last message SFML emitted through `sf::err()` on the calling thread.
+    /// See [`system::err`].
+    CallFailedWithMessage(String),
+}
+
+impl SfError {
+    /// Attach the last message SFML emitted on this thread to a failed call, if it was
+    /// emitted after `since` messages, that is, during the call.
+    fn with_message_since(self, since: u64) -> Self {
+        match self {
+            Self::CallFailed if system::err::message_count() > since => {
+                system::err::take_last_message()
+                    .map_or(Self::CallFailed, Self::CallFailedWithMessage)
+            }
+            other => other,
+        }
+    }
+    /// The message SFML reported for this error, if any
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Self::CallFailedWithMessage(msg) => Some(msg),
+            Self::NulInStr | Self::CallFailed => None,
+        }
+    }
 }
 
 impl Display for SfError {
@@ -47,6 +81,9 @@ impl Display for SfError {
         match self {
             SfError::NulInStr => write!(f, "Interior nul byte in string passed to SFML"),
             SfError::CallFailed => write!(f, "Call to SFML function returned an error"),
+            SfError::CallFailedWithMessage(msg) => {
+                write!(f, "Call to SFML function returned an error: {msg}")
+            }
         }
     }
 }
@@ -81,3 +118,13 @@ impl<T: ?Sized> IntoSfResult<cpp::FBox<T>> for Option<cpp::FBox<T>> {
         self.ok_or(SfError::CallFailed)
     }
 }
+
+/// Make a fallible call into SFML.
+///
+/// If the call fails, the error carries the message SFML emitted during the call, if any.
+fn sf_call<T, R: IntoSfResult<T>>(call: impl FnOnce() -> R) -> SfResult<T> {
+    let since = system::err::message_count();
+    call()
+        .into_sf_result()
+        .map_err(|err| err.with_message_since(since))
+}
@@ -0,0 +1,83 @@
+//! `Serialize` and `Deserialize` implementations that can't be derived.
+//!
+//! - [`Color`] is a `"#rrggbbaa"` hex string in human-readable formats (any string accepted
+//!   by its [`FromStr`](std::str::FromStr) implementation is also accepted when deserializing),
+//!   and a `u32` in RGBA order otherwise.
+//! - [`Time`] is an integer number of microseconds.
+//! - [`Transform`] is its 3x3 matrix, as an array of 3 rows.
+
+use {
+    crate::{
+        graphics::{Color, Transform},
+        system::Time,
+    },
+    serde::{Deserialize, Deserializer, Serialize, Serializer, de},
+    std::fmt,
+};
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let Self { r, g, b, a } = *self;
+            serializer.collect_str(&format_args!("#{r:02x}{g:02x}{b:02x}{a:02x}"))
+        } else {
+            serializer.serialize_u32((*self).into())
+        }
+    }
+}
+
+struct ColorVisitor;
+
+impl de::Visitor<'_> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a CSS color string, or a u32 in RGBA order")
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Color, E> {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Color, E> {
+        u32::try_from(v)
+            .map(Color::from)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ColorVisitor)
+        } else {
+            deserializer.deserialize_u32(ColorVisitor)
+        }
+    }
+}
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.as_microseconds())
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i64::deserialize(deserializer).map(Time::microseconds)
+    }
+}
+
+impl Serialize for Transform {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [a00, a10, a20, a01, a11, a21, a02, a12, a22] = self.matrix3();
+        [[a00, a01, a02], [a10, a11, a12], [a20, a21, a22]].serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Transform {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [[a00, a01, a02], [a10, a11, a12], [a20, a21, a22]] =
+            <[[f32; 3]; 3]>::deserialize(deserializer)?;
+        Ok(Self::new(a00, a01, a02, a10, a11, a12, a20, a21, a22))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{IntoSfResult as _, SfResult, cpp::FBox, ffi::system as ffi, system::Time};
+use crate::{SfResult, cpp::FBox, ffi::system as ffi, sf_call, system::Time};
 
 decl_opaque! {
     /// Utility type that measures the elapsed time.
@@ -34,7 +34,7 @@ impl Drop for Clock {
 impl Clock {
     /// Creates a new Clock and starts it automatically.
     pub fn start() -> SfResult<FBox<Self>> {
-        unsafe { FBox::new(ffi::sfClock_new()) }.into_sf_result()
+        sf_call(|| unsafe { FBox::new(ffi::sfClock_new()) })
     }
 
     /// Gets the elapsed time.
@@ -0,0 +1,147 @@
+//! Access to SFML's error output stream (`sf::err()`).
+//!
+//! SFML reports diagnostics like failed texture creation, OpenGL context warnings or
+//! shader compilation errors through `sf::err()`, which writes to the standard error output
+//! by default.
+//!
+//! sf2g captures these messages, so they can be routed somewhere else with [`set_handler`],
+//! or into the [`log`](https://docs.rs/log) crate with `redirect_to_log` (requires the `log`
+//! feature).
+//!
+//! Regardless of where the messages go, the last message a failing SFML call emitted is
+//! attached to the [`SfError`](crate::SfError) it returns.
+
+use {
+    crate::{cpp::FBox, ffi::system as ffi},
+    std::{
+        ffi::c_char,
+        sync::{Arc, RwLock},
+    },
+};
+
+/// Severity of a message emitted by SFML.
+///
+/// SFML itself doesn't categorize its messages, so the level is guessed from the wording
+/// of the message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Something failed, like creating a texture or compiling a shader
+    Error,
+    /// Something is unsupported or degraded, but the operation still went ahead
+    Warn,
+    /// Informational message
+    Info,
+}
+
+impl Level {
+    /// Guess the severity of a message emitted by SFML.
+    #[must_use]
+    pub fn of_message(message: &str) -> Self {
+        const ERROR_PREFIXES: &[&str] = &[
+            "Failed",
+            "Error",
+            "An internal",
+            "Impossible",
+            "Cannot",
+            "Could not",
+            "Couldn't",
+            "Unable",
+        ];
+        const WARN_MARKERS: &[&str] = &[
+            "warning",
+            "not supported",
+            "unavailable",
+            "not available",
+            "disabled",
+            "may occur",
+            "switching to",
+            "falling back",
+            "retrying",
+        ];
+        if ERROR_PREFIXES.iter().any(|pfx| message.starts_with(pfx)) {
+            return Self::Error;
+        }
+        let lower = message.to_ascii_lowercase();
+        if WARN_MARKERS.iter().any(|marker| lower.contains(marker)) {
+            Self::Warn
+        } else {
+            Self::Info
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<Level> for log::Level {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => Self::Error,
+            Level::Warn => Self::Warn,
+            Level::Info => Self::Info,
+        }
+    }
+}
+
+type Handler = Arc<dyn Fn(Level, &str) + Send + Sync>;
+
+static HANDLER: RwLock<Option<Handler>> = RwLock::new(None);
+
+unsafe extern "C" fn dispatch(message: *const c_char, len: usize) {
+    let bytes = unsafe { std::slice::from_raw_parts(message.cast::<u8>(), len) };
+    let message = String::from_utf8_lossy(bytes);
+    // Clone the handler out, so it can call back into SFML without deadlocking
+    let handler = HANDLER
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    if let Some(handler) = handler {
+        handler(Level::of_message(&message), &message);
+    }
+}
+
+/// Route messages emitted by SFML to `handler`, instead of the standard error output.
+///
+/// The handler can be called from any thread that SFML emits a message on.
+pub fn set_handler<F>(handler: F)
+where
+    F: Fn(Level, &str) + Send + Sync + 'static,
+{
+    *HANDLER
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(Arc::new(handler));
+    unsafe { ffi::sfErr_setCallback(Some(dispatch)) }
+}
+
+/// Remove the handler set by [`set_handler`], and print messages to the standard error
+/// output again.
+pub fn reset_handler() {
+    unsafe { ffi::sfErr_setCallback(None) }
+    *HANDLER
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+}
+
+/// Route messages emitted by SFML into the [`log`](https://docs.rs/log) crate.
+///
+/// Messages are logged with the `sf2g::sfml` target, at the level given by
+/// [`Level::of_message`].
+#[cfg(feature = "log")]
+pub fn redirect_to_log() {
+    set_handler(|level, message| {
+        log::log!(target: "sf2g::sfml", level.into(), "{message}");
+    });
+}
+
+/// Take the last message SFML emitted on the current thread, if any.
+///
+/// Failing SFML calls take the message they emitted to attach it to their
+/// [`SfError`](crate::SfError), so this is mainly useful after calls that don't report failure.
+#[must_use]
+pub fn take_last_message() -> Option<String> {
+    let msg = FBox::new(unsafe { ffi::sfErr_takeLastMessage() })?;
+    Some(msg.to_string())
+}
+
+/// Number of messages SFML emitted on the current thread so far
+pub(crate) fn message_count() -> u64 {
+    unsafe { ffi::sfErr_messageCount() }
+}
@@ -13,6 +13,7 @@ pub use self::{
 };
 
 mod clock;
+pub mod err;
 mod sleep;
 mod string;
 mod time;
@@ -34,6 +34,8 @@ use {
 /// assert_eq!(t3.as_seconds(), -0.8);
 /// ```
 ///
+/// With the `serde` feature, `Time` is serialized as an integer number of microseconds.
+///
 /// # See also
 /// - [`Clock`]
 ///
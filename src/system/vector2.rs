@@ -41,6 +41,7 @@ use {
 /// [`Vector3`]: crate::system::Vector3
 #[repr(C)]
 #[derive(Clone, PartialEq, Eq, Debug, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector2<T> {
     /// X coordinate of the vector.
     pub x: T,
@@ -75,8 +76,8 @@ impl<T> Vector2<T> {
     /// # use sf2g::system::Vector2;
     /// let vu: Vector2<u16> = Vector2::new(6969, 6969);
     /// let vi: Vector2<i32> = vu.into_other();
-    /// assert_eq!(vu.x, vi.x.try_into().unwrap());
-    /// assert_eq!(vu.y, vu.y.try_into().unwrap());
+    /// assert_eq!(vu.x, u16::try_from(vi.x).unwrap());
+    /// assert_eq!(vu.y, u16::try_from(vi.y).unwrap());
     /// ```
     pub fn into_other<U>(self) -> Vector2<U>
     where
@@ -42,6 +42,7 @@ use {
 /// [`Vector2`]: crate::system::Vector2
 #[repr(C)]
 #[derive(Clone, PartialEq, Eq, Debug, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector3<T> {
     /// X coordinate of the vector.
     pub x: T,
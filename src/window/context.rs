@@ -1,5 +1,5 @@
 use {
-    crate::{IntoSfResult, SfResult, cpp::FBox, ffi::window as ffi, window::ContextSettings},
+    crate::{SfResult, cpp::FBox, ffi::window as ffi, sf_call, window::ContextSettings},
     std::ffi::CStr,
 };
 
@@ -23,7 +23,7 @@ pub Context;
 impl Context {
     /// Creates and activates a new context.
     pub fn new() -> SfResult<FBox<Self>> {
-        FBox::new(unsafe { ffi::sfContext_new() }).into_sf_result()
+        sf_call(|| FBox::new(unsafe { ffi::sfContext_new() }))
     }
 
     /// Explicitly activates or deactivates the context.
@@ -31,7 +31,7 @@ impl Context {
     /// # Arguments
     /// * active - `true` to activate, `false` to deactivate
     pub fn set_active(&mut self, active: bool) -> SfResult<()> {
-        unsafe { ffi::sfContext_setActive(self, active) }.into_sf_result()
+        sf_call(|| unsafe { ffi::sfContext_setActive(self, active) })
     }
     /// Get the settings of the context.
     ///
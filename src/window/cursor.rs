@@ -1,7 +1,8 @@
 use crate::{
-    IntoSfResult, SfResult,
+    SfResult,
     cpp::FBox,
     ffi::window::{self as ffi, sfCursor_loadFromPixels, sfCursor_loadFromSystem, sfCursor_new},
+    sf_call,
     system::Vector2u,
 };
 
@@ -37,7 +38,7 @@ impl Cursor {
     ///
     /// Must be inited with [`Self::load_from_pixels`] or [`Self::load_from_system`]
     unsafe fn new() -> SfResult<FBox<Self>> {
-        FBox::new(unsafe { sfCursor_new() }).into_sf_result()
+        sf_call(|| FBox::new(unsafe { sfCursor_new() }))
     }
     /// Create a new `Cursor` from the provided image data.
     ///
@@ -111,7 +112,7 @@ impl Cursor {
         size: Vector2u,
         hotspot: Vector2u,
     ) -> SfResult<()> {
-        unsafe { sfCursor_loadFromPixels(self, pixels.as_ptr(), size, hotspot) }.into_sf_result()
+        sf_call(|| unsafe { sfCursor_loadFromPixels(self, pixels.as_ptr(), size, hotspot) })
     }
     /// Load a native system cursor.
     ///
@@ -124,7 +125,7 @@ impl Cursor {
     ///
     /// Returns an error if the cursor type is not supported by the operating system.
     pub fn load_from_system(&mut self, type_: Type) -> SfResult<()> {
-        unsafe { sfCursor_loadFromSystem(self, type_) }.into_sf_result()
+        sf_call(|| unsafe { sfCursor_loadFromSystem(self, type_) })
     }
 }
 
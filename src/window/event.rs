@@ -15,6 +15,7 @@ use crate::ffi::{window as ffi, window::EventType};
 /// [`Window::wait_event`]: crate::window::Window::wait_event
 ///
 #[derive(Clone, PartialEq, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// The window requested to be closed
     Closed,
@@ -4,6 +4,7 @@ bitflags::bitflags! {
     /// Available styles applicable to windows.
     #[repr(C)]
     #[derive(Clone, Copy, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Style: u32 {
         /// No decorations (cannot be combined with other flags).
         const NONE = 0;
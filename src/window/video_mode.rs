@@ -25,6 +25,7 @@ use crate::{
 /// pixel depth as the current resolution.
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VideoMode {
     /// Video mode width, in pixels
     pub width: std::ffi::c_uint,
@@ -1,7 +1,8 @@
 use crate::{
-    IntoSfResult, SfResult,
+    SfResult,
     cpp::FBox,
     ffi::window as ffi,
+    sf_call,
     system::{SfStrConv, Vector2i, Vector2u},
     window::{ContextSettings, Cursor, Event, Style, VideoMode, thread_safety},
 };
@@ -31,7 +32,7 @@ decl_opaque! {
 impl Window {
     /// Create a new (closed) window.
     pub fn new() -> SfResult<FBox<Self>> {
-        FBox::new(unsafe { ffi::sfWindow_new() }).into_sf_result()
+        sf_call(|| FBox::new(unsafe { ffi::sfWindow_new() }))
     }
     /// Creates a new window and opens it with the specified parameters.
     ///